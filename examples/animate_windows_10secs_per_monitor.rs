@@ -462,7 +462,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .collect();
 
             monitor_animation_jobs.push(animation_data);
-            monitor_rects.push(monitor_rect.to_rect());
+            monitor_rects.push(monitor_rect.into());
             monitor_cols_rows.push((cols, rows));
         }
         drop(tracker_guard); // Release immutable borrow before mutable borrow
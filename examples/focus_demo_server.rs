@@ -200,6 +200,10 @@ impl FocusDemoServer {
             monitor_col_start: 0,
             monitor_row_end: 1,
             monitor_col_end: 1,
+            group_id: 0,
+            group_index: 0,
+            group_size: 0,
+            group_active: 0,
         };
 
         if let Err(e) = self.details_publisher.send_copy(details) {
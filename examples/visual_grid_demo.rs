@@ -302,6 +302,7 @@ impl VisualGridDemo {
         if let Ok(mut tracker) = self.tracker.lock() {
             // Update config first
             tracker.config = self.target_config.clone();
+            tracker.update_virtual_config();
 
             // Calculate new grid positions for each window
             let target_rows = self.target_config.rows;
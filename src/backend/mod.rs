@@ -0,0 +1,137 @@
+// Window system backends
+//
+// Everything the tracker needs from the operating system goes through the
+// `WindowBackend` trait. `Win32Backend` talks to a live desktop, while
+// `SimulatedDesktop` keeps windows, monitors and z-order in memory so the grid,
+// layout and animation logic can be exercised without Windows.
+
+pub mod simulated;
+#[cfg(feature = "win32")]
+pub mod win32;
+
+pub use simulated::{SimulatedDesktop, SimulatedWindow};
+#[cfg(feature = "win32")]
+pub use win32::Win32Backend;
+
+use dashmap::DashMap;
+use std::collections::HashMap;
+
+use crate::config::{FilterDecision, WindowFilter, WindowTraits};
+use crate::geometry::{Rect, DEFAULT_DPI};
+use crate::window::info::WindowInfo;
+
+/// Full bounds, work area and DPI of one monitor, in virtual screen coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MonitorAreas {
+    pub bounds: Rect,
+    /// Bounds minus taskbars and docked app bars
+    pub work_area: Rect,
+    /// Effective DPI; 96 is 100% scaling
+    pub dpi: u32,
+}
+
+impl MonitorAreas {
+    /// A 100%-scaled monitor without any bars, whose work area is its full bounds.
+    pub fn full(bounds: Rect) -> Self {
+        Self {
+            bounds,
+            work_area: bounds,
+            dpi: DEFAULT_DPI,
+        }
+    }
+}
+
+/// How `WindowBackend::show_window` shows or puts away a window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowShowState {
+    /// Hidden entirely, without a taskbar button
+    Hidden,
+    /// Minimized to the taskbar
+    Minimized,
+    /// Visible and restored, without taking focus
+    Shown,
+    /// Visible, restored and brought to the foreground
+    Focused,
+}
+
+/// Abstraction over the windowing system used by `WindowTracker`.
+pub trait WindowBackend: Send + Sync {
+    /// Outer rectangle of a window in virtual screen coordinates.
+    fn get_window_rect(&self, hwnd: u64) -> Option<Rect>;
+
+    /// True if the window should be tracked and placed on the grid.
+    fn is_manageable_window(&self, hwnd: u64) -> bool {
+        self.explain_window(hwnd).accepted
+    }
+
+    /// Why the manageable-window filter accepts or rejects a window.
+    fn explain_window(&self, hwnd: u64) -> FilterDecision;
+
+    /// Replace the filter deciding which windows are manageable.
+    fn set_window_filter(&self, filter: WindowFilter);
+
+    /// Class, title, process and styles of a window as the filter sees them, or None
+    /// if it does not exist. The executable is only looked up if `with_executable`.
+    fn get_window_traits(&self, hwnd: u64, with_executable: bool) -> Option<WindowTraits>;
+
+    /// Move and resize a window without changing its z-order or activation.
+    fn move_window_to_rect(&self, hwnd: u64, rect: Rect) -> Result<(), String>;
+
+    /// Hide, minimize or show and restore a window.
+    fn show_window(&self, hwnd: u64, state: WindowShowState) -> Result<(), String>;
+
+    /// Every top-level window, topmost first, including hidden and unmanageable ones.
+    fn list_windows(&self) -> Vec<u64>;
+
+    /// Collect every manageable top-level window.
+    fn enumerate_windows(&self) -> DashMap<u64, WindowInfo>;
+
+    /// Map of HWND to z-order index (0 = topmost).
+    fn get_hwnd_z_order_map(&self) -> HashMap<u64, usize>;
+
+    /// Snapshot of a single window, or None if it does not exist.
+    fn get_window_info(&self, hwnd: u64) -> Option<WindowInfo>;
+
+    fn is_window(&self, hwnd: u64) -> bool;
+
+    fn is_window_visible(&self, hwnd: u64) -> bool;
+
+    fn is_window_maximized(&self, hwnd: u64) -> bool;
+
+    fn get_window_title(&self, hwnd: u64) -> String;
+
+    fn get_window_class_name(&self, hwnd: u64) -> String;
+
+    /// File name of the executable owning the window, e.g. "notepad.exe", or an
+    /// empty string if it can't be read.
+    fn get_window_executable(&self, hwnd: u64) -> String;
+
+    fn get_foreground_window(&self) -> Option<u64>;
+
+    /// Bounds of every physical monitor.
+    fn get_monitor_bounds(&self) -> Vec<Rect>;
+
+    /// Bounds and work area of every physical monitor, in the same order as
+    /// `get_monitor_bounds`. Backends without bars report the bounds as work area.
+    fn get_monitors(&self) -> Vec<MonitorAreas> {
+        self.get_monitor_bounds()
+            .into_iter()
+            .map(MonitorAreas::full)
+            .collect()
+    }
+
+    /// Desktop/shell windows (Progman, WorkerW, ...) that never occupy cells.
+    fn find_desktop_hwnds(&self) -> Vec<u64>;
+
+    /// Bounding rectangle of all monitors combined.
+    fn get_virtual_screen_rect(&self) -> Rect {
+        let monitors = self.get_monitor_bounds();
+        if monitors.is_empty() {
+            return Rect::default();
+        }
+        monitors
+            .iter()
+            .skip(1)
+            .fold(monitors[0], |acc, m| acc.union(m))
+    }
+}
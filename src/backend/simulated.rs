@@ -0,0 +1,425 @@
+// Simulated desktop - an in-memory window system for tests and headless runs
+
+use dashmap::DashMap;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::{MonitorAreas, WindowBackend, WindowShowState};
+use crate::config::window_filter::styles::{WS_EX_TOOLWINDOW, WS_OVERLAPPEDWINDOW};
+use crate::config::{FilterDecision, WindowFilter, WindowTraits};
+use crate::geometry::Rect;
+use crate::window::info::{RectWrapper, WindowInfo};
+
+/// A fake top-level window.
+#[derive(Clone)]
+pub struct SimulatedWindow {
+    pub hwnd: u64,
+    pub title: String,
+    pub class_name: String,
+    pub rect: Rect,
+    pub process_id: u32,
+    /// File name reported as the owning executable; empty by default
+    pub executable: String,
+    /// Win32 style bits; an ordinary overlapped window by default
+    pub style: u32,
+    pub is_visible: bool,
+    pub is_minimized: bool,
+    pub is_maximized: bool,
+    /// Tool windows are never manageable, just like WS_EX_TOOLWINDOW on Win32.
+    pub is_tool_window: bool,
+}
+
+impl SimulatedWindow {
+    pub fn new(hwnd: u64, title: &str, class_name: &str, rect: Rect) -> Self {
+        Self {
+            hwnd,
+            title: title.to_string(),
+            class_name: class_name.to_string(),
+            rect,
+            process_id: 0,
+            executable: String::new(),
+            style: WS_OVERLAPPEDWINDOW,
+            is_visible: true,
+            is_minimized: false,
+            is_maximized: false,
+            is_tool_window: false,
+        }
+    }
+
+    fn to_window_traits(&self) -> WindowTraits {
+        WindowTraits {
+            class_name: self.class_name.clone(),
+            title: self.title.clone(),
+            process_id: self.process_id,
+            executable: self.executable.clone(),
+            style: self.style,
+            ex_style: if self.is_tool_window {
+                WS_EX_TOOLWINDOW
+            } else {
+                0
+            },
+            is_visible: self.is_visible,
+            is_minimized: self.is_minimized,
+        }
+    }
+
+    fn to_window_info(&self) -> WindowInfo {
+        let mut title = [0u16; 256];
+        let mut title_len = 0u32;
+        for (i, c) in self.title.encode_utf16().take(256).enumerate() {
+            title[i] = c;
+            title_len += 1;
+        }
+        let mut class_name = [0u16; 256];
+        let mut class_name_len = 0u32;
+        for (i, c) in self.class_name.encode_utf16().take(256).enumerate() {
+            class_name[i] = c;
+            class_name_len += 1;
+        }
+        WindowInfo {
+            hwnd: self.hwnd,
+            title,
+            title_len,
+            window_rect: RectWrapper::from_rect(self.rect),
+            monitor_ids: [0usize; 8],
+            z_order: 0,
+            is_visible: self.is_visible,
+            is_minimized: self.is_minimized,
+            is_maximized: self.is_maximized,
+            process_id: self.process_id,
+            class_name,
+            class_name_len,
+        }
+    }
+}
+
+#[derive(Default)]
+struct SimulatedState {
+    windows: HashMap<u64, SimulatedWindow>,
+    z_order: Vec<u64>, // front (topmost) first
+    monitors: Vec<MonitorAreas>,
+    desktop_hwnds: Vec<u64>,
+    foreground: Option<u64>,
+    filter: WindowFilter,
+    next_hwnd: u64,
+}
+
+/// In-memory desktop holding fake windows, monitors and z-order.
+///
+/// Share it with a tracker through `Arc<SimulatedDesktop>` and keep a handle
+/// to drive window creation, moves and focus changes from a test.
+pub struct SimulatedDesktop {
+    state: Mutex<SimulatedState>,
+}
+
+impl SimulatedDesktop {
+    pub fn new(monitors: Vec<Rect>) -> Self {
+        Self {
+            state: Mutex::new(SimulatedState {
+                monitors: monitors.into_iter().map(MonitorAreas::full).collect(),
+                next_hwnd: 0x1000,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Convenience constructor for a single monitor at (0,0).
+    pub fn single_monitor(width: i32, height: i32) -> Self {
+        Self::new(vec![Rect::new(0, 0, width, height)])
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SimulatedState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Create a window on top of the z-order, focus it and return its HWND.
+    pub fn add_window(&self, title: &str, class_name: &str, rect: Rect) -> u64 {
+        let hwnd = {
+            let mut state = self.lock();
+            let hwnd = state.next_hwnd;
+            state.next_hwnd += 1;
+            hwnd
+        };
+        self.insert_window(SimulatedWindow::new(hwnd, title, class_name, rect));
+        hwnd
+    }
+
+    /// Insert a fully specified window on top of the z-order and focus it.
+    pub fn insert_window(&self, window: SimulatedWindow) {
+        let mut state = self.lock();
+        let hwnd = window.hwnd;
+        if hwnd >= state.next_hwnd {
+            state.next_hwnd = hwnd + 1;
+        }
+        state.z_order.retain(|h| *h != hwnd);
+        state.z_order.insert(0, hwnd);
+        state.windows.insert(hwnd, window);
+        state.foreground = Some(hwnd);
+    }
+
+    pub fn remove_window(&self, hwnd: u64) -> bool {
+        let mut state = self.lock();
+        state.z_order.retain(|h| *h != hwnd);
+        if state.foreground == Some(hwnd) {
+            state.foreground = state.z_order.first().copied();
+        }
+        state.windows.remove(&hwnd).is_some()
+    }
+
+    pub fn window(&self, hwnd: u64) -> Option<SimulatedWindow> {
+        self.lock().windows.get(&hwnd).cloned()
+    }
+
+    pub fn set_window_rect(&self, hwnd: u64, rect: Rect) -> bool {
+        match self.lock().windows.get_mut(&hwnd) {
+            Some(window) => {
+                window.rect = rect;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_minimized(&self, hwnd: u64, minimized: bool) -> bool {
+        match self.lock().windows.get_mut(&hwnd) {
+            Some(window) => {
+                window.is_minimized = minimized;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_maximized(&self, hwnd: u64, maximized: bool) -> bool {
+        match self.lock().windows.get_mut(&hwnd) {
+            Some(window) => {
+                window.is_maximized = maximized;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_visible(&self, hwnd: u64, visible: bool) -> bool {
+        match self.lock().windows.get_mut(&hwnd) {
+            Some(window) => {
+                window.is_visible = visible;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Raise a window to the top of the z-order and make it the foreground window.
+    pub fn bring_to_front(&self, hwnd: u64) -> bool {
+        let mut state = self.lock();
+        if !state.windows.contains_key(&hwnd) {
+            return false;
+        }
+        state.z_order.retain(|h| *h != hwnd);
+        state.z_order.insert(0, hwnd);
+        state.foreground = Some(hwnd);
+        true
+    }
+
+    pub fn set_monitors(&self, monitors: Vec<Rect>) {
+        self.lock().monitors = monitors.into_iter().map(MonitorAreas::full).collect();
+    }
+
+    /// Set the effective DPI of monitor `index` (96 = 100%, 144 = 150%).
+    pub fn set_monitor_dpi(&self, index: usize, dpi: u32) -> bool {
+        match self.lock().monitors.get_mut(index) {
+            Some(monitor) => {
+                monitor.dpi = dpi;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the work area of monitor `index`, as if a taskbar or app bar docked on it.
+    pub fn set_work_area(&self, index: usize, work_area: Rect) -> bool {
+        match self.lock().monitors.get_mut(index) {
+            Some(monitor) => {
+                monitor.work_area = work_area;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mark HWNDs as desktop/shell windows (the Progman/WorkerW equivalent).
+    pub fn set_desktop_hwnds(&self, hwnds: Vec<u64>) {
+        self.lock().desktop_hwnds = hwnds;
+    }
+
+    /// HWNDs from topmost to bottommost.
+    pub fn z_order(&self) -> Vec<u64> {
+        self.lock().z_order.clone()
+    }
+}
+
+impl WindowBackend for SimulatedDesktop {
+    fn get_window_rect(&self, hwnd: u64) -> Option<Rect> {
+        self.lock().windows.get(&hwnd).map(|w| w.rect)
+    }
+
+    fn explain_window(&self, hwnd: u64) -> FilterDecision {
+        let state = self.lock();
+        match state.windows.get(&hwnd) {
+            Some(window) => state.filter.decide(&window.to_window_traits()),
+            None => FilterDecision::missing(),
+        }
+    }
+
+    fn set_window_filter(&self, filter: WindowFilter) {
+        self.lock().filter = filter;
+    }
+
+    fn get_window_traits(&self, hwnd: u64, _with_executable: bool) -> Option<WindowTraits> {
+        self.lock().windows.get(&hwnd).map(|w| w.to_window_traits())
+    }
+
+    fn move_window_to_rect(&self, hwnd: u64, rect: Rect) -> Result<(), String> {
+        if self.set_window_rect(hwnd, rect) {
+            Ok(())
+        } else {
+            Err(format!("Invalid window handle: 0x{:X}", hwnd))
+        }
+    }
+
+    fn show_window(&self, hwnd: u64, state: WindowShowState) -> Result<(), String> {
+        let mut desktop = self.lock();
+        let window = desktop
+            .windows
+            .get_mut(&hwnd)
+            .ok_or_else(|| format!("Invalid window handle: 0x{:X}", hwnd))?;
+        match state {
+            WindowShowState::Hidden => window.is_visible = false,
+            WindowShowState::Minimized => window.is_minimized = true,
+            WindowShowState::Shown => {
+                window.is_visible = true;
+                window.is_minimized = false;
+            }
+            WindowShowState::Focused => {
+                window.is_visible = true;
+                window.is_minimized = false;
+                drop(desktop);
+                self.bring_to_front(hwnd);
+            }
+        }
+        Ok(())
+    }
+
+    fn list_windows(&self) -> Vec<u64> {
+        self.z_order()
+    }
+
+    fn enumerate_windows(&self) -> DashMap<u64, WindowInfo> {
+        let found_windows = DashMap::new();
+        let handles = self.list_windows();
+        for hwnd in handles {
+            if self.is_manageable_window(hwnd) {
+                if let Some(window_info) = self.get_window_info(hwnd) {
+                    found_windows.insert(hwnd, window_info);
+                }
+            }
+        }
+        found_windows
+    }
+
+    fn get_hwnd_z_order_map(&self) -> HashMap<u64, usize> {
+        self.lock()
+            .z_order
+            .iter()
+            .enumerate()
+            .map(|(z, hwnd)| (*hwnd, z))
+            .collect()
+    }
+
+    fn get_window_info(&self, hwnd: u64) -> Option<WindowInfo> {
+        let state = self.lock();
+        let window = state.windows.get(&hwnd)?;
+        let mut info = window.to_window_info();
+        info.z_order = state.z_order.iter().position(|h| *h == hwnd).unwrap_or(0) as u32;
+        Some(info)
+    }
+
+    fn is_window(&self, hwnd: u64) -> bool {
+        self.lock().windows.contains_key(&hwnd)
+    }
+
+    fn is_window_visible(&self, hwnd: u64) -> bool {
+        self.lock().windows.get(&hwnd).is_some_and(|w| w.is_visible)
+    }
+
+    fn is_window_maximized(&self, hwnd: u64) -> bool {
+        self.lock()
+            .windows
+            .get(&hwnd)
+            .is_some_and(|w| w.is_maximized)
+    }
+
+    fn get_window_title(&self, hwnd: u64) -> String {
+        self.lock()
+            .windows
+            .get(&hwnd)
+            .map(|w| w.title.clone())
+            .unwrap_or_default()
+    }
+
+    fn get_window_class_name(&self, hwnd: u64) -> String {
+        self.lock()
+            .windows
+            .get(&hwnd)
+            .map(|w| w.class_name.clone())
+            .unwrap_or_default()
+    }
+
+    fn get_window_executable(&self, hwnd: u64) -> String {
+        self.lock()
+            .windows
+            .get(&hwnd)
+            .map(|w| w.executable.clone())
+            .unwrap_or_default()
+    }
+
+    fn get_foreground_window(&self) -> Option<u64> {
+        self.lock().foreground
+    }
+
+    fn get_monitor_bounds(&self) -> Vec<Rect> {
+        self.lock().monitors.iter().map(|m| m.bounds).collect()
+    }
+
+    fn get_monitors(&self) -> Vec<MonitorAreas> {
+        self.lock().monitors.clone()
+    }
+
+    fn find_desktop_hwnds(&self) -> Vec<u64> {
+        self.lock().desktop_hwnds.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_z_order_and_manageability() {
+        let desktop = SimulatedDesktop::single_monitor(1920, 1080);
+        let a = desktop.add_window("A", "Notepad", Rect::new(0, 0, 100, 100));
+        let b = desktop.add_window("B", "Notepad", Rect::new(50, 50, 150, 150));
+        assert_eq!(desktop.z_order(), vec![b, a]);
+        assert_eq!(desktop.get_foreground_window(), Some(b));
+
+        desktop.bring_to_front(a);
+        let z_map = desktop.get_hwnd_z_order_map();
+        assert_eq!(z_map[&a], 0);
+        assert_eq!(z_map[&b], 1);
+
+        desktop.set_minimized(b, true);
+        assert!(!desktop.is_manageable_window(b));
+        assert_eq!(desktop.enumerate_windows().len(), 1);
+    }
+}
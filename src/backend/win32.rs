@@ -0,0 +1,450 @@
+// Win32 backend - talks to the live Windows desktop through winapi
+
+use dashmap::DashMap;
+use std::collections::HashMap;
+use std::ptr;
+use std::sync::RwLock;
+use winapi::shared::minwindef::{FALSE, LPARAM};
+use winapi::shared::windef::{HWND, RECT};
+use winapi::shared::winerror::S_OK;
+use winapi::um::errhandlingapi::GetLastError;
+use winapi::um::handleapi::CloseHandle;
+use winapi::um::processthreadsapi::OpenProcess;
+use winapi::um::shellscalingapi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+use winapi::um::winbase::QueryFullProcessImageNameW;
+use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+use winapi::um::winuser::*;
+
+use super::{MonitorAreas, WindowBackend, WindowShowState};
+use crate::config::{FilterDecision, WindowFilter, WindowTraits};
+use crate::geometry::{Rect, DEFAULT_DPI};
+use crate::window::info::{RectWrapper, WindowInfo};
+
+#[derive(Debug, Default)]
+pub struct Win32Backend {
+    /// Decides which windows are manageable
+    filter: RwLock<WindowFilter>,
+}
+
+impl Win32Backend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl WindowBackend for Win32Backend {
+    fn get_window_rect(&self, hwnd: u64) -> Option<Rect> {
+        unsafe {
+            let mut rect = RECT {
+                left: 0,
+                top: 0,
+                right: 0,
+                bottom: 0,
+            };
+            if GetWindowRect(hwnd as HWND, &mut rect) != 0 {
+                Some(rect.into())
+            } else {
+                None
+            }
+        }
+    }
+
+    fn explain_window(&self, hwnd: u64) -> FilterDecision {
+        let filter = self.filter.read().unwrap_or_else(|e| e.into_inner());
+        match self.get_window_traits(hwnd, filter.uses_executable()) {
+            Some(traits) => filter.decide(&traits),
+            None => FilterDecision::missing(),
+        }
+    }
+
+    fn set_window_filter(&self, filter: WindowFilter) {
+        *self.filter.write().unwrap_or_else(|e| e.into_inner()) = filter;
+    }
+
+    fn get_window_traits(&self, hwnd: u64, with_executable: bool) -> Option<WindowTraits> {
+        unsafe {
+            if IsWindow(hwnd as HWND) == 0 {
+                return None;
+            }
+            let mut process_id: u32 = 0;
+            GetWindowThreadProcessId(hwnd as HWND, &mut process_id);
+            Some(WindowTraits {
+                class_name: self.get_window_class_name(hwnd),
+                title: self.get_window_title(hwnd),
+                process_id,
+                executable: if with_executable {
+                    self.get_window_executable(hwnd)
+                } else {
+                    String::new()
+                },
+                style: GetWindowLongW(hwnd as HWND, GWL_STYLE) as u32,
+                ex_style: GetWindowLongW(hwnd as HWND, GWL_EXSTYLE) as u32,
+                is_visible: IsWindowVisible(hwnd as HWND) != 0,
+                is_minimized: IsIconic(hwnd as HWND) != 0,
+            })
+        }
+    }
+
+    fn move_window_to_rect(&self, hwnd: u64, rect: Rect) -> Result<(), String> {
+        unsafe {
+            let result = SetWindowPos(
+                hwnd as HWND,
+                ptr::null_mut(),
+                rect.left,
+                rect.top,
+                rect.right - rect.left,
+                rect.bottom - rect.top,
+                SWP_NOZORDER | SWP_NOACTIVATE,
+            );
+            if result != 0 {
+                return Ok(());
+            }
+            let error = GetLastError();
+            // If error is 5 (access denied), try moving without resizing and
+            // push the window to the back so it stops fighting the layout.
+            if error == 5 {
+                let move_only_result = SetWindowPos(
+                    hwnd as HWND,
+                    ptr::null_mut(),
+                    rect.left,
+                    rect.top,
+                    0,
+                    0,
+                    SWP_NOZORDER | SWP_NOACTIVATE | SWP_NOSIZE,
+                );
+                if move_only_result == 0 {
+                    println!(
+                        "[DEBUG] Move-only SetWindowPos also failed for hwnd=0x{:X} with error code: {}",
+                        hwnd,
+                        GetLastError()
+                    );
+                }
+                SetWindowPos(
+                    hwnd as HWND,
+                    HWND_BOTTOM,
+                    0,
+                    0,
+                    0,
+                    0,
+                    SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+                );
+            }
+            Err(format!(
+                "SetWindowPos failed for 0x{:X} with error: {}",
+                hwnd, error
+            ))
+        }
+    }
+
+    fn show_window(&self, hwnd: u64, state: WindowShowState) -> Result<(), String> {
+        unsafe {
+            if IsWindow(hwnd as HWND) == 0 {
+                return Err(format!("Invalid window handle: 0x{:X}", hwnd));
+            }
+            let command = match state {
+                WindowShowState::Hidden => SW_HIDE,
+                WindowShowState::Minimized => SW_SHOWMINNOACTIVE,
+                // SW_RESTORE activates the window; only use it to bring one back from minimized
+                WindowShowState::Shown | WindowShowState::Focused
+                    if IsIconic(hwnd as HWND) != 0 =>
+                {
+                    SW_RESTORE
+                }
+                WindowShowState::Shown => SW_SHOWNA,
+                WindowShowState::Focused => SW_SHOW,
+            };
+            // The return value is the previous visibility, not an error
+            ShowWindow(hwnd as HWND, command);
+            if state == WindowShowState::Focused {
+                SetForegroundWindow(hwnd as HWND);
+            }
+        }
+        Ok(())
+    }
+
+    fn list_windows(&self) -> Vec<u64> {
+        let mut handles: Vec<u64> = Vec::new();
+
+        unsafe extern "system" fn enum_windows_proc_collect(hwnd: HWND, lparam: LPARAM) -> i32 {
+            let handles = &mut *(lparam as *mut Vec<u64>);
+            handles.push(hwnd as u64);
+            1
+        }
+
+        unsafe {
+            EnumWindows(
+                Some(enum_windows_proc_collect),
+                &mut handles as *mut Vec<u64> as LPARAM,
+            );
+        }
+        handles
+    }
+
+    fn enumerate_windows(&self) -> DashMap<u64, WindowInfo> {
+        let found_windows = DashMap::new();
+        for hwnd in self.list_windows() {
+            if self.is_manageable_window(hwnd) {
+                if let Some(window_info) = self.get_window_info(hwnd) {
+                    found_windows.insert(hwnd, window_info);
+                }
+            }
+        }
+        found_windows
+    }
+
+    fn get_hwnd_z_order_map(&self) -> HashMap<u64, usize> {
+        let mut z_map = HashMap::new();
+        unsafe {
+            let mut hwnd = GetTopWindow(ptr::null_mut());
+            let mut z = 0;
+            while !hwnd.is_null() {
+                z_map.insert(hwnd as u64, z);
+                hwnd = GetWindow(hwnd, GW_HWNDNEXT);
+                z += 1;
+            }
+        }
+        z_map
+    }
+
+    fn get_window_info(&self, hwnd: u64) -> Option<WindowInfo> {
+        let rect = self.get_window_rect(hwnd)?;
+        let mut title_buf = [0u16; 256];
+        let title_len =
+            unsafe { GetWindowTextW(hwnd as HWND, title_buf.as_mut_ptr(), title_buf.len() as i32) };
+        let mut class_name_buf = [0u16; 256];
+        let class_name_len = unsafe {
+            GetClassNameW(
+                hwnd as HWND,
+                class_name_buf.as_mut_ptr(),
+                class_name_buf.len() as i32,
+            )
+        };
+        let mut process_id: u32 = 0;
+        unsafe {
+            GetWindowThreadProcessId(hwnd as HWND, &mut process_id);
+        }
+        Some(WindowInfo {
+            hwnd,
+            title: title_buf,
+            title_len: title_len.max(0) as u32,
+            window_rect: RectWrapper::from_rect(rect),
+            monitor_ids: [0usize; 8],
+            z_order: 0,
+            is_visible: self.is_window_visible(hwnd),
+            is_minimized: unsafe { IsIconic(hwnd as HWND) != 0 },
+            is_maximized: self.is_window_maximized(hwnd),
+            process_id,
+            class_name: class_name_buf,
+            class_name_len: class_name_len.max(0) as u32,
+        })
+    }
+
+    fn is_window(&self, hwnd: u64) -> bool {
+        unsafe { IsWindow(hwnd as HWND) != 0 }
+    }
+
+    fn is_window_visible(&self, hwnd: u64) -> bool {
+        unsafe { IsWindowVisible(hwnd as HWND) != 0 }
+    }
+
+    fn is_window_maximized(&self, hwnd: u64) -> bool {
+        unsafe {
+            if IsWindow(hwnd as HWND) == 0 {
+                return false;
+            }
+            // Check window placement
+            let mut placement = std::mem::zeroed::<WINDOWPLACEMENT>();
+            placement.length = std::mem::size_of::<WINDOWPLACEMENT>() as u32;
+            if GetWindowPlacement(hwnd as HWND, &mut placement) != 0
+                && placement.showCmd == SW_MAXIMIZE as u32
+            {
+                return true;
+            }
+            // Fallback to IsZoomed and WS_MAXIMIZE style
+            let is_zoomed = IsZoomed(hwnd as HWND) != 0;
+            let style = GetWindowLongW(hwnd as HWND, GWL_STYLE) as u32;
+            let is_maximized_style = (style & WS_MAXIMIZE) != 0;
+            if is_zoomed || is_maximized_style {
+                return true;
+            }
+        }
+        // Check if window rect matches any monitor rect
+        if let Some(rect) = self.get_window_rect(hwnd) {
+            for monitor_rect in self.get_monitor_bounds() {
+                if rect.left == monitor_rect.left
+                    && rect.top == monitor_rect.top
+                    && rect.right == monitor_rect.right
+                    && rect.bottom == monitor_rect.bottom
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn get_window_title(&self, hwnd: u64) -> String {
+        unsafe {
+            let mut buffer = [0u16; 256];
+            let len = GetWindowTextW(hwnd as HWND, buffer.as_mut_ptr(), buffer.len() as i32);
+            if len > 0 {
+                String::from_utf16_lossy(&buffer[..len as usize])
+            } else {
+                String::new()
+            }
+        }
+    }
+
+    fn get_window_class_name(&self, hwnd: u64) -> String {
+        unsafe {
+            let mut buffer = [0u16; 256];
+            let len = GetClassNameW(hwnd as HWND, buffer.as_mut_ptr(), buffer.len() as i32);
+            if len > 0 {
+                String::from_utf16_lossy(&buffer[..len as usize])
+            } else {
+                String::new()
+            }
+        }
+    }
+
+    fn get_window_executable(&self, hwnd: u64) -> String {
+        unsafe {
+            let mut process_id: u32 = 0;
+            GetWindowThreadProcessId(hwnd as HWND, &mut process_id);
+            if process_id == 0 {
+                return String::new();
+            }
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, process_id);
+            if process.is_null() {
+                return String::new();
+            }
+            let mut buffer = [0u16; 1024];
+            let mut len = buffer.len() as u32;
+            let ok = QueryFullProcessImageNameW(process, 0, buffer.as_mut_ptr(), &mut len);
+            CloseHandle(process);
+            if ok == 0 {
+                return String::new();
+            }
+            let path = String::from_utf16_lossy(&buffer[..len as usize]);
+            path.rsplit(['\\', '/'])
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        }
+    }
+
+    fn get_foreground_window(&self) -> Option<u64> {
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd.is_null() {
+                None
+            } else {
+                Some(hwnd as u64)
+            }
+        }
+    }
+
+    fn get_monitor_bounds(&self) -> Vec<Rect> {
+        let mut monitors: Vec<RECT> = Vec::new();
+        unsafe {
+            extern "system" fn monitor_enum_proc(
+                _hmonitor: winapi::shared::windef::HMONITOR,
+                _hdc: winapi::shared::windef::HDC,
+                rect: *mut RECT,
+                data: LPARAM,
+            ) -> i32 {
+                let monitors = unsafe { &mut *(data as *mut Vec<RECT>) };
+                monitors.push(unsafe { *rect });
+                1 // Continue enumeration
+            }
+            EnumDisplayMonitors(
+                ptr::null_mut(),
+                ptr::null(),
+                Some(monitor_enum_proc),
+                &mut monitors as *mut Vec<RECT> as LPARAM,
+            );
+        }
+        monitors.into_iter().map(Rect::from).collect()
+    }
+
+    fn get_monitors(&self) -> Vec<MonitorAreas> {
+        let mut monitors: Vec<MonitorAreas> = Vec::new();
+        unsafe {
+            extern "system" fn monitor_enum_proc(
+                hmonitor: winapi::shared::windef::HMONITOR,
+                _hdc: winapi::shared::windef::HDC,
+                rect: *mut RECT,
+                data: LPARAM,
+            ) -> i32 {
+                let monitors = unsafe { &mut *(data as *mut Vec<MonitorAreas>) };
+                let mut mi: MONITORINFO = unsafe { std::mem::zeroed() };
+                mi.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+                // rcWork excludes the taskbar and any docked app bars
+                let mut areas = if unsafe { GetMonitorInfoW(hmonitor, &mut mi) } != 0 {
+                    MonitorAreas {
+                        bounds: Rect::from(mi.rcMonitor),
+                        work_area: Rect::from(mi.rcWork),
+                        dpi: DEFAULT_DPI,
+                    }
+                } else {
+                    MonitorAreas::full(Rect::from(unsafe { *rect }))
+                };
+                // Effective DPI includes the user's scaling setting (144 = 150%)
+                let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+                let hr = unsafe {
+                    GetDpiForMonitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y)
+                };
+                if hr == S_OK && dpi_x > 0 {
+                    areas.dpi = dpi_x;
+                }
+                monitors.push(areas);
+                1 // Continue enumeration
+            }
+            EnumDisplayMonitors(
+                ptr::null_mut(),
+                ptr::null(),
+                Some(monitor_enum_proc),
+                &mut monitors as *mut Vec<MonitorAreas> as LPARAM,
+            );
+        }
+        monitors
+    }
+
+    fn find_desktop_hwnds(&self) -> Vec<u64> {
+        let mut desktop_hwnds: Vec<u64> = Vec::new();
+        unsafe {
+            extern "system" fn enum_proc(hwnd: HWND, lparam: LPARAM) -> i32 {
+                let desktop_hwnds = unsafe { &mut *(lparam as *mut Vec<u64>) };
+                let mut class_buf = [0u16; 256];
+                let len = unsafe { GetClassNameW(hwnd, class_buf.as_mut_ptr(), 256) };
+                if len > 0 {
+                    let class = String::from_utf16_lossy(&class_buf[..len as usize]);
+                    if class == "Progman"
+                        || class == "WorkerW"
+                        || class == "ApplicationFrameWindow"
+                        || class == "Windows.UI.Core.CoreWindow"
+                    {
+                        desktop_hwnds.push(hwnd as u64);
+                    }
+                }
+                1
+            }
+            EnumWindows(
+                Some(enum_proc),
+                &mut desktop_hwnds as *mut Vec<u64> as LPARAM,
+            );
+        }
+        desktop_hwnds
+    }
+
+    fn get_virtual_screen_rect(&self) -> Rect {
+        unsafe {
+            Rect::from_xywh(
+                GetSystemMetrics(SM_XVIRTUALSCREEN),
+                GetSystemMetrics(SM_YVIRTUALSCREEN),
+                GetSystemMetrics(SM_CXVIRTUALSCREEN),
+                GetSystemMetrics(SM_CYVIRTUALSCREEN),
+            )
+        }
+    }
+}
@@ -1,8 +1,196 @@
+use crate::geometry::Insets;
+
+/// Size of one grid row or column, modelled on CSS grid tracks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TrackSize {
+    /// Share of the space left over once every `Px` track is placed (CSS `fr`)
+    Fr(u32),
+    /// Fixed width or height in pixels (CSS `px`)
+    Px(i32),
+}
+
+/// Part of a monitor a grid is laid over.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum GridArea {
+    /// The full monitor bounds, including the space under taskbars and docked bars
+    Bounds,
+    /// The monitor's work area, i.e. bounds minus taskbars and docked app bars
+    #[default]
+    WorkArea,
+}
+
+/// Unit the pixel values of a `GridConfig` (`Px` tracks, padding, gaps) are given in.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum GridUnits {
+    /// Raw device pixels
+    #[default]
+    Physical,
+    /// DPI-independent pixels (96 DPI), scaled per monitor before use
+    Logical,
+}
+
+/// Rule deciding which cells a window occupies.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum CellOccupancyStrategy {
+    /// Any overlap at all, however small
+    AnyOverlap,
+    /// The window covers at least this fraction (0.0 to 1.0) of the cell
+    MinCoverage(f32),
+    /// Only the cell containing the window's centre point
+    CenterPoint,
+    /// Each cell belongs to the window covering most of it. Asked about a single window,
+    /// this means the window covers at least half of the cell.
+    MajorityOwner,
+}
+
+impl Default for CellOccupancyStrategy {
+    fn default() -> Self {
+        Self::MinCoverage(crate::COVERAGE_THRESHOLD)
+    }
+}
+
+/// Address of a cell through nested sub-grids, outermost first: `[(0, 2), (1, 0)]` is
+/// row 1, col 0 of the grid that cell (0, 2) is split into.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct CellPath(pub Vec<(usize, usize)>);
+
+impl CellPath {
+    /// Path to a cell of the outermost grid.
+    pub fn new(row: usize, col: usize) -> Self {
+        Self(vec![(row, col)])
+    }
+
+    /// Path to cell (row, col) of the grid this cell is split into.
+    pub fn child(&self, row: usize, col: usize) -> Self {
+        let mut cells = self.0.clone();
+        cells.push((row, col));
+        Self(cells)
+    }
+
+    /// Number of grid levels the path goes through.
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.0
+    }
+}
+
+impl From<(usize, usize)> for CellPath {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self::new(row, col)
+    }
+}
+
+impl std::fmt::Display for CellPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (row, col)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+            write!(f, "({},{})", row, col)?;
+        }
+        Ok(())
+    }
+}
+
+/// Largest grid a `GridConfig` may describe. IPC cell runs could address more, but
+/// layout messages (`GridLayoutMessage`, `GridCellAssignment`) store rows and columns
+/// in a byte.
+pub const MAX_GRID_ROWS: usize = u8::MAX as usize;
+pub const MAX_GRID_COLS: usize = u8::MAX as usize;
+/// Deepest cell path (grid levels) a grid may nest to, and an IPC command can address.
+pub const MAX_CELL_PATH_DEPTH: usize = 4;
+
+/// Check a grid config, sub-grids included, stays within the grid limits above.
+pub fn validate_grid_config(config: &GridConfig) -> Result<(), String> {
+    fn check(config: &GridConfig, path: &CellPath) -> Result<(), String> {
+        let name = if path.0.is_empty() {
+            "Grid".to_string()
+        } else {
+            format!("Sub-grid of cell {}", path)
+        };
+        if config.rows == 0 || config.cols == 0 {
+            return Err(format!(
+                "{} has no cells ({}x{})",
+                name, config.rows, config.cols
+            ));
+        }
+        if config.rows > MAX_GRID_ROWS || config.cols > MAX_GRID_COLS {
+            return Err(format!(
+                "{} is {}x{}, the IPC protocol carries at most {}x{}",
+                name, config.rows, config.cols, MAX_GRID_ROWS, MAX_GRID_COLS
+            ));
+        }
+        for sub in &config.subgrids {
+            let child = path.child(sub.row, sub.col);
+            if child.depth() >= MAX_CELL_PATH_DEPTH {
+                return Err(format!(
+                    "Sub-grid of cell {} is nested too deeply: cell paths hold at most {} levels",
+                    child, MAX_CELL_PATH_DEPTH
+                ));
+            }
+            check(&sub.grid, &child)?;
+        }
+        Ok(())
+    }
+    check(config, &CellPath::default())
+}
+
+/// A cell split into a grid of its own, laid over the cell's rectangle.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SubGrid {
+    pub row: usize,
+    pub col: usize,
+    pub grid: GridConfig,
+}
+
 // Dynamic grid configuration
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct GridConfig {
     pub rows: usize,
     pub cols: usize,
+    /// Row heights, top to bottom. Empty means every row gets the same height.
+    #[serde(default)]
+    pub row_tracks: Vec<TrackSize>,
+    /// Column widths, left to right. Empty means every column gets the same width.
+    #[serde(default)]
+    pub col_tracks: Vec<TrackSize>,
+    /// Space between the grid area's edges and the outermost cells.
+    #[serde(default)]
+    pub padding: Insets,
+    /// Gutter between adjacent rows, in pixels.
+    #[serde(default)]
+    pub row_gap: i32,
+    /// Gutter between adjacent columns, in pixels.
+    #[serde(default)]
+    pub col_gap: i32,
+    /// Unit of `Px` tracks, padding and gaps.
+    #[serde(default)]
+    pub units: GridUnits,
+    /// Rule deciding which cells a window occupies.
+    #[serde(default)]
+    pub occupancy: CellOccupancyStrategy,
+    /// Cells split into child grids.
+    #[serde(default)]
+    pub subgrids: Vec<SubGrid>,
 }
 
 impl Default for GridConfig {
@@ -10,16 +198,188 @@ impl Default for GridConfig {
         Self {
             rows: 8, // Default grid size
             cols: 12,
+            row_tracks: Vec::new(),
+            col_tracks: Vec::new(),
+            padding: Insets::default(),
+            row_gap: 0,
+            col_gap: 0,
+            units: GridUnits::Physical,
+            occupancy: CellOccupancyStrategy::default(),
+            subgrids: Vec::new(),
         }
     }
 }
 
 impl GridConfig {
     pub fn new(rows: usize, cols: usize) -> Self {
-        Self { rows, cols }
+        Self {
+            rows,
+            cols,
+            row_tracks: Vec::new(),
+            col_tracks: Vec::new(),
+            padding: Insets::default(),
+            row_gap: 0,
+            col_gap: 0,
+            units: GridUnits::Physical,
+            occupancy: CellOccupancyStrategy::default(),
+            subgrids: Vec::new(),
+        }
+    }
+
+    /// Grid sized track by track, e.g. `[Px(300), Fr(1), Px(300)]` for a wide centre
+    /// column between two sidebars. `rows`/`cols` follow the track counts.
+    pub fn with_tracks(row_tracks: Vec<TrackSize>, col_tracks: Vec<TrackSize>) -> Self {
+        Self {
+            rows: row_tracks.len(),
+            cols: col_tracks.len(),
+            row_tracks,
+            col_tracks,
+            padding: Insets::default(),
+            row_gap: 0,
+            col_gap: 0,
+            units: GridUnits::Physical,
+            occupancy: CellOccupancyStrategy::default(),
+            subgrids: Vec::new(),
+        }
+    }
+
+    /// Outer padding between the grid area's edges and the cells.
+    pub fn with_padding(mut self, padding: Insets) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Gutters between rows and between columns.
+    pub fn with_gaps(mut self, row_gap: i32, col_gap: i32) -> Self {
+        self.row_gap = row_gap;
+        self.col_gap = col_gap;
+        self
+    }
+
+    /// Choose the rule deciding which cells a window occupies.
+    pub fn with_occupancy(mut self, occupancy: CellOccupancyStrategy) -> Self {
+        self.occupancy = occupancy;
+        self
+    }
+
+    /// Split cell (row, col) into `grid`, replacing any earlier split of that cell.
+    pub fn with_subgrid(mut self, row: usize, col: usize, grid: GridConfig) -> Self {
+        self.subgrids.retain(|sub| (sub.row, sub.col) != (row, col));
+        self.subgrids.push(SubGrid { row, col, grid });
+        self
+    }
+
+    /// Child grid cell (row, col) is split into, if any.
+    pub fn subgrid(&self, row: usize, col: usize) -> Option<&GridConfig> {
+        self.subgrids
+            .iter()
+            .find(|sub| (sub.row, sub.col) == (row, col))
+            .map(|sub| &sub.grid)
+    }
+
+    /// Grid holding the last cell of `path`, or `None` if the path leaves the grid or
+    /// goes through a cell that isn't split.
+    pub fn grid_at(&self, path: &CellPath) -> Option<&GridConfig> {
+        let (last, parents) = path.0.split_last()?;
+        let mut grid = self;
+        for (row, col) in parents {
+            grid = grid.subgrid(*row, *col)?;
+        }
+        (last.0 < grid.rows && last.1 < grid.cols).then_some(grid)
+    }
+
+    /// Split the cell at `path` into `grid`, or with `None` merge it back into one cell.
+    pub fn subdivide(&mut self, path: &CellPath, grid: Option<GridConfig>) -> Result<(), String> {
+        if self.grid_at(path).is_none() {
+            return Err(format!("Cell {} does not exist", path));
+        }
+        let (&(row, col), parents) = path.0.split_last().expect("grid_at checked the path");
+        let mut parent = self;
+        for (r, c) in parents {
+            parent = parent
+                .subgrids
+                .iter_mut()
+                .find(|sub| (sub.row, sub.col) == (*r, *c))
+                .map(|sub| &mut sub.grid)
+                .expect("grid_at checked the path");
+        }
+        if let Some(grid) = &grid {
+            if grid.rows == 0 || grid.cols == 0 {
+                return Err(format!(
+                    "Cannot split cell {} into {}x{}",
+                    path, grid.rows, grid.cols
+                ));
+            }
+        }
+        parent
+            .subgrids
+            .retain(|sub| (sub.row, sub.col) != (row, col));
+        if let Some(grid) = grid {
+            parent.subgrids.push(SubGrid { row, col, grid });
+        }
+        Ok(())
+    }
+
+    /// Interpret `Px` tracks, padding and gaps as logical (96 DPI) pixels.
+    pub fn in_logical_units(mut self) -> Self {
+        self.units = GridUnits::Logical;
+        self
+    }
+
+    /// This config in physical pixels for a monitor with the given scale factor
+    /// (1.0 = 96 DPI). Physical configs are returned unchanged. Each sub-grid is
+    /// converted according to its own units.
+    pub fn to_physical(&self, scale: f64) -> GridConfig {
+        let subgrids = self
+            .subgrids
+            .iter()
+            .map(|sub| SubGrid {
+                row: sub.row,
+                col: sub.col,
+                grid: sub.grid.to_physical(scale),
+            })
+            .collect();
+        if self.units == GridUnits::Physical {
+            return GridConfig {
+                subgrids,
+                ..self.clone()
+            };
+        }
+        let px = |value: i32| (value as f64 * scale).round() as i32;
+        let scale_tracks = |tracks: &[TrackSize]| {
+            tracks
+                .iter()
+                .map(|track| match track {
+                    TrackSize::Px(value) => TrackSize::Px(px(*value)),
+                    TrackSize::Fr(fr) => TrackSize::Fr(*fr),
+                })
+                .collect()
+        };
+        GridConfig {
+            rows: self.rows,
+            cols: self.cols,
+            row_tracks: scale_tracks(&self.row_tracks),
+            col_tracks: scale_tracks(&self.col_tracks),
+            padding: Insets::new(
+                px(self.padding.left),
+                px(self.padding.top),
+                px(self.padding.right),
+                px(self.padding.bottom),
+            ),
+            row_gap: px(self.row_gap),
+            col_gap: px(self.col_gap),
+            units: GridUnits::Physical,
+            occupancy: self.occupancy,
+            subgrids,
+        }
     }
 
     pub fn cell_count(&self) -> usize {
         self.rows * self.cols
     }
+
+    /// True when rows and columns are evenly sized.
+    pub fn is_uniform(&self) -> bool {
+        self.row_tracks.is_empty() && self.col_tracks.is_empty()
+    }
 }
@@ -1,5 +1,22 @@
 // Configuration module
 pub mod grid_config;
+pub mod scratchpad;
+pub mod snap;
+pub mod window_filter;
+pub mod window_rules;
+pub mod zone_layout;
 
 // Re-export for convenience
-pub use grid_config::GridConfig;
+pub use grid_config::{
+    validate_grid_config, CellOccupancyStrategy, CellPath, GridArea, GridConfig, GridUnits,
+    SubGrid, TrackSize, MAX_CELL_PATH_DEPTH, MAX_GRID_COLS, MAX_GRID_ROWS,
+};
+pub use scratchpad::{Scratchpad, Scratchpads};
+pub use snap::{SnapConfig, SnapPolicy, SnapRule};
+pub use window_filter::{
+    FilterAction, FilterDecision, FilterRule, WindowFilter, WindowPredicate, WindowTraits,
+};
+pub use window_rules::{
+    RulePlacement, TitlePattern, WindowMatcher, WindowProperties, WindowRule, WindowRules,
+};
+pub use zone_layout::{Zone, ZoneLayout};
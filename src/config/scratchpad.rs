@@ -0,0 +1,153 @@
+use super::window_rules::{WindowMatcher, WindowProperties};
+
+/// A named place to keep one window out of sight until it is toggled into view.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Scratchpad {
+    pub name: String,
+    /// Identity of the scratchpad's window, matched again when the server restarts
+    #[serde(rename = "match")]
+    pub matcher: WindowMatcher,
+    /// Cells from `start` to `end` (row, col), inclusive, the window covers when shown
+    /// on the monitor that has focus
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl Scratchpad {
+    pub fn new(
+        name: impl Into<String>,
+        matcher: WindowMatcher,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> Self {
+        Self {
+            name: name.into(),
+            matcher,
+            start,
+            end,
+        }
+    }
+}
+
+/// The configured scratchpads. Sending a window to a scratchpad records the window's
+/// identity here, so saving the file keeps the membership across restarts.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Scratchpads {
+    #[serde(default)]
+    pub scratchpads: Vec<Scratchpad>,
+}
+
+impl Scratchpads {
+    /// File the server keeps scratchpads in when `E_GRID_SCRATCHPADS` doesn't name another
+    pub const DEFAULT_FILE: &'static str = "e_grid_scratchpads.json";
+
+    pub fn with_scratchpad(mut self, scratchpad: Scratchpad) -> Self {
+        self.scratchpads.push(scratchpad);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Scratchpad> {
+        self.scratchpads.iter().find(|s| s.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Scratchpad> {
+        self.scratchpads.iter_mut().find(|s| s.name == name)
+    }
+
+    /// Record `window` as the window of scratchpad `name`. A new scratchpad shows the
+    /// window in `cells`; an existing one keeps its cells and only changes its matcher
+    /// if the window doesn't already match it.
+    pub fn assign(
+        &mut self,
+        name: &str,
+        window: &WindowProperties,
+        cells: ((usize, usize), (usize, usize)),
+    ) {
+        match self.get_mut(name) {
+            Some(scratchpad) if scratchpad.matcher.matches(window) => {}
+            Some(scratchpad) => scratchpad.matcher = WindowMatcher::identity(window),
+            None => self.scratchpads.push(Scratchpad::new(
+                name,
+                WindowMatcher::identity(window),
+                cells.0,
+                cells.1,
+            )),
+        }
+    }
+
+    /// Load scratchpads from a JSON file.
+    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Save scratchpads to a JSON file.
+    pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// The file named by `E_GRID_SCRATCHPADS`, or `DEFAULT_FILE`.
+    pub fn default_path() -> String {
+        std::env::var("E_GRID_SCRATCHPADS").unwrap_or_else(|_| Self::DEFAULT_FILE.to_string())
+    }
+
+    /// Load the file at [`Self::default_path`] if it exists. No file means no
+    /// scratchpads.
+    pub fn load_default() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::default_path();
+        if std::path::Path::new(&path).exists() {
+            Self::load_from_file(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scratchpad_assignment() {
+        let terminal = WindowProperties {
+            class_name: "CASCADIA_HOSTING_WINDOW_CLASS".to_string(),
+            title: "PowerShell".to_string(),
+            process_id: 7,
+            executable: "WindowsTerminal.exe".to_string(),
+            width: 800,
+            height: 400,
+        };
+        let notes = WindowProperties {
+            class_name: "Notepad".to_string(),
+            process_id: 9,
+            ..Default::default()
+        };
+
+        // A new scratchpad remembers the window's identity and cells
+        let mut scratchpads = Scratchpads::default();
+        scratchpads.assign("term", &terminal, ((0, 0), (0, 3)));
+        let term = scratchpads.get("term").unwrap();
+        assert_eq!((term.start, term.end), ((0, 0), (0, 3)));
+        assert!(term.matcher.matches(&WindowProperties {
+            title: "cmd".to_string(),
+            process_id: 8,
+            ..terminal.clone()
+        }));
+        assert!(!term.matcher.matches(&notes));
+
+        // Another window takes over the identity but not the cells; windows without an
+        // executable are known by their process
+        scratchpads.assign("term", &notes, ((1, 1), (1, 1)));
+        let term = scratchpads.get("term").unwrap();
+        assert_eq!(term.start, (0, 0));
+        assert!(term.matcher.matches(&notes));
+        assert_eq!(term.matcher.process_id, Some(9));
+
+        let json = serde_json::to_string(&scratchpads).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Scratchpads>(&json).unwrap(),
+            scratchpads
+        );
+    }
+}
@@ -0,0 +1,116 @@
+/// Where a window lands when the user finishes dragging or resizing it.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum SnapPolicy {
+    /// The single cell nearest the window's centre, inside sub-grids where cells are split
+    NearestCell,
+    /// The span of cells the window covers most: each edge moves to the nearest cell
+    /// boundary, keeping at least one cell
+    #[default]
+    CoveredSpan,
+    /// The zone under the mouse cursor, or the cell under it on monitors without zones
+    ZoneUnderCursor,
+}
+
+/// Windows left alone by snapping. Every criterion that is set must match.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SnapRule {
+    /// Exact window class name, e.g. "Shell_TrayWnd"
+    #[serde(default)]
+    pub class_name: Option<String>,
+    /// Text the window title contains, ignoring case
+    #[serde(default)]
+    pub title_contains: Option<String>,
+    #[serde(default)]
+    pub process_id: Option<u32>,
+}
+
+impl SnapRule {
+    /// Match windows of one class.
+    pub fn class(class_name: impl Into<String>) -> Self {
+        Self {
+            class_name: Some(class_name.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Match windows whose title contains `text`.
+    pub fn title(text: impl Into<String>) -> Self {
+        Self {
+            title_contains: Some(text.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Match windows of one process.
+    pub fn process(process_id: u32) -> Self {
+        Self {
+            process_id: Some(process_id),
+            ..Default::default()
+        }
+    }
+
+    /// True if a window with this class, title and process matches the rule. A rule
+    /// with no criteria matches nothing.
+    pub fn matches(&self, class_name: &str, title: &str, process_id: u32) -> bool {
+        if self.class_name.is_none() && self.title_contains.is_none() && self.process_id.is_none() {
+            return false;
+        }
+        self.class_name
+            .as_deref()
+            .is_none_or(|class| class == class_name)
+            && self
+                .title_contains
+                .as_deref()
+                .is_none_or(|text| title.to_lowercase().contains(&text.to_lowercase()))
+            && self.process_id.is_none_or(|id| id == process_id)
+    }
+}
+
+/// Snap-to-grid behaviour when a drag or resize ends. Off unless `enabled` is set.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SnapConfig {
+    pub enabled: bool,
+    #[serde(default)]
+    pub policy: SnapPolicy,
+    /// Windows that are never snapped
+    #[serde(default)]
+    pub exclude: Vec<SnapRule>,
+    /// Slide into place over this many milliseconds with this easing instead of
+    /// jumping there
+    #[cfg(feature = "animation")]
+    #[serde(default)]
+    pub animation: Option<(u32, crate::EasingType)>,
+}
+
+impl SnapConfig {
+    /// Snapping switched on with `policy`.
+    pub fn new(policy: SnapPolicy) -> Self {
+        Self {
+            enabled: true,
+            policy,
+            ..Default::default()
+        }
+    }
+
+    /// Never snap windows matching `rule`.
+    pub fn with_exclusion(mut self, rule: SnapRule) -> Self {
+        self.exclude.push(rule);
+        self
+    }
+
+    /// Animate windows into place.
+    #[cfg(feature = "animation")]
+    pub fn with_animation(mut self, duration_ms: u32, easing: crate::EasingType) -> Self {
+        self.animation = Some((duration_ms, easing));
+        self
+    }
+
+    /// True if any exclusion rule matches the window.
+    pub fn is_excluded(&self, class_name: &str, title: &str, process_id: u32) -> bool {
+        self.exclude
+            .iter()
+            .any(|rule| rule.matches(class_name, title, process_id))
+    }
+}
@@ -0,0 +1,352 @@
+use once_cell::sync::Lazy;
+
+use super::window_rules::TitlePattern;
+
+/// Win32 style bits, so filters can test styles without winapi.
+pub mod styles {
+    pub const WS_POPUP: u32 = 0x8000_0000;
+    pub const WS_CHILD: u32 = 0x4000_0000;
+    pub const WS_CAPTION: u32 = 0x00C0_0000;
+    pub const WS_BORDER: u32 = 0x0080_0000;
+    pub const WS_THICKFRAME: u32 = 0x0004_0000;
+    pub const WS_OVERLAPPEDWINDOW: u32 = 0x00CF_0000;
+    pub const WS_EX_TOOLWINDOW: u32 = 0x0000_0080;
+    pub const WS_EX_APPWINDOW: u32 = 0x0004_0000;
+    pub const WS_EX_NOACTIVATE: u32 = 0x0800_0000;
+}
+
+/// What the manageable-window filter sees of a window.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WindowTraits {
+    pub class_name: String,
+    pub title: String,
+    pub process_id: u32,
+    /// File name of the owning executable; only looked up when the filter tests
+    /// executables (see `WindowFilter::uses_executable`)
+    pub executable: String,
+    pub style: u32,
+    pub ex_style: u32,
+    pub is_visible: bool,
+    pub is_minimized: bool,
+}
+
+/// A test on a window's traits. Predicates compose with `All`, `Any` and `Not`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowPredicate {
+    /// Exact class name; "" matches windows without one
+    Class(String),
+    /// Regular expression the title must match
+    Title(TitlePattern),
+    Process(u32),
+    /// Executable file name, ignoring case, e.g. "notepad.exe"
+    Executable(String),
+    /// Every bit of the mask is set in the window style (see [`styles`])
+    Style(u32),
+    /// Every bit of the mask is set in the extended window style
+    ExStyle(u32),
+    Invisible,
+    Minimized,
+    All(Vec<WindowPredicate>),
+    Any(Vec<WindowPredicate>),
+    Not(Box<WindowPredicate>),
+}
+
+impl WindowPredicate {
+    pub fn matches(&self, window: &WindowTraits) -> bool {
+        match self {
+            Self::Class(class) => *class == window.class_name,
+            Self::Title(pattern) => pattern.is_match(&window.title),
+            Self::Process(id) => *id == window.process_id,
+            Self::Executable(exe) => exe.eq_ignore_ascii_case(&window.executable),
+            Self::Style(mask) => window.style & mask == *mask,
+            Self::ExStyle(mask) => window.ex_style & mask == *mask,
+            Self::Invisible => !window.is_visible,
+            Self::Minimized => window.is_minimized,
+            Self::All(predicates) => predicates.iter().all(|p| p.matches(window)),
+            Self::Any(predicates) => predicates.iter().any(|p| p.matches(window)),
+            Self::Not(predicate) => !predicate.matches(window),
+        }
+    }
+
+    /// True if testing this predicate needs the window's executable.
+    pub fn uses_executable(&self) -> bool {
+        match self {
+            Self::Executable(_) => true,
+            Self::All(predicates) | Self::Any(predicates) => {
+                predicates.iter().any(|p| p.uses_executable())
+            }
+            Self::Not(predicate) => predicate.uses_executable(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    Include,
+    Exclude,
+}
+
+/// One link of the filter chain: windows matching `when` are included or excluded.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FilterRule {
+    /// Shown when explaining a decision
+    pub name: String,
+    pub action: FilterAction,
+    pub when: WindowPredicate,
+}
+
+impl FilterRule {
+    pub fn include(name: impl Into<String>, when: WindowPredicate) -> Self {
+        Self {
+            name: name.into(),
+            action: FilterAction::Include,
+            when,
+        }
+    }
+
+    pub fn exclude(name: impl Into<String>, when: WindowPredicate) -> Self {
+        Self {
+            name: name.into(),
+            action: FilterAction::Exclude,
+            when,
+        }
+    }
+}
+
+/// Why a window is or isn't manageable.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FilterDecision {
+    pub accepted: bool,
+    /// Rule that decided; None when no rule matched or the window doesn't exist
+    pub rule: Option<String>,
+}
+
+impl FilterDecision {
+    /// The decision for a window that doesn't exist.
+    pub fn missing() -> Self {
+        Self {
+            accepted: false,
+            rule: None,
+        }
+    }
+}
+
+impl std::fmt::Display for FilterDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.accepted, &self.rule) {
+            (true, Some(rule)) => write!(f, "accepted by rule '{}'", rule),
+            (false, Some(rule)) => write!(f, "rejected by rule '{}'", rule),
+            (true, None) => write!(f, "accepted: no filter rule matched"),
+            (false, None) => write!(f, "rejected: window does not exist"),
+        }
+    }
+}
+
+static BUILTIN_RULES: Lazy<Vec<FilterRule>> = Lazy::new(|| {
+    use styles::*;
+    use WindowPredicate::*;
+    vec![
+        FilterRule::exclude("hidden windows", Invisible),
+        FilterRule::exclude("minimized windows", Minimized),
+        FilterRule::exclude("tool windows", ExStyle(WS_EX_TOOLWINDOW)),
+        FilterRule::exclude("child windows", Style(WS_CHILD)),
+        FilterRule::exclude("windows without a class", Class(String::new())),
+        FilterRule::exclude(
+            "composition bridge windows",
+            Class("Windows.UI.Composition.DesktopWindowContentBridge".to_string()),
+        ),
+        FilterRule::exclude(
+            "UWP core windows",
+            Class("Windows.UI.Core.CoreWindow".to_string()),
+        ),
+        FilterRule::exclude(
+            "XAML island hosts",
+            Class("XamlExplorerHostIslandWindow".to_string()),
+        ),
+        // Chrome and Electron draw tooltips and popups as borderless top-level windows
+        FilterRule::exclude(
+            "borderless Chrome popups",
+            All(vec![
+                Class("Chrome_WidgetWin_1".to_string()),
+                Not(Box::new(Style(WS_BORDER))),
+            ]),
+        ),
+    ]
+});
+
+fn default_true() -> bool {
+    true
+}
+
+/// Decides which windows are manageable: tracked and placed on the grid. The user's
+/// rules are tried in order, then the built-in exclusions; the first rule matching a
+/// window decides, and windows no rule matches are included.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowFilter {
+    #[serde(default)]
+    pub rules: Vec<FilterRule>,
+    /// Apply [`WindowFilter::builtin_rules`] after the user's rules
+    #[serde(default = "default_true")]
+    pub builtin_exclusions: bool,
+}
+
+impl Default for WindowFilter {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            builtin_exclusions: true,
+        }
+    }
+}
+
+impl WindowFilter {
+    /// File the server reads the filter from when `E_GRID_FILTER` doesn't name another
+    pub const DEFAULT_FILE: &'static str = "e_grid_filter.json";
+
+    /// The built-in exclusions: hidden, minimized, tool and child windows, windows
+    /// without a class, shell host windows and borderless Chrome popups.
+    pub fn builtin_rules() -> &'static [FilterRule] {
+        &BUILTIN_RULES
+    }
+
+    /// Include windows matching `when`, even ones a built-in rule would exclude.
+    pub fn with_include(mut self, name: impl Into<String>, when: WindowPredicate) -> Self {
+        self.rules.push(FilterRule::include(name, when));
+        self
+    }
+
+    /// Exclude windows matching `when`.
+    pub fn with_exclude(mut self, name: impl Into<String>, when: WindowPredicate) -> Self {
+        self.rules.push(FilterRule::exclude(name, when));
+        self
+    }
+
+    pub fn without_builtin_exclusions(mut self) -> Self {
+        self.builtin_exclusions = false;
+        self
+    }
+
+    /// True if any rule tests executables, so backends only look them up when needed.
+    pub fn uses_executable(&self) -> bool {
+        self.rules.iter().any(|rule| rule.when.uses_executable())
+    }
+
+    /// Whether `window` is manageable, and which rule decided.
+    pub fn decide(&self, window: &WindowTraits) -> FilterDecision {
+        let builtin: &[FilterRule] = if self.builtin_exclusions {
+            Self::builtin_rules()
+        } else {
+            &[]
+        };
+        match self
+            .rules
+            .iter()
+            .chain(builtin)
+            .find(|rule| rule.when.matches(window))
+        {
+            Some(rule) => FilterDecision {
+                accepted: rule.action == FilterAction::Include,
+                rule: Some(rule.name.clone()),
+            },
+            None => FilterDecision {
+                accepted: true,
+                rule: None,
+            },
+        }
+    }
+
+    /// Load a filter from a JSON file.
+    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Load the file named by `E_GRID_FILTER`, or `DEFAULT_FILE` if it exists. No file
+    /// means the built-in exclusions only.
+    pub fn load_default() -> Result<Self, Box<dyn std::error::Error>> {
+        match std::env::var("E_GRID_FILTER") {
+            Ok(path) => Self::load_from_file(&path),
+            Err(_) if std::path::Path::new(Self::DEFAULT_FILE).exists() => {
+                Self::load_from_file(Self::DEFAULT_FILE)
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::styles::*;
+    use super::*;
+
+    #[test]
+    fn test_window_filter() {
+        let popup = WindowTraits {
+            class_name: "Chrome_WidgetWin_1".to_string(),
+            title: "Slack".to_string(),
+            executable: "slack.exe".to_string(),
+            style: WS_POPUP,
+            is_visible: true,
+            ..Default::default()
+        };
+        let editor = WindowTraits {
+            class_name: "Notepad".to_string(),
+            style: WS_OVERLAPPEDWINDOW,
+            is_visible: true,
+            ..Default::default()
+        };
+
+        // The built-in exclusions reject borderless Chrome windows and nothing else here
+        let filter = WindowFilter::default();
+        assert_eq!(
+            filter.decide(&popup).to_string(),
+            "rejected by rule 'borderless Chrome popups'"
+        );
+        assert_eq!(
+            filter.decide(&editor),
+            FilterDecision {
+                accepted: true,
+                rule: None
+            }
+        );
+        assert!(!filter.decide(&WindowTraits::default()).accepted);
+        assert!(!filter.uses_executable());
+
+        // User rules come first, so they can override the built-ins
+        let filter = WindowFilter::default()
+            .with_include(
+                "slack",
+                WindowPredicate::Executable("Slack.exe".to_string()),
+            )
+            .with_exclude(
+                "minimizable notepads",
+                WindowPredicate::All(vec![
+                    WindowPredicate::Class("Notepad".to_string()),
+                    WindowPredicate::Style(WS_OVERLAPPEDWINDOW),
+                ]),
+            );
+        assert!(filter.uses_executable());
+        assert_eq!(filter.decide(&popup).rule.as_deref(), Some("slack"));
+        assert!(filter.decide(&popup).accepted);
+        assert!(!filter.decide(&editor).accepted);
+        assert!(
+            WindowFilter::default()
+                .without_builtin_exclusions()
+                .decide(&popup)
+                .accepted
+        );
+
+        // Filters load from JSON; the built-ins stay on unless turned off
+        let parsed: WindowFilter = serde_json::from_str(
+            r#"{"rules": [{"name": "pip", "action": "include",
+                "when": {"all": [{"title": "(?i)picture"}, {"not": "minimized"}]}}]}"#,
+        )
+        .unwrap();
+        assert!(parsed.builtin_exclusions);
+        let json = serde_json::to_string(&filter).unwrap();
+        assert_eq!(serde_json::from_str::<WindowFilter>(&json).unwrap(), filter);
+    }
+}
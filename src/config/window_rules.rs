@@ -0,0 +1,333 @@
+/// Regular expression a window title must match, checked when the rules are loaded.
+#[derive(Clone, Debug)]
+pub struct TitlePattern(regex::Regex);
+
+impl TitlePattern {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        regex::Regex::new(pattern)
+            .map(Self)
+            .map_err(|e| format!("Invalid title pattern '{}': {}", pattern, e))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn is_match(&self, title: &str) -> bool {
+        self.0.is_match(title)
+    }
+}
+
+impl PartialEq for TitlePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl serde::Serialize for TitlePattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TitlePattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// What the rules see of a window when they are applied.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WindowProperties {
+    pub class_name: String,
+    pub title: String,
+    pub process_id: u32,
+    /// File name of the owning executable, e.g. "notepad.exe"; empty if unknown
+    pub executable: String,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Which windows a rule applies to. Every criterion that is set must match.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowMatcher {
+    /// Exact window class name, e.g. "Notepad"
+    #[serde(default)]
+    pub class_name: Option<String>,
+    /// Regular expression for the title, e.g. "(?i)picture.in.picture"
+    #[serde(default)]
+    pub title: Option<TitlePattern>,
+    #[serde(default)]
+    pub process_id: Option<u32>,
+    /// Executable file name, ignoring case, e.g. "notepad.exe"
+    #[serde(default)]
+    pub executable: Option<String>,
+    /// Smallest (width, height) the window may have when the rule is applied
+    #[serde(default)]
+    pub min_size: Option<(i32, i32)>,
+    /// Largest (width, height) the window may have when the rule is applied
+    #[serde(default)]
+    pub max_size: Option<(i32, i32)>,
+}
+
+impl WindowMatcher {
+    /// Match windows of one class.
+    pub fn class(class_name: impl Into<String>) -> Self {
+        Self {
+            class_name: Some(class_name.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Match windows whose title matches the regular expression `pattern`.
+    pub fn title(pattern: &str) -> Result<Self, String> {
+        Ok(Self {
+            title: Some(TitlePattern::new(pattern)?),
+            ..Default::default()
+        })
+    }
+
+    /// Match windows of one process.
+    pub fn process(process_id: u32) -> Self {
+        Self {
+            process_id: Some(process_id),
+            ..Default::default()
+        }
+    }
+
+    /// Match windows of every process started from `executable`, e.g. "notepad.exe".
+    pub fn executable(executable: impl Into<String>) -> Self {
+        Self {
+            executable: Some(executable.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Match windows like `window` across restarts: same class and executable, or same
+    /// class and process when the executable is unknown.
+    pub fn identity(window: &WindowProperties) -> Self {
+        Self {
+            class_name: Some(window.class_name.clone()),
+            executable: (!window.executable.is_empty()).then(|| window.executable.clone()),
+            process_id: window.executable.is_empty().then_some(window.process_id),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_min_size(mut self, width: i32, height: i32) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    pub fn with_max_size(mut self, width: i32, height: i32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    /// True if the window meets every criterion. A matcher with no criteria matches
+    /// nothing.
+    pub fn matches(&self, window: &WindowProperties) -> bool {
+        if *self == Self::default() {
+            return false;
+        }
+        self.class_name
+            .as_deref()
+            .is_none_or(|class| class == window.class_name)
+            && self
+                .title
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(&window.title))
+            && self.process_id.is_none_or(|id| id == window.process_id)
+            && self
+                .executable
+                .as_deref()
+                .is_none_or(|exe| exe.eq_ignore_ascii_case(&window.executable))
+            && self
+                .min_size
+                .is_none_or(|(w, h)| window.width >= w && window.height >= h)
+            && self
+                .max_size
+                .is_none_or(|(w, h)| window.width <= w && window.height <= h)
+    }
+}
+
+/// Where a rule puts a window, on the rule's monitor.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RulePlacement {
+    /// The cells from `start` to `end` (row, col), inclusive; on the virtual grid if
+    /// the rule names no monitor
+    Span {
+        start: (usize, usize),
+        end: (usize, usize),
+    },
+    /// A named zone; the rule must name a monitor
+    Zone(String),
+}
+
+/// What happens to the windows a matcher picks out.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowRule {
+    /// Shown in logs and IPC queries
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "match")]
+    pub matcher: WindowMatcher,
+    /// Never track the window: it occupies no cells and is never tiled or moved
+    #[serde(default)]
+    pub ignore: bool,
+    /// Keep the window out of tiling layouts
+    #[serde(default)]
+    pub float: bool,
+    /// Move the window to this monitor
+    #[serde(default)]
+    pub monitor: Option<usize>,
+    #[serde(default)]
+    pub placement: Option<RulePlacement>,
+    /// Easing used whenever the window is animated
+    #[cfg(feature = "animation")]
+    #[serde(default)]
+    pub easing: Option<crate::EasingType>,
+}
+
+impl WindowRule {
+    pub fn new(name: impl Into<String>, matcher: WindowMatcher) -> Self {
+        Self {
+            name: name.into(),
+            matcher,
+            ..Default::default()
+        }
+    }
+
+    pub fn ignored(mut self) -> Self {
+        self.ignore = true;
+        self
+    }
+
+    pub fn floating(mut self) -> Self {
+        self.float = true;
+        self
+    }
+
+    pub fn on_monitor(mut self, monitor_id: usize) -> Self {
+        self.monitor = Some(monitor_id);
+        self
+    }
+
+    pub fn with_placement(mut self, placement: RulePlacement) -> Self {
+        self.placement = Some(placement);
+        self
+    }
+
+    #[cfg(feature = "animation")]
+    pub fn with_easing(mut self, easing: crate::EasingType) -> Self {
+        self.easing = Some(easing);
+        self
+    }
+}
+
+/// Window rules in priority order: the first rule that matches a window decides what
+/// happens to it.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowRules {
+    #[serde(default)]
+    pub rules: Vec<WindowRule>,
+}
+
+impl WindowRules {
+    /// File the server reads rules from when `E_GRID_RULES` doesn't name another
+    pub const DEFAULT_FILE: &'static str = "e_grid_rules.json";
+
+    pub fn with_rule(mut self, rule: WindowRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The first rule matching `window`.
+    pub fn first_match(&self, window: &WindowProperties) -> Option<&WindowRule> {
+        self.rules.iter().find(|rule| rule.matcher.matches(window))
+    }
+
+    /// Load rules from a JSON file.
+    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Save rules to a JSON file.
+    pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Load the file named by `E_GRID_RULES`, or `DEFAULT_FILE` if it exists. No file
+    /// means no rules.
+    pub fn load_default() -> Result<Self, Box<dyn std::error::Error>> {
+        match std::env::var("E_GRID_RULES") {
+            Ok(path) => Self::load_from_file(&path),
+            Err(_) if std::path::Path::new(Self::DEFAULT_FILE).exists() => {
+                Self::load_from_file(Self::DEFAULT_FILE)
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_rules() {
+        let notepad = WindowProperties {
+            class_name: "Notepad".to_string(),
+            title: "notes.txt - Notepad".to_string(),
+            process_id: 42,
+            executable: "notepad.exe".to_string(),
+            width: 800,
+            height: 600,
+        };
+        assert!(WindowMatcher::class("Notepad").matches(&notepad));
+        assert!(WindowMatcher::executable("NOTEPAD.EXE").matches(&notepad));
+        assert!(WindowMatcher::title(r"\.txt - ").unwrap().matches(&notepad));
+        assert!(!WindowMatcher::title("^Notepad").unwrap().matches(&notepad));
+        assert!(WindowMatcher::title("(").is_err());
+        assert!(!WindowMatcher::default().matches(&notepad));
+        assert!(WindowMatcher::process(42)
+            .with_max_size(800, 600)
+            .matches(&notepad));
+        assert!(!WindowMatcher::class("Notepad")
+            .with_min_size(1000, 0)
+            .matches(&notepad));
+
+        // The first matching rule wins
+        let rules = WindowRules::default()
+            .with_rule(
+                WindowRule::new("tiny", WindowMatcher::default().with_max_size(100, 100)).ignored(),
+            )
+            .with_rule(
+                WindowRule::new("editors", WindowMatcher::executable("notepad.exe"))
+                    .on_monitor(1)
+                    .with_placement(RulePlacement::Zone("left".to_string())),
+            )
+            .with_rule(WindowRule::new("all notepads", WindowMatcher::class("Notepad")).floating());
+        assert_eq!(rules.first_match(&notepad).unwrap().name, "editors");
+
+        // Rules survive a round trip through their file format; bad patterns don't load
+        let json = serde_json::to_string(&rules).unwrap();
+        assert_eq!(serde_json::from_str::<WindowRules>(&json).unwrap(), rules);
+        let parsed: WindowRules = serde_json::from_str(
+            r#"{"rules": [{"name": "pip", "match": {"title": "(?i)picture.in.picture"}, "float": true}]}"#,
+        )
+        .unwrap();
+        assert!(parsed.rules[0].float);
+        assert!(
+            serde_json::from_str::<WindowRules>(r#"{"rules": [{"match": {"title": "["}}]}"#)
+                .is_err()
+        );
+    }
+}
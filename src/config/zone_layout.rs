@@ -0,0 +1,125 @@
+use crate::config::CellOccupancyStrategy;
+
+/// A named rectangle on a monitor, given as fractions (0.0 to 1.0) of the area
+/// the layout is laid over.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Zone {
+    pub name: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Zone {
+    pub fn new(name: impl Into<String>, x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            name: name.into(),
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Free-form alternative to a uniform grid, in the style of FancyZones: an arbitrary
+/// set of named zones that may overlap.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ZoneLayout {
+    pub name: String,
+    /// Zones in the order they are numbered; later zones are drawn on top
+    pub zones: Vec<Zone>,
+    /// Rule deciding which zones a window occupies, applied as for grid cells
+    #[serde(default)]
+    pub occupancy: CellOccupancyStrategy,
+}
+
+impl ZoneLayout {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a zone covering the given fractions of the area.
+    pub fn with_zone(
+        mut self,
+        name: impl Into<String>,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) -> Self {
+        self.zones.push(Zone::new(name, x, y, width, height));
+        self
+    }
+
+    pub fn with_occupancy(mut self, occupancy: CellOccupancyStrategy) -> Self {
+        self.occupancy = occupancy;
+        self
+    }
+
+    /// Full-height columns of the given relative widths, named "1", "2", ...
+    pub fn columns(name: impl Into<String>, weights: &[u32]) -> Self {
+        let total: u32 = weights.iter().sum::<u32>().max(1);
+        let mut layout = Self::new(name);
+        let mut x = 0;
+        for (i, weight) in weights.iter().enumerate() {
+            layout = layout.with_zone(
+                (i + 1).to_string(),
+                x as f32 / total as f32,
+                0.0,
+                *weight as f32 / total as f32,
+                1.0,
+            );
+            x += weight;
+        }
+        layout
+    }
+
+    /// FancyZones' "priority grid": a wide centre zone between two narrow ones.
+    pub fn priority_grid() -> Self {
+        let mut layout = Self::columns("Priority Grid", &[1, 2, 1]);
+        for (zone, name) in layout.zones.iter_mut().zip(["Left", "Center", "Right"]) {
+            zone.name = name.to_string();
+        }
+        layout
+    }
+
+    pub fn len(&self) -> usize {
+        self.zones.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    /// Index of the zone called `name`.
+    pub fn zone_index(&self, name: &str) -> Option<usize> {
+        self.zones.iter().position(|zone| zone.name == name)
+    }
+
+    /// Check every zone lies inside the area and has a unique, non-empty name.
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, zone) in self.zones.iter().enumerate() {
+            if zone.name.is_empty() {
+                return Err(format!("Zone {} has no name", i));
+            }
+            if self.zones[..i].iter().any(|other| other.name == zone.name) {
+                return Err(format!("Duplicate zone name '{}'", zone.name));
+            }
+            let inside = |start: f32, size: f32| {
+                start >= 0.0 && size > 0.0 && start + size <= 1.0 + f32::EPSILON
+            };
+            if !inside(zone.x, zone.width) || !inside(zone.y, zone.height) {
+                return Err(format!(
+                    "Zone '{}' does not fit inside the monitor: x={} y={} width={} height={}",
+                    zone.name, zone.x, zone.y, zone.width, zone.height
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 // Display module for grid formatting and visualization
+#[cfg(feature = "tui")]
 pub mod formatters;
 
 // Re-export main functions
+#[cfg(feature = "tui")]
 pub use formatters::{
     format_hwnd_display, print_column_headers, print_empty_cell, print_monitor_header,
     print_offscreen_cell, print_row_prefix, print_virtual_monitor_header,
@@ -0,0 +1,323 @@
+// Portable geometry primitives
+//
+// Crate-owned replacements for winapi's RECT/POINT/SIZE. Field names match the
+// Win32 structs so code reading `.left`/`.top`/`.right`/`.bottom` works with
+// either type; conversions are available with the `win32` feature.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// DPI of a monitor at 100% scaling; logical pixels are pixels at this DPI.
+pub const DEFAULT_DPI: u32 = 96;
+
+/// Scale factor for a monitor DPI, e.g. 144 DPI -> 1.5.
+pub fn dpi_to_scale(dpi: u32) -> f64 {
+    if dpi == 0 {
+        return 1.0;
+    }
+    dpi as f64 / DEFAULT_DPI as f64
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Size {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Size {
+    pub const fn new(width: i32, height: i32) -> Self {
+        Self { width, height }
+    }
+
+    pub fn area(&self) -> i64 {
+        if self.width <= 0 || self.height <= 0 {
+            0
+        } else {
+            self.width as i64 * self.height as i64
+        }
+    }
+}
+
+/// Per-edge spacing in pixels, e.g. padding between a monitor edge and the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Insets {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Insets {
+    pub const fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Same spacing on every edge.
+    pub const fn uniform(px: i32) -> Self {
+        Self::new(px, px, px, px)
+    }
+}
+
+/// Axis-aligned rectangle with exclusive right/bottom edges (same as Win32 RECT).
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Rect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Rect {
+    pub const fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    pub const fn from_xywh(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self::new(x, y, x + width, y + height)
+    }
+
+    pub fn from_origin_size(origin: Point, size: Size) -> Self {
+        Self::from_xywh(origin.x, origin.y, size.width, size.height)
+    }
+
+    pub fn width(&self) -> i32 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> i32 {
+        self.bottom - self.top
+    }
+
+    pub fn origin(&self) -> Point {
+        Point::new(self.left, self.top)
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width(), self.height())
+    }
+
+    pub fn center(&self) -> Point {
+        Point::new(self.left + self.width() / 2, self.top + self.height() / 2)
+    }
+
+    /// Area in pixels; zero for empty or inverted rectangles.
+    pub fn area(&self) -> i64 {
+        self.size().area()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.right <= self.left || self.bottom <= self.top
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.left < other.right
+            && self.right > other.left
+            && self.top < other.bottom
+            && self.bottom > other.top
+    }
+
+    /// Overlapping region, or None if the rectangles do not overlap.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect::new(
+            self.left.max(other.left),
+            self.top.max(other.top),
+            self.right.min(other.right),
+            self.bottom.min(other.bottom),
+        );
+        if rect.is_empty() {
+            None
+        } else {
+            Some(rect)
+        }
+    }
+
+    /// Area of the overlapping region (0 if none).
+    pub fn intersection_area(&self, other: &Rect) -> i64 {
+        self.intersection(other).map_or(0, |r| r.area())
+    }
+
+    /// Smallest rectangle containing both.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        Rect::new(
+            self.left.min(other.left),
+            self.top.min(other.top),
+            self.right.max(other.right),
+            self.bottom.max(other.bottom),
+        )
+    }
+
+    pub fn contains_point(&self, point: Point) -> bool {
+        point.x >= self.left && point.x < self.right && point.y >= self.top && point.y < self.bottom
+    }
+
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        other.left >= self.left
+            && other.top >= self.top
+            && other.right <= self.right
+            && other.bottom <= self.bottom
+    }
+
+    pub fn offset(&self, dx: i32, dy: i32) -> Rect {
+        Rect::new(
+            self.left + dx,
+            self.top + dy,
+            self.right + dx,
+            self.bottom + dy,
+        )
+    }
+
+    /// Shrink by `insets` on each edge.
+    pub fn shrink(&self, insets: &Insets) -> Rect {
+        self.inset(insets.left, insets.top, insets.right, insets.bottom)
+    }
+
+    /// Shrink each edge inward (negative values grow the rectangle).
+    pub fn inset(&self, left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect::new(
+            self.left + left,
+            self.top + top,
+            self.right - right,
+            self.bottom - bottom,
+        )
+    }
+
+    /// Scale distances from `origin` by `factor`, rounding each edge to the nearest pixel.
+    /// Used to convert between physical and logical pixels around a monitor's origin.
+    pub fn scale_about(&self, origin: Point, factor: f64) -> Rect {
+        let x = |value: i32| origin.x + ((value - origin.x) as f64 * factor).round() as i32;
+        let y = |value: i32| origin.y + ((value - origin.y) as f64 * factor).round() as i32;
+        Rect::new(x(self.left), y(self.top), x(self.right), y(self.bottom))
+    }
+}
+
+impl fmt::Debug for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rect")
+            .field("left", &self.left)
+            .field("top", &self.top)
+            .field("right", &self.right)
+            .field("bottom", &self.bottom)
+            .finish()
+    }
+}
+
+#[cfg(feature = "win32")]
+impl From<winapi::shared::windef::RECT> for Rect {
+    fn from(rect: winapi::shared::windef::RECT) -> Self {
+        Rect::new(rect.left, rect.top, rect.right, rect.bottom)
+    }
+}
+
+#[cfg(feature = "win32")]
+impl From<Rect> for winapi::shared::windef::RECT {
+    fn from(rect: Rect) -> Self {
+        winapi::shared::windef::RECT {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+#[cfg(feature = "win32")]
+impl From<winapi::shared::windef::POINT> for Point {
+    fn from(point: winapi::shared::windef::POINT) -> Self {
+        Point::new(point.x, point.y)
+    }
+}
+
+// Wrapper for a RECT-like struct using usize fields (grid coordinates)
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct UsizeRect {
+    pub left: usize,
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+}
+
+impl UsizeRect {
+    pub fn from_bounds(left: usize, top: usize, right: usize, bottom: usize) -> Self {
+        UsizeRect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+}
+
+impl fmt::Debug for UsizeRect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UsizeRect")
+            .field("left", &self.left)
+            .field("top", &self.top)
+            .field("right", &self.right)
+            .field("bottom", &self.bottom)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersection_and_area() {
+        let a = Rect::new(0, 0, 100, 100);
+        let b = Rect::new(50, 50, 150, 150);
+        assert_eq!(a.intersection(&b), Some(Rect::new(50, 50, 100, 100)));
+        assert_eq!(a.intersection_area(&b), 2500);
+        assert_eq!(a.intersection(&Rect::new(100, 0, 200, 100)), None);
+        assert_eq!(Rect::new(10, 10, 5, 20).area(), 0);
+    }
+
+    #[test]
+    fn test_union_and_containment() {
+        let a = Rect::new(0, 0, 100, 100);
+        let b = Rect::new(-50, 20, 40, 300);
+        assert_eq!(a.union(&b), Rect::new(-50, 0, 100, 300));
+        assert_eq!(a.union(&Rect::default()), a);
+        assert!(a.contains_rect(&Rect::new(10, 10, 100, 100)));
+        assert!(!a.contains_rect(&b));
+        assert!(a.contains_point(Point::new(0, 99)));
+        assert!(!a.contains_point(Point::new(100, 50)));
+    }
+
+    #[test]
+    fn test_scale_about_round_trip() {
+        let monitor = Point::new(1920, 0);
+        let physical = Rect::new(2070, 150, 2970, 1050);
+        let logical = physical.scale_about(monitor, 1.0 / dpi_to_scale(144));
+        assert_eq!(logical, Rect::new(2020, 100, 2620, 700));
+        assert_eq!(logical.scale_about(monitor, dpi_to_scale(144)), physical);
+    }
+}
@@ -3,47 +3,17 @@
 
 use crate::config::GridConfig;
 use crate::display::format_hwnd_display;
+use crate::geometry::Rect;
+use crate::grid::math;
 use crate::grid::traits::{AnimatableGrid, CellDisplay, GridError, GridResult, GridTrait};
+// EasingType lives with WindowAnimation; re-exported here for existing callers
+pub use crate::window::animation::{EasingType, EASING_TYPE_MAP};
 use crate::window::info::RectWrapper;
 use crate::window::{WindowAnimation, WindowInfo};
-use iceoryx2::prelude::ZeroCopySend;
-use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
-use winapi::shared::windef::RECT;
 use winapi::um::winuser::{SetWindowPos, SWP_NOACTIVATE, SWP_NOZORDER};
 
-#[repr(C)]
-#[derive(
-    Debug, Clone, serde::Serialize, serde::Deserialize, Copy, ZeroCopySend, PartialEq, Eq, Hash,
-)]
-pub enum EasingType {
-    Linear,
-    EaseIn,
-    EaseOut,
-    EaseInOut,
-    Bounce,
-    Elastic,
-    Back,
-}
-
-// Automatically build a static map from enum discriminant to name string
-pub static EASING_TYPE_MAP: Lazy<HashMap<EasingType, &'static str>> = Lazy::new(|| {
-    use EasingType::*;
-    [
-        (Linear, "Linear"),
-        (EaseIn, "EaseIn"),
-        (EaseOut, "EaseOut"),
-        (EaseInOut, "EaseInOut"),
-        (Bounce, "Bounce"),
-        (Elastic, "Elastic"),
-        (Back, "Back"),
-    ]
-    .iter()
-    .copied()
-    .collect()
-});
-
 #[derive(Debug, Clone)]
 pub enum AnimationCellState {
     Empty,
@@ -88,27 +58,9 @@ impl AnimationGrid {
     }
 
     /// Calculate the screen position for a grid cell
-    fn calculate_cell_position(&self, row: usize, col: usize) -> GridResult<RECT> {
+    fn calculate_cell_position(&self, row: usize, col: usize) -> GridResult<Rect> {
         self.validate_coordinates(row, col)?;
-
-        let (monitor_left, monitor_top, monitor_right, monitor_bottom) = self.monitor_bounds;
-        let monitor_width = monitor_right - monitor_left;
-        let monitor_height = monitor_bottom - monitor_top;
-
-        let cell_width = monitor_width / self.config.cols as i32;
-        let cell_height = monitor_height / self.config.rows as i32;
-
-        let left = monitor_left + (col as i32 * cell_width);
-        let top = monitor_top + (row as i32 * cell_height);
-        let right = left + cell_width - 30; // Margin for visibility
-        let bottom = top + cell_height - 30; // Margin for visibility
-
-        Ok(RECT {
-            left,
-            top,
-            right,
-            bottom,
-        })
+        self.calculate_cell_position_for_config(&self.config, row, col)
     }
 
     /// Apply easing function to animation progress
@@ -209,40 +161,24 @@ impl AnimationGrid {
         config: &GridConfig,
         row: usize,
         col: usize,
-    ) -> GridResult<RECT> {
-        if row >= config.rows || col >= config.cols {
-            return Err(GridError::InvalidCoordinates {
+    ) -> GridResult<Rect> {
+        let (left, top, right, bottom) = self.monitor_bounds;
+        let monitor_rect = Rect::new(left, top, right, bottom);
+        // Padding and gutters come from the config
+        math::cell_to_rect(&monitor_rect, config, row, col).ok_or_else(|| {
+            GridError::InvalidCoordinates {
                 row,
                 col,
-                max_row: config.rows - 1,
-                max_col: config.cols - 1,
-            });
-        }
-
-        let (monitor_left, monitor_top, monitor_right, monitor_bottom) = self.monitor_bounds;
-        let monitor_width = monitor_right - monitor_left;
-        let monitor_height = monitor_bottom - monitor_top;
-
-        let cell_width = monitor_width / config.cols as i32;
-        let cell_height = monitor_height / config.rows as i32;
-
-        let left = monitor_left + (col as i32 * cell_width);
-        let top = monitor_top + (row as i32 * cell_height);
-        let right = left + cell_width - 30;
-        let bottom = top + cell_height - 30;
-
-        Ok(RECT {
-            left,
-            top,
-            right,
-            bottom,
+                max_row: config.rows.saturating_sub(1),
+                max_col: config.cols.saturating_sub(1),
+            }
         })
     }
 
     /// Start a batch animation for multiple windows
     fn start_batch_animation(
         &mut self,
-        targets: HashMap<u64, (usize, usize, RECT)>,
+        targets: HashMap<u64, (usize, usize, Rect)>,
         duration_ms: u64,
         easing: EasingType,
     ) -> GridResult<()> {
@@ -2,10 +2,10 @@
 // This maintains backwards compatibility with the existing grid system
 
 use crate::config::GridConfig;
+use crate::geometry::Rect;
 use crate::grid::traits::{CellDisplay, GridResult, GridTrait};
 use crate::window::WindowInfo;
 use std::collections::HashMap;
-use winapi::shared::windef::RECT;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum BasicCellState {
@@ -44,7 +44,7 @@ impl BasicGrid {
     }
 
     /// Calculate which cells a window occupies based on its rectangle
-    fn window_to_cells(&self, rect: &RECT) -> Vec<(usize, usize)> {
+    fn window_to_cells(&self, rect: &Rect) -> Vec<(usize, usize)> {
         let (monitor_left, monitor_top, monitor_right, monitor_bottom) = self.monitor_bounds;
         let monitor_width = monitor_right - monitor_left;
         let monitor_height = monitor_bottom - monitor_top;
@@ -88,7 +88,7 @@ impl BasicGrid {
 
     /// Add a window to the grid
     pub fn add_window(&mut self, hwnd: u64, window_info: WindowInfo) -> GridResult<()> {
-        self.windows.insert(hwnd, window_info.clone());
+        self.windows.insert(hwnd, window_info);
 
         // Calculate which cells this window occupies
         let cells = self.window_to_cells(&window_info.window_rect);
@@ -155,7 +155,7 @@ impl GridTrait for BasicGrid {
         let windows_to_update: Vec<(u64, WindowInfo)> = self
             .windows
             .iter()
-            .map(|(&hwnd, info)| (hwnd, info.clone()))
+            .map(|(&hwnd, info)| (hwnd, *info))
             .collect();
 
         for (hwnd, window_info) in windows_to_update {
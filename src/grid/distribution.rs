@@ -0,0 +1,328 @@
+// Window distribution - spread windows over every monitor in optimally sized grids
+//
+// Planning only: windows and monitors go in, a target rectangle per window comes out.
+// WindowTracker::apply_distribution moves the windows, and GridIpcServer runs it as a
+// server mode.
+
+use std::collections::HashMap;
+
+use crate::config::GridConfig;
+use crate::geometry::Rect;
+use crate::grid::math::cell_to_rect;
+
+/// Grid size (rows, cols) for `n` windows: as few empty cells as possible, then as
+/// square as possible, never larger than `max_rows` x `max_cols`.
+pub fn optimal_grid(n: usize, max_rows: usize, max_cols: usize) -> (usize, usize) {
+    let (max_rows, max_cols) = (max_rows.max(1), max_cols.max(1));
+    if n <= 1 {
+        return (1, 1);
+    }
+    if n >= max_rows * max_cols {
+        return (max_rows, max_cols);
+    }
+    let mut best = (max_rows, max_cols);
+    let mut min_unused = usize::MAX;
+    for rows in 1..=max_rows {
+        for cols in 1..=max_cols {
+            if rows * cols < n {
+                continue;
+            }
+            let unused = rows * cols - n;
+            let aspect = rows.abs_diff(cols);
+            if unused < min_unused || (unused == min_unused && aspect < best.0.abs_diff(best.1)) {
+                best = (rows, cols);
+                min_unused = unused;
+            }
+        }
+    }
+    best
+}
+
+/// Split `count` windows between monitors in proportion to their area. A monitor never
+/// gets more than `capacity` windows while another still has room; the largest monitor
+/// takes whatever is left once every monitor is full.
+pub fn windows_per_monitor(monitors: &[Rect], count: usize, capacity: usize) -> Vec<usize> {
+    if monitors.is_empty() {
+        return Vec::new();
+    }
+    let areas: Vec<i64> = monitors.iter().map(|m| m.area().max(0)).collect();
+    let total_area: i64 = areas.iter().sum::<i64>().max(1);
+    let mut shares: Vec<usize> = areas
+        .iter()
+        .map(|&area| ((area as f64 / total_area as f64) * count as f64).round() as usize)
+        .collect();
+
+    // Rounding can leave the total a window or two off: the largest monitors absorb
+    // any shortfall and the smallest give back any excess
+    let mut by_size: Vec<usize> = (0..monitors.len()).collect();
+    by_size.sort_by_key(|&i| (std::cmp::Reverse(areas[i]), i));
+    let mut assigned: usize = shares.iter().sum();
+    for &i in by_size.iter().rev().cycle() {
+        if assigned <= count {
+            break;
+        }
+        if shares[i] > 0 {
+            shares[i] -= 1;
+            assigned -= 1;
+        }
+    }
+    shares[by_size[0]] += count - assigned;
+
+    // Move overflow to the largest monitors that still have empty cells
+    let capacity = capacity.max(1);
+    let mut overflow = 0;
+    for share in shares.iter_mut() {
+        if *share > capacity {
+            overflow += *share - capacity;
+            *share = capacity;
+        }
+    }
+    for &i in &by_size {
+        let room = (capacity - shares[i]).min(overflow);
+        shares[i] += room;
+        overflow -= room;
+    }
+    shares[by_size[0]] += overflow;
+    shares
+}
+
+/// Limits and spacing for a distribution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistributionConstraints {
+    /// Largest grid used on any one monitor
+    pub max_rows: usize,
+    pub max_cols: usize,
+    /// Pixels left between neighbouring windows
+    pub gap: i32,
+    /// Windows that are never distributed, e.g. the terminal running the tool
+    pub exclude: Vec<u64>,
+    /// Slide windows into place over this many milliseconds with this easing instead of
+    /// jumping there
+    #[cfg(feature = "animation")]
+    pub animation: Option<(u32, crate::EasingType)>,
+}
+
+impl Default for DistributionConstraints {
+    fn default() -> Self {
+        Self {
+            max_rows: 4,
+            max_cols: 4,
+            gap: 0,
+            exclude: Vec::new(),
+            #[cfg(feature = "animation")]
+            animation: None,
+        }
+    }
+}
+
+impl DistributionConstraints {
+    pub fn new(max_rows: usize, max_cols: usize) -> Self {
+        Self {
+            max_rows,
+            max_cols,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_gap(mut self, gap: i32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Never move `hwnd`.
+    pub fn with_exclusion(mut self, hwnd: u64) -> Self {
+        self.exclude.push(hwnd);
+        self
+    }
+
+    /// Animate windows into place.
+    #[cfg(feature = "animation")]
+    pub fn with_animation(mut self, duration_ms: u32, easing: crate::EasingType) -> Self {
+        self.animation = Some((duration_ms, easing));
+        self
+    }
+}
+
+/// The grid chosen for one monitor and the windows placed on it, in row-major order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorPlan {
+    pub monitor_id: usize,
+    pub area: Rect,
+    pub rows: usize,
+    pub cols: usize,
+    pub windows: Vec<u64>,
+}
+
+/// Where every window goes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DistributionPlan {
+    pub monitors: Vec<MonitorPlan>,
+    /// One target per window, in the order the windows were given
+    pub targets: Vec<(u64, Rect)>,
+}
+
+/// Spread `windows` over `monitors` (the areas to tile, indexed by monitor id). Each
+/// monitor gets a share in proportion to its size and the smallest grid that fits it.
+/// Windows beyond every monitor's largest grid share cells on the largest monitor.
+pub fn distribute(
+    windows: &[u64],
+    monitors: &[Rect],
+    constraints: &DistributionConstraints,
+) -> DistributionPlan {
+    let capacity = constraints.max_rows.max(1) * constraints.max_cols.max(1);
+    let shares = windows_per_monitor(monitors, windows.len(), capacity);
+    let mut plan = DistributionPlan::default();
+    let mut remaining = windows;
+    for (monitor_id, (area, share)) in monitors.iter().zip(shares).enumerate() {
+        let (mine, rest) = remaining.split_at(share);
+        remaining = rest;
+        let (rows, cols) = optimal_grid(mine.len(), constraints.max_rows, constraints.max_cols);
+        let config = GridConfig::new(rows, cols).with_gaps(constraints.gap, constraints.gap);
+        for (i, hwnd) in mine.iter().enumerate() {
+            let cell = i % (rows * cols);
+            if let Some(rect) = cell_to_rect(area, &config, cell / cols, cell % cols) {
+                plan.targets.push((*hwnd, rect));
+            }
+        }
+        plan.monitors.push(MonitorPlan {
+            monitor_id,
+            area: *area,
+            rows,
+            cols,
+            windows: mine.to_vec(),
+        });
+    }
+    plan
+}
+
+/// Keeps a distribution going as windows come and go: windows keep their place in line,
+/// newcomers join at the end, and everyone's original position is remembered so they can
+/// be put back.
+#[derive(Clone, Debug, Default)]
+pub struct Distributor {
+    pub constraints: DistributionConstraints,
+    order: Vec<u64>,
+    originals: HashMap<u64, Rect>,
+}
+
+impl Distributor {
+    pub fn new(constraints: DistributionConstraints) -> Self {
+        Self {
+            constraints,
+            ..Default::default()
+        }
+    }
+
+    /// Distributed windows, in the order they joined.
+    pub fn windows(&self) -> &[u64] {
+        &self.order
+    }
+
+    /// Where `hwnd` was before it was first distributed.
+    pub fn original_rect(&self, hwnd: u64) -> Option<Rect> {
+        self.originals.get(&hwnd).copied()
+    }
+
+    /// Bring the window list in line with `current`, the candidate windows and their
+    /// present rects. Returns true if any window joined or left.
+    pub fn sync(&mut self, current: &[(u64, Rect)]) -> bool {
+        let before = self.order.len();
+        self.order
+            .retain(|hwnd| current.iter().any(|(candidate, _)| candidate == hwnd));
+        self.originals.retain(|hwnd, _| self.order.contains(hwnd));
+        let mut changed = self.order.len() != before;
+        for (hwnd, rect) in current {
+            if self.constraints.exclude.contains(hwnd) || self.order.contains(hwnd) {
+                continue;
+            }
+            self.order.push(*hwnd);
+            self.originals.insert(*hwnd, *rect);
+            changed = true;
+        }
+        changed
+    }
+
+    /// Targets for the current windows over `monitors`.
+    pub fn plan(&self, monitors: &[Rect]) -> DistributionPlan {
+        distribute(&self.order, monitors, &self.constraints)
+    }
+
+    /// Each window's original position, to undo the distribution.
+    pub fn restore_targets(&self) -> Vec<(u64, Rect)> {
+        self.order
+            .iter()
+            .filter_map(|hwnd| Some((*hwnd, self.original_rect(*hwnd)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimal_grid() {
+        assert_eq!(optimal_grid(0, 4, 4), (1, 1));
+        assert_eq!(optimal_grid(1, 4, 4), (1, 1));
+        assert_eq!(optimal_grid(2, 4, 4), (1, 2));
+        assert_eq!(optimal_grid(4, 4, 4), (2, 2));
+        assert_eq!(optimal_grid(5, 4, 4), (2, 3));
+        assert_eq!(optimal_grid(9, 4, 4), (3, 3));
+        assert_eq!(optimal_grid(40, 4, 4), (4, 4));
+        assert_eq!(optimal_grid(3, 1, 2), (1, 2));
+    }
+
+    #[test]
+    fn test_distribute_across_monitors() {
+        // A 4K monitor has four times the room of a 1080p one
+        let monitors = [Rect::new(0, 0, 3840, 2160), Rect::new(3840, 0, 5760, 1080)];
+        assert_eq!(windows_per_monitor(&monitors, 5, 16), vec![4, 1]);
+        assert_eq!(windows_per_monitor(&monitors, 3, 16), vec![2, 1]);
+        // Overflow moves to the monitor with room, then piles onto the largest
+        assert_eq!(windows_per_monitor(&monitors, 10, 4), vec![6, 4]);
+
+        let constraints = DistributionConstraints::new(2, 2).with_gap(10);
+        let plan = distribute(&[1, 2, 3, 4, 5], &monitors, &constraints);
+        assert_eq!(plan.monitors[0].windows, vec![1, 2, 3, 4]);
+        assert_eq!((plan.monitors[0].rows, plan.monitors[0].cols), (2, 2));
+        assert_eq!((plan.monitors[1].rows, plan.monitors[1].cols), (1, 1));
+        assert_eq!(
+            plan.targets,
+            vec![
+                (1, Rect::new(0, 0, 1915, 1075)),
+                (2, Rect::new(1925, 0, 3840, 1075)),
+                (3, Rect::new(0, 1085, 1915, 2160)),
+                (4, Rect::new(1925, 1085, 3840, 2160)),
+                (5, Rect::new(3840, 0, 5760, 1080)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distributor_regrows_and_restores() {
+        let monitor = [Rect::new(0, 0, 1200, 800)];
+        let mut distributor =
+            Distributor::new(DistributionConstraints::default().with_exclusion(99));
+        let start = |x| Rect::new(x, 0, x + 100, 100);
+
+        assert!(distributor.sync(&[(1, start(0)), (99, start(50)), (2, start(10))]));
+        assert!(!distributor.sync(&[(2, start(400)), (1, start(300))]));
+        assert_eq!(distributor.windows(), &[1, 2]);
+        assert_eq!(
+            distributor.plan(&monitor).targets,
+            vec![
+                (1, Rect::new(0, 0, 600, 800)),
+                (2, Rect::new(600, 0, 1200, 800)),
+            ]
+        );
+
+        // A window leaves and another arrives: the grid regrows around the newcomer
+        assert!(distributor.sync(&[(2, start(600)), (3, start(20)), (4, start(30))]));
+        assert_eq!(distributor.windows(), &[2, 3, 4]);
+        assert_eq!(distributor.plan(&monitor).monitors[0].cols, 3);
+        assert_eq!(
+            distributor.restore_targets(),
+            vec![(2, start(10)), (3, start(20)), (4, start(30))]
+        );
+    }
+}
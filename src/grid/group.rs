@@ -0,0 +1,156 @@
+// Window groups - tabbed windows sharing one cell span
+//
+// Only the active member of a group is shown; the others are hidden and untracked
+// until they become active, when they take the active member's rect. The group's span
+// is therefore always the span of the window on screen, and moving or resizing that
+// window moves and resizes the whole group.
+//
+// Hidden members follow lazily: their own rects go stale while the group moves, and
+// they are put in the group's span only when shown again, by becoming active or by
+// leaving the group. Until then they are reported at the active member's rect.
+
+/// Most windows one group holds, matching what a `WindowGroupMessage` can list.
+pub const MAX_GROUP_MEMBERS: usize = 16;
+
+/// Windows sharing one place on the grid, in tab order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WindowGroup {
+    pub id: u32,
+    pub members: Vec<u64>,
+    /// Index into `members` of the window on screen
+    pub active: usize,
+}
+
+/// Where a window sits in its group, as reported to clients drawing tab strips.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GroupMembership {
+    pub group_id: u32,
+    pub index: usize,
+    pub size: usize,
+    pub is_active: bool,
+}
+
+impl WindowGroup {
+    pub fn new(id: u32, first: u64) -> Self {
+        Self {
+            id,
+            members: vec![first],
+            active: 0,
+        }
+    }
+
+    /// The member on screen, or None once every member was removed.
+    pub fn active_window(&self) -> Option<u64> {
+        self.members.get(self.active).copied()
+    }
+
+    pub fn contains(&self, hwnd: u64) -> bool {
+        self.members.contains(&hwnd)
+    }
+
+    pub fn membership(&self, hwnd: u64) -> Option<GroupMembership> {
+        let index = self.members.iter().position(|member| *member == hwnd)?;
+        Some(GroupMembership {
+            group_id: self.id,
+            index,
+            size: self.members.len(),
+            is_active: index == self.active,
+        })
+    }
+
+    /// Add a window as the tab after the active one and make it active. Returns false
+    /// if it is already a member.
+    pub fn add(&mut self, hwnd: u64) -> bool {
+        if self.contains(hwnd) {
+            return false;
+        }
+        self.active += 1;
+        self.members.insert(self.active, hwnd);
+        true
+    }
+
+    /// Remove a window. If it was active, the next tab (or the previous one, for the
+    /// last tab) becomes active. Returns false if it wasn't a member.
+    pub fn remove(&mut self, hwnd: u64) -> bool {
+        let Some(index) = self.members.iter().position(|member| *member == hwnd) else {
+            return false;
+        };
+        self.members.remove(index);
+        if index < self.active || self.active >= self.members.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        true
+    }
+
+    /// Make the next tab active, or the previous one when `forward` is false, wrapping
+    /// around. Returns the new active window, or None for an empty group.
+    pub fn cycle(&mut self, forward: bool) -> Option<u64> {
+        let count = self.members.len();
+        if count == 0 {
+            return None;
+        }
+        self.active = if forward {
+            (self.active + 1) % count
+        } else {
+            (self.active + count - 1) % count
+        };
+        self.active_window()
+    }
+
+    /// Make `hwnd` the active member. Returns false if it isn't a member.
+    pub fn activate(&mut self, hwnd: u64) -> bool {
+        match self.members.iter().position(|member| *member == hwnd) {
+            Some(index) => {
+                self.active = index;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_group_tabs() {
+        let mut group = WindowGroup::new(1, 10);
+        assert!(group.add(20));
+        assert!(!group.add(20));
+        assert_eq!(group.active_window(), Some(20));
+
+        // New tabs open after the active one
+        group.activate(10);
+        group.add(30);
+        assert_eq!(group.members, vec![10, 30, 20]);
+        assert_eq!(
+            group.membership(30),
+            Some(GroupMembership {
+                group_id: 1,
+                index: 1,
+                size: 3,
+                is_active: true,
+            })
+        );
+        assert_eq!(group.membership(99), None);
+
+        assert_eq!(group.cycle(true), Some(20));
+        assert_eq!(group.cycle(true), Some(10));
+        assert_eq!(group.cycle(false), Some(20));
+
+        // Removing the active last tab activates the one before it; removing an
+        // earlier tab keeps the same window active
+        assert!(group.remove(20));
+        assert_eq!(group.active_window(), Some(30));
+        assert!(group.remove(10));
+        assert_eq!(group.active_window(), Some(30));
+        assert!(!group.remove(10));
+        assert_eq!(group.members, vec![30]);
+
+        // An emptied group has no active window rather than panicking
+        assert!(group.remove(30));
+        assert_eq!(group.active_window(), None);
+        assert_eq!(group.cycle(true), None);
+    }
+}
@@ -1,6 +1,6 @@
 // Layout Grid - Grid that supports saving and loading window arrangements
 
-use crate::config::GridConfig;
+use crate::config::{CellPath, GridConfig, ZoneLayout};
 use crate::grid::basic::BasicGrid;
 use crate::grid::traits::{GridError, GridResult, GridTrait, LayoutGrid as LayoutGridTrait};
 use crate::window_tracker::WindowTracker;
@@ -17,6 +17,9 @@ pub struct MonitorGridLayout {
     pub monitor_id: usize,
     pub config: GridConfig,
     pub grid: Vec<Vec<Option<u64>>>,
+    pub subgrid_cells: Vec<(CellPath, Option<u64>)>, // cells inside sub-grids, by path
+    pub zones: Option<ZoneLayout>,
+    pub zone_owners: Vec<Option<u64>>, // by zone index
 }
 #[derive(Debug, Clone)]
 pub struct GridLayout {
@@ -76,7 +79,7 @@ impl LayoutGrid {
                                 .basic_grid
                                 .get_cell_windows(row, col)
                                 .unwrap_or_default();
-                            cell_windows.get(0).copied()
+                            cell_windows.first().copied()
                         })
                         .collect()
                 })
@@ -142,6 +145,16 @@ impl LayoutGrid {
                 monitor_id: monitor_grid.monitor_id,
                 config: monitor_grid.config.clone(),
                 grid: vec![vec![None; monitor_grid.config.cols]; monitor_grid.config.rows],
+                subgrid_cells: monitor_grid
+                    .subgrid_cells
+                    .iter()
+                    .map(|(path, state)| match state {
+                        CellState::Occupied(hwnd) => (path.clone(), Some(*hwnd)),
+                        _ => (path.clone(), None),
+                    })
+                    .collect(),
+                zones: monitor_grid.zones.clone(),
+                zone_owners: monitor_grid.zone_owners.clone(),
             };
 
             for row in 0..monitor_grid.config.rows {
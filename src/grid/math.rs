@@ -0,0 +1,857 @@
+// Grid math - pure cell/pixel conversions shared by every grid type
+//
+// Nothing here touches the windowing system, so the grid engine can be reused
+// by tools that never link against winapi.
+
+use crate::config::{CellOccupancyStrategy, CellPath, GridConfig, TrackSize, Zone, ZoneLayout};
+use crate::geometry::{Point, Rect, UsizeRect};
+
+/// Nominal size of a single cell when `area` is split into `config.rows` x `config.cols`.
+/// Cells may be a pixel larger than this when the area doesn't divide evenly, and weighted
+/// grids have cells of different sizes; use `cell_to_rect` for exact bounds.
+pub fn cell_size(area: &Rect, config: &GridConfig) -> (i32, i32) {
+    if config.rows == 0 || config.cols == 0 {
+        return (0, 0);
+    }
+    (
+        area.width() / config.cols as i32,
+        area.height() / config.rows as i32,
+    )
+}
+
+/// Boundaries of `count` tracks laid over `start..start + length`.
+///
+/// Returns `count + 1` ascending positions; track `i` spans `[offsets[i], offsets[i + 1])`.
+/// With no `tracks` (or a list whose length doesn't match `count`) every track gets the
+/// same size, give or take the one pixel needed to cover the whole length. Otherwise `Px`
+/// tracks are placed first and `Fr` tracks share what is left.
+pub fn track_offsets(start: i32, length: i32, count: usize, tracks: &[TrackSize]) -> Vec<i32> {
+    if count == 0 {
+        return vec![start];
+    }
+
+    if tracks.len() != count {
+        // Exact partition: remainder pixels are spread across the tracks instead of
+        // being left over at the end, so the last boundary is always `start + length`
+        let (length, count) = (length as i64, count as i64);
+        return (0..=count)
+            .map(|i| start + (length * i / count) as i32)
+            .collect();
+    }
+
+    let fixed: i64 = tracks
+        .iter()
+        .map(|track| match track {
+            TrackSize::Px(px) => (*px).max(0) as i64,
+            TrackSize::Fr(_) => 0,
+        })
+        .sum();
+    let total_fr: i64 = tracks
+        .iter()
+        .map(|track| match track {
+            TrackSize::Fr(fr) => *fr as i64,
+            TrackSize::Px(_) => 0,
+        })
+        .sum();
+    let flexible = (length as i64 - fixed).max(0);
+
+    // Accumulate in whole units so rounding never drifts across tracks
+    let mut offsets = Vec::with_capacity(count + 1);
+    let (mut px_before, mut fr_before) = (0i64, 0i64);
+    offsets.push(start);
+    for track in tracks {
+        match track {
+            TrackSize::Px(px) => px_before += (*px).max(0) as i64,
+            TrackSize::Fr(fr) => fr_before += *fr as i64,
+        }
+        let flex_before = if total_fr > 0 {
+            flexible * fr_before / total_fr
+        } else {
+            0
+        };
+        offsets.push(start + (px_before + flex_before) as i32);
+    }
+    offsets
+}
+
+/// Where `count` tracks sit inside `start..start + length` once `padding` (before, after)
+/// and a `gap` between neighbouring tracks are taken out.
+///
+/// Returns one `(start, end)` pair per track. Windows are placed on these extents.
+pub fn track_extents(
+    start: i32,
+    length: i32,
+    count: usize,
+    tracks: &[TrackSize],
+    padding: (i32, i32),
+    gap: i32,
+) -> Vec<(i32, i32)> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let (pad_before, pad_after) = padding;
+    let content = (length - pad_before - pad_after - gap * (count as i32 - 1)).max(0);
+    let offsets = track_offsets(0, content, count, tracks);
+    (0..count)
+        .map(|i| {
+            let shift = start + pad_before + i as i32 * gap;
+            (shift + offsets[i], shift + offsets[i + 1])
+        })
+        .collect()
+}
+
+/// Cell boundaries for occupancy: each gutter is split between its neighbours and the
+/// outer padding belongs to the edge tracks, so the cells still cover the whole area.
+fn extents_to_offsets(extents: &[(i32, i32)], padding: (i32, i32), gap: i32) -> Vec<i32> {
+    let mut offsets = Vec::with_capacity(extents.len() + 1);
+    if let (Some(first), Some(last)) = (extents.first(), extents.last()) {
+        offsets.push(first.0 - padding.0);
+        offsets.extend(extents[..extents.len() - 1].iter().map(|e| e.1 + gap / 2));
+        offsets.push(last.1 + padding.1);
+    }
+    offsets
+}
+
+/// Column extents (left, right) of the grid laid over `area`, after padding and gutters.
+pub fn column_extents(area: &Rect, config: &GridConfig) -> Vec<(i32, i32)> {
+    track_extents(
+        area.left,
+        area.width(),
+        config.cols,
+        &config.col_tracks,
+        (config.padding.left, config.padding.right),
+        config.col_gap,
+    )
+}
+
+/// Row extents (top, bottom) of the grid laid over `area`, after padding and gutters.
+pub fn row_extents(area: &Rect, config: &GridConfig) -> Vec<(i32, i32)> {
+    track_extents(
+        area.top,
+        area.height(),
+        config.rows,
+        &config.row_tracks,
+        (config.padding.top, config.padding.bottom),
+        config.row_gap,
+    )
+}
+
+/// Column boundaries of the grid laid over `area`.
+pub fn column_offsets(area: &Rect, config: &GridConfig) -> Vec<i32> {
+    if config.cols == 0 {
+        return vec![area.left];
+    }
+    extents_to_offsets(
+        &column_extents(area, config),
+        (config.padding.left, config.padding.right),
+        config.col_gap,
+    )
+}
+
+/// Row boundaries of the grid laid over `area`.
+pub fn row_offsets(area: &Rect, config: &GridConfig) -> Vec<i32> {
+    if config.rows == 0 {
+        return vec![area.top];
+    }
+    extents_to_offsets(
+        &row_extents(area, config),
+        (config.padding.top, config.padding.bottom),
+        config.row_gap,
+    )
+}
+
+/// Index of the track containing `pos`. Positions before the first track map to 0,
+/// positions at or past the last boundary map to the track count.
+fn track_index(offsets: &[i32], pos: i32) -> usize {
+    offsets.partition_point(|&boundary| boundary <= pos).max(1) - 1
+}
+
+/// Window rectangle for cell (row, col) inside `area`, with padding and gutters applied.
+pub fn cell_to_rect(area: &Rect, config: &GridConfig, row: usize, col: usize) -> Option<Rect> {
+    cell_span_to_rect(area, config, row, col, 1, 1)
+}
+
+/// Window rectangle covering `row_span` x `col_span` cells starting at (row, col).
+/// The inner gutters between the spanned cells become part of the window.
+pub fn cell_span_to_rect(
+    area: &Rect,
+    config: &GridConfig,
+    row: usize,
+    col: usize,
+    row_span: usize,
+    col_span: usize,
+) -> Option<Rect> {
+    if row_span == 0
+        || col_span == 0
+        || row + row_span > config.rows
+        || col + col_span > config.cols
+    {
+        return None;
+    }
+    let cols = column_extents(area, config);
+    let rows = row_extents(area, config);
+    Some(Rect::new(
+        cols[col].0,
+        rows[row].0,
+        cols[col + col_span - 1].1,
+        rows[row + row_span - 1].1,
+    ))
+}
+
+/// True if a window at `window` occupies `cell` under `strategy`.
+pub fn occupies_cell(strategy: CellOccupancyStrategy, window: &Rect, cell: &Rect) -> bool {
+    let cell_area = cell.area();
+    if cell_area <= 0 {
+        return false;
+    }
+    let coverage = window.intersection_area(cell) as f64 / cell_area as f64;
+    match strategy {
+        CellOccupancyStrategy::AnyOverlap => coverage > 0.0,
+        CellOccupancyStrategy::MinCoverage(fraction) => {
+            coverage > 0.0 && coverage >= fraction as f64
+        }
+        CellOccupancyStrategy::CenterPoint => cell.contains_point(window.center()),
+        CellOccupancyStrategy::MajorityOwner => coverage >= 0.5,
+    }
+}
+
+/// Cells of the grid laid over `area` that `rect` occupies under `config.occupancy`.
+pub fn window_to_grid_cells(area: &Rect, config: &GridConfig, rect: &Rect) -> Vec<(usize, usize)> {
+    let strategy = config.occupancy;
+    overlapped_cells(area, config, rect)
+        .into_iter()
+        .filter(|(_, _, cell_rect)| occupies_cell(strategy, rect, cell_rect))
+        .map(|(row, col, _)| (row, col))
+        .collect()
+}
+
+/// Every cell `rect` overlaps, with the cell's boundaries.
+fn overlapped_cells(area: &Rect, config: &GridConfig, rect: &Rect) -> Vec<(usize, usize, Rect)> {
+    let mut cells = Vec::new();
+
+    // Skip windows with invalid rectangle (right must be > left, bottom > top)
+    if rect.right < rect.left || rect.bottom < rect.top {
+        return cells;
+    }
+
+    if config.rows == 0 || config.cols == 0 {
+        return cells;
+    }
+    let cols = column_offsets(area, config);
+    let rows = row_offsets(area, config);
+    if cols[0] == cols[config.cols] || rows[0] == rows[config.rows] {
+        return cells;
+    }
+
+    // Only the part of the window inside the grid area can cover cells
+    let clipped = match rect.intersection(area) {
+        Some(clipped) => clipped,
+        None => return cells,
+    };
+
+    // Calculate potential range of cells that might be affected
+    let start_col = track_index(&cols, clipped.left);
+    let end_col = track_index(&cols, clipped.right).min(config.cols - 1);
+    let start_row = track_index(&rows, clipped.top);
+    let end_row = track_index(&rows, clipped.bottom).min(config.rows - 1);
+
+    for row in start_row..=end_row {
+        for col in start_col..=end_col {
+            let cell_rect = Rect::new(cols[col], rows[row], cols[col + 1], rows[row + 1]);
+            if cell_rect.intersects(rect) {
+                cells.push((row, col, cell_rect));
+            }
+        }
+    }
+
+    cells
+}
+
+/// Owner of every cell of the grid laid over `area`, indexed `[row][col]`.
+///
+/// `windows` is in z-order, topmost last: with the per-window strategies a later window
+/// takes over cells it shares with an earlier one. With `MajorityOwner` each cell goes to
+/// the window covering most of it, the earlier one on a tie.
+pub fn assign_cell_owners(
+    area: &Rect,
+    config: &GridConfig,
+    windows: &[(u64, Rect)],
+) -> Vec<Vec<Option<u64>>> {
+    let mut owners = vec![vec![None; config.cols]; config.rows];
+    if config.occupancy != CellOccupancyStrategy::MajorityOwner {
+        for (hwnd, rect) in windows {
+            for (row, col) in window_to_grid_cells(area, config, rect) {
+                owners[row][col] = Some(*hwnd);
+            }
+        }
+        return owners;
+    }
+
+    let mut best = vec![vec![0i64; config.cols]; config.rows];
+    for (hwnd, rect) in windows {
+        for (row, col, cell_rect) in overlapped_cells(area, config, rect) {
+            let covered = cell_rect.intersection_area(rect);
+            if covered > best[row][col] {
+                best[row][col] = covered;
+                owners[row][col] = Some(*hwnd);
+            }
+        }
+    }
+    owners
+}
+
+/// Pixel rectangle of the cell at `path`, descending through the sub-grids of `config`.
+pub fn cell_path_to_rect(area: &Rect, config: &GridConfig, path: &CellPath) -> Option<Rect> {
+    let (&(row, col), rest) = path.cells().split_first()?;
+    let cell = cell_to_rect(area, config, row, col)?;
+    if rest.is_empty() {
+        return Some(cell);
+    }
+    cell_path_to_rect(&cell, config.subgrid(row, col)?, &CellPath(rest.to_vec()))
+}
+
+/// Every cell `rect` occupies, at every level: top-level cells plus the cells of each
+/// sub-grid, each sub-grid applying its own occupancy rule.
+pub fn window_to_cell_paths(area: &Rect, config: &GridConfig, rect: &Rect) -> Vec<CellPath> {
+    let mut paths: Vec<CellPath> = window_to_grid_cells(area, config, rect)
+        .into_iter()
+        .map(CellPath::from)
+        .collect();
+    for sub in &config.subgrids {
+        let Some(cell) = cell_to_rect(area, config, sub.row, sub.col) else {
+            continue;
+        };
+        let parent = CellPath::new(sub.row, sub.col);
+        for child in window_to_cell_paths(&cell, &sub.grid, rect) {
+            let mut cells = parent.0.clone();
+            cells.extend(child.0);
+            paths.push(CellPath(cells));
+        }
+    }
+    paths
+}
+
+/// Owner of every cell inside the sub-grids of `config`, at every depth, keyed by path.
+/// Top-level cells are left to [`assign_cell_owners`]; `windows` is in z-order,
+/// topmost last.
+pub fn assign_subgrid_owners(
+    area: &Rect,
+    config: &GridConfig,
+    windows: &[(u64, Rect)],
+) -> Vec<(CellPath, Option<u64>)> {
+    let mut owners = Vec::new();
+    for sub in &config.subgrids {
+        let Some(cell) = cell_to_rect(area, config, sub.row, sub.col) else {
+            continue;
+        };
+        let parent = CellPath::new(sub.row, sub.col);
+        for (row, row_owners) in assign_cell_owners(&cell, &sub.grid, windows)
+            .into_iter()
+            .enumerate()
+        {
+            for (col, owner) in row_owners.into_iter().enumerate() {
+                owners.push((parent.child(row, col), owner));
+            }
+        }
+        for (child, owner) in assign_subgrid_owners(&cell, &sub.grid, windows) {
+            let mut cells = parent.0.clone();
+            cells.extend(child.0);
+            owners.push((CellPath(cells), owner));
+        }
+    }
+    owners
+}
+
+/// Pixel rectangle of `zone` laid over `area`. Edges are rounded, so zones sharing a
+/// fractional edge share a pixel edge.
+pub fn zone_to_rect(area: &Rect, zone: &Zone) -> Rect {
+    let edge =
+        |start: i32, size: i32, fraction: f64| start + (size as f64 * fraction).round() as i32;
+    let (width, height) = (area.width(), area.height());
+    Rect::new(
+        edge(area.left, width, zone.x as f64),
+        edge(area.top, height, zone.y as f64),
+        edge(area.left, width, zone.x as f64 + zone.width as f64),
+        edge(area.top, height, zone.y as f64 + zone.height as f64),
+    )
+}
+
+/// Indices of the zones of `layout` that `rect` occupies under `layout.occupancy`.
+pub fn window_to_zones(area: &Rect, layout: &ZoneLayout, rect: &Rect) -> Vec<usize> {
+    layout
+        .zones
+        .iter()
+        .enumerate()
+        .filter(|(_, zone)| occupies_cell(layout.occupancy, rect, &zone_to_rect(area, zone)))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Owner of every zone of `layout` laid over `area`, by zone index. Same rules as
+/// [`assign_cell_owners`]: `windows` is in z-order, topmost last.
+pub fn assign_zone_owners(
+    area: &Rect,
+    layout: &ZoneLayout,
+    windows: &[(u64, Rect)],
+) -> Vec<Option<u64>> {
+    let mut owners = vec![None; layout.zones.len()];
+    let mut best = vec![0i64; layout.zones.len()];
+    for (i, zone) in layout.zones.iter().enumerate() {
+        let zone_rect = zone_to_rect(area, zone);
+        for (hwnd, rect) in windows {
+            if layout.occupancy == CellOccupancyStrategy::MajorityOwner {
+                let covered = zone_rect.intersection_area(rect);
+                if covered > best[i] {
+                    best[i] = covered;
+                    owners[i] = Some(*hwnd);
+                }
+            } else if occupies_cell(layout.occupancy, rect, &zone_rect) {
+                owners[i] = Some(*hwnd);
+            }
+        }
+    }
+    owners
+}
+
+/// Cell containing `point`, or the nearest one when `point` lies outside the grid or
+/// in a gutter. Descends into sub-grids, so the path ends at a cell that isn't split.
+pub fn nearest_cell_path(area: &Rect, config: &GridConfig, point: Point) -> Option<CellPath> {
+    if config.rows == 0 || config.cols == 0 {
+        return None;
+    }
+    let col = track_index(&column_offsets(area, config), point.x).min(config.cols - 1);
+    let row = track_index(&row_offsets(area, config), point.y).min(config.rows - 1);
+    let mut path = CellPath::new(row, col);
+    if let Some(sub) = config.subgrid(row, col) {
+        let cell = cell_to_rect(area, config, row, col)?;
+        if let Some(child) = nearest_cell_path(&cell, sub, point) {
+            path.0.extend(child.0);
+        }
+    }
+    Some(path)
+}
+
+/// Cell span `rect` covers most, as its top-left and bottom-right `(row, col)`: each
+/// edge of `rect` moves to the nearest cell boundary, keeping at least one cell.
+/// `None` if `rect` misses the grid.
+pub fn covered_span(
+    area: &Rect,
+    config: &GridConfig,
+    rect: &Rect,
+) -> Option<((usize, usize), (usize, usize))> {
+    if config.rows == 0 || config.cols == 0 || rect.intersection(area).is_none() {
+        return None;
+    }
+    let (left, right) = nearest_tracks(&column_offsets(area, config), rect.left, rect.right);
+    let (top, bottom) = nearest_tracks(&row_offsets(area, config), rect.top, rect.bottom);
+    Some(((top, left), (bottom, right)))
+}
+
+/// First and last track of the run whose outer boundaries lie nearest `start` and `end`.
+fn nearest_tracks(offsets: &[i32], start: i32, end: i32) -> (usize, usize) {
+    let count = offsets.len() - 1;
+    let nearest = |pos: i32, candidates: std::ops::Range<usize>| {
+        candidates
+            .min_by_key(|&i| (offsets[i] - pos).abs())
+            .unwrap_or_default()
+    };
+    let first = nearest(start, 0..count);
+    let last = nearest(end, first + 1..count + 1) - 1;
+    (first, last)
+}
+
+/// Cells of the grid over `area` that overlap none of `monitors`, indexed `[row][col]`.
+/// With offset or mixed-size monitors these are the dead space in their bounding box.
+pub fn offscreen_cells(area: &Rect, config: &GridConfig, monitors: &[Rect]) -> Vec<Vec<bool>> {
+    (0..config.rows)
+        .map(|row| {
+            (0..config.cols)
+                .map(|col| {
+                    cell_to_rect(area, config, row, col)
+                        .is_none_or(|cell| !monitors.iter().any(|m| cell.intersects(m)))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `(row, col)` itself if it is on screen, otherwise the on-screen cell whose centre
+/// lies nearest its centre. `None` if every cell is off screen.
+pub fn nearest_onscreen_cell(
+    area: &Rect,
+    config: &GridConfig,
+    offscreen: &[Vec<bool>],
+    row: usize,
+    col: usize,
+) -> Option<(usize, usize)> {
+    let is_offscreen = |r: usize, c: usize| {
+        offscreen
+            .get(r)
+            .and_then(|cells| cells.get(c))
+            .copied()
+            .unwrap_or(false)
+    };
+    if !is_offscreen(row, col) {
+        return Some((row, col));
+    }
+    let target = cell_to_rect(area, config, row, col)?.center();
+    (0..config.rows)
+        .flat_map(|r| (0..config.cols).map(move |c| (r, c)))
+        .filter(|&(r, c)| !is_offscreen(r, c))
+        .filter_map(|(r, c)| Some(((r, c), cell_to_rect(area, config, r, c)?.center())))
+        .min_by_key(|(_, centre)| {
+            let (dx, dy) = ((centre.x - target.x) as i64, (centre.y - target.y) as i64);
+            dx * dx + dy * dy
+        })
+        .map(|(cell, _)| cell)
+}
+
+/// Bounding grid rectangle (start_col, start_row, end_col, end_row) of `rect`.
+pub fn window_to_grid_rect(area: &Rect, config: &GridConfig, rect: &Rect) -> UsizeRect {
+    // Skip invalid rectangles
+    if rect.is_empty() || config.rows == 0 || config.cols == 0 {
+        return UsizeRect::default();
+    }
+
+    let cols = column_offsets(area, config);
+    let rows = row_offsets(area, config);
+    if cols[0] == cols[config.cols] || rows[0] == rows[config.rows] {
+        return UsizeRect::default();
+    }
+
+    let start_col = track_index(&cols, rect.left);
+    let end_col = track_index(&cols, rect.right - 1).min(config.cols - 1);
+    let start_row = track_index(&rows, rect.top);
+    let end_row = track_index(&rows, rect.bottom - 1).min(config.rows - 1);
+
+    if start_row > end_row || start_col > end_col {
+        return UsizeRect::default();
+    }
+
+    UsizeRect::from_bounds(start_col, start_row, end_col, end_row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Insets;
+
+    #[test]
+    fn test_cell_round_trip() {
+        let area = Rect::new(0, 0, 1920, 1080);
+        let config = GridConfig::new(2, 2);
+        let cell = cell_to_rect(&area, &config, 1, 1).unwrap();
+        assert_eq!(cell, Rect::new(960, 540, 1920, 1080));
+        assert_eq!(window_to_grid_cells(&area, &config, &cell), vec![(1, 1)]);
+        assert_eq!(
+            window_to_grid_rect(&area, &config, &Rect::new(0, 0, 1920, 540)),
+            UsizeRect::from_bounds(0, 0, 1, 0)
+        );
+        assert!(cell_to_rect(&area, &config, 2, 0).is_none());
+    }
+
+    #[test]
+    fn test_window_outside_area_has_no_cells() {
+        let area = Rect::new(0, 0, 1920, 1080);
+        let config = GridConfig::new(4, 4);
+        let offscreen = Rect::new(-800, -600, -10, -10);
+        assert!(window_to_grid_cells(&area, &config, &offscreen).is_empty());
+    }
+
+    #[test]
+    fn test_weighted_tracks() {
+        let area = Rect::new(0, 0, 1920, 1080);
+        let config = GridConfig::with_tracks(
+            vec![TrackSize::Fr(1), TrackSize::Fr(2)],
+            vec![TrackSize::Px(300), TrackSize::Fr(1), TrackSize::Px(300)],
+        );
+        assert_eq!(column_offsets(&area, &config), vec![0, 300, 1620, 1920]);
+        assert_eq!(row_offsets(&area, &config), vec![0, 360, 1080]);
+        assert_eq!(
+            cell_to_rect(&area, &config, 1, 1),
+            Some(Rect::new(300, 360, 1620, 1080))
+        );
+
+        // A window filling the centre column lands only in that column
+        let centre = Rect::new(300, 0, 1620, 1080);
+        assert_eq!(
+            window_to_grid_cells(&area, &config, &centre),
+            vec![(0, 1), (1, 1)]
+        );
+        assert_eq!(
+            window_to_grid_rect(&area, &config, &centre),
+            UsizeRect::from_bounds(1, 0, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_gaps_and_padding() {
+        let area = Rect::new(0, 0, 1000, 500);
+        let config = GridConfig::new(1, 2)
+            .with_padding(Insets::uniform(10))
+            .with_gaps(0, 20);
+        let left = cell_to_rect(&area, &config, 0, 0).unwrap();
+        let right = cell_to_rect(&area, &config, 0, 1).unwrap();
+        assert_eq!(left, Rect::new(10, 10, 490, 490));
+        assert_eq!(right, Rect::new(510, 10, 990, 490));
+
+        // A span absorbs the gutter between its cells
+        assert_eq!(
+            cell_span_to_rect(&area, &config, 0, 0, 1, 2),
+            Some(Rect::new(10, 10, 990, 490))
+        );
+        assert!(cell_span_to_rect(&area, &config, 0, 1, 1, 2).is_none());
+
+        // Occupancy boundaries still cover the whole area, split mid-gutter
+        assert_eq!(column_offsets(&area, &config), vec![0, 500, 1000]);
+        assert_eq!(window_to_grid_cells(&area, &config, &right), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn test_cells_tile_area_exactly() {
+        // Sizes that don't divide evenly by the track counts
+        let area = Rect::new(-1366, 7, 0, 775);
+        for (rows, cols) in [(8, 12), (3, 7), (5, 5), (1, 1)] {
+            let config = GridConfig::new(rows, cols);
+            let mut covered = 0i64;
+            for row in 0..rows {
+                for col in 0..cols {
+                    let cell = cell_to_rect(&area, &config, row, col).unwrap();
+                    assert!(!cell.is_empty());
+                    covered += cell.width() as i64 * cell.height() as i64;
+                    // Neighbours share an edge: no gap, no overlap
+                    if col + 1 < cols {
+                        let right = cell_to_rect(&area, &config, row, col + 1).unwrap();
+                        assert_eq!(cell.right, right.left);
+                    }
+                    if row + 1 < rows {
+                        let below = cell_to_rect(&area, &config, row + 1, col).unwrap();
+                        assert_eq!(cell.bottom, below.top);
+                    }
+                }
+            }
+            assert_eq!(covered, area.width() as i64 * area.height() as i64);
+
+            let last = cell_to_rect(&area, &config, rows - 1, cols - 1).unwrap();
+            assert_eq!((last.right, last.bottom), (area.right, area.bottom));
+            assert_eq!(
+                cell_span_to_rect(&area, &config, 0, 0, rows, cols),
+                Some(area)
+            );
+        }
+    }
+
+    #[test]
+    fn test_occupancy_strategies() {
+        let area = Rect::new(0, 0, 400, 400);
+        let config = GridConfig::new(4, 4);
+        // Fills cell (0, 0) and pokes 10px into the cell to its right
+        let window = Rect::new(0, 0, 110, 100);
+
+        let cells_with = |strategy| {
+            let config = config.clone().with_occupancy(strategy);
+            window_to_grid_cells(&area, &config, &window)
+        };
+        assert_eq!(
+            cells_with(CellOccupancyStrategy::AnyOverlap),
+            vec![(0, 0), (0, 1)]
+        );
+        assert_eq!(
+            cells_with(CellOccupancyStrategy::MinCoverage(0.3)),
+            vec![(0, 0)]
+        );
+        assert_eq!(cells_with(CellOccupancyStrategy::CenterPoint), vec![(0, 0)]);
+        assert_eq!(
+            cells_with(CellOccupancyStrategy::MajorityOwner),
+            vec![(0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_cell_owners() {
+        let area = Rect::new(0, 0, 200, 100);
+        // Bottom window covers 70% of cell (0, 1), the top one covers 30% of it
+        let windows = [
+            (1, Rect::new(0, 0, 170, 100)),
+            (2, Rect::new(170, 0, 200, 100)),
+        ];
+
+        let config = GridConfig::new(1, 2).with_occupancy(CellOccupancyStrategy::AnyOverlap);
+        assert_eq!(
+            assign_cell_owners(&area, &config, &windows),
+            vec![vec![Some(1), Some(2)]]
+        );
+
+        let config = config.with_occupancy(CellOccupancyStrategy::MajorityOwner);
+        assert_eq!(
+            assign_cell_owners(&area, &config, &windows),
+            vec![vec![Some(1), Some(1)]]
+        );
+    }
+
+    #[test]
+    fn test_zones() {
+        let area = Rect::new(100, 0, 1300, 600);
+        // Overlapping zones: a left half, a right half and a centred "focus" zone
+        let layout = ZoneLayout::new("Focus")
+            .with_zone("Left", 0.0, 0.0, 0.5, 1.0)
+            .with_zone("Right", 0.5, 0.0, 0.5, 1.0)
+            .with_zone("Focus", 0.25, 0.25, 0.5, 0.5);
+        assert!(layout.validate().is_ok());
+        assert_eq!(
+            zone_to_rect(&area, &layout.zones[0]),
+            Rect::new(100, 0, 700, 600)
+        );
+        assert_eq!(
+            zone_to_rect(&area, &layout.zones[2]),
+            Rect::new(400, 150, 1000, 450)
+        );
+
+        let focus = Rect::new(400, 150, 1000, 450);
+        let layout = layout.with_occupancy(CellOccupancyStrategy::MinCoverage(0.5));
+        assert_eq!(window_to_zones(&area, &layout, &focus), vec![2]);
+        let layout = layout.with_occupancy(CellOccupancyStrategy::AnyOverlap);
+        assert_eq!(window_to_zones(&area, &layout, &focus), vec![0, 1, 2]);
+
+        let windows = [(1, Rect::new(100, 0, 700, 600)), (2, focus)];
+        assert_eq!(
+            assign_zone_owners(&area, &layout, &windows),
+            vec![Some(2), Some(2), Some(2)]
+        );
+        let layout = layout.with_occupancy(CellOccupancyStrategy::MajorityOwner);
+        assert_eq!(
+            assign_zone_owners(&area, &layout, &windows),
+            vec![Some(1), Some(2), Some(2)]
+        );
+
+        let thirds = ZoneLayout::columns("Thirds", &[1, 1, 1]);
+        assert_eq!(
+            zone_to_rect(&area, &thirds.zones[2]),
+            Rect::new(900, 0, 1300, 600)
+        );
+        assert!(ZoneLayout::priority_grid().zone_index("Center") == Some(1));
+        assert!(ZoneLayout::new("Bad")
+            .with_zone("A", 0.5, 0.0, 0.6, 1.0)
+            .validate()
+            .is_err());
+        assert!(ZoneLayout::new("Bad")
+            .with_zone("A", 0.0, 0.0, 0.5, 1.0)
+            .with_zone("A", 0.5, 0.0, 0.5, 1.0)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_snap_targets() {
+        let area = Rect::new(0, 0, 1200, 900);
+        let config = GridConfig::new(3, 4).with_subgrid(2, 3, GridConfig::new(2, 1));
+
+        // Even a sliver keeps one cell; a window past the edge clamps to the grid
+        assert_eq!(
+            covered_span(&area, &config, &Rect::new(640, 310, 700, 350)),
+            Some(((1, 2), (1, 2)))
+        );
+        assert_eq!(
+            covered_span(&area, &config, &Rect::new(-500, 450, 1700, 1400)),
+            Some(((1, 0), (2, 3)))
+        );
+        assert_eq!(
+            covered_span(&area, &config, &Rect::new(1300, 0, 1500, 100)),
+            None
+        );
+
+        // Points outside the grid go to the nearest cell, split cells to a sub-cell
+        assert_eq!(
+            nearest_cell_path(&area, &config, Point::new(-40, 100)),
+            Some(CellPath::new(0, 0))
+        );
+        assert_eq!(
+            nearest_cell_path(&area, &config, Point::new(1000, 880)),
+            Some(CellPath::new(2, 3).child(1, 0))
+        );
+    }
+
+    #[test]
+    fn test_offscreen_cells() {
+        // A 1920x1080 monitor beside a taller 1080x1920 one, top edges aligned
+        let monitors = [Rect::new(0, 0, 1920, 1080), Rect::new(1920, 0, 3000, 1920)];
+        let area = Rect::new(0, 0, 3000, 1920);
+        let config = GridConfig::new(4, 5);
+        let offscreen = offscreen_cells(&area, &config, &monitors);
+        assert_eq!(
+            offscreen,
+            vec![
+                vec![false; 5],
+                vec![false; 5],
+                vec![false; 5],
+                vec![true, true, true, false, false],
+            ]
+        );
+
+        // On-screen cells stay put; dead space goes to the closest cell with a monitor
+        assert_eq!(
+            nearest_onscreen_cell(&area, &config, &offscreen, 1, 1),
+            Some((1, 1))
+        );
+        assert_eq!(
+            nearest_onscreen_cell(&area, &config, &offscreen, 3, 0),
+            Some((2, 0))
+        );
+        assert_eq!(
+            nearest_onscreen_cell(&area, &config, &offscreen, 3, 2),
+            Some((2, 2))
+        );
+        assert_eq!(
+            nearest_onscreen_cell(&area, &config, &vec![vec![true; 5]; 4], 0, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_nested_subgrids() {
+        let area = Rect::new(0, 0, 1200, 600);
+        // Split the top-right cell into three rows, and the last of those in two
+        let mut config = GridConfig::new(2, 3).with_subgrid(0, 2, GridConfig::new(3, 1));
+        config
+            .subdivide(
+                &CellPath::new(0, 2).child(2, 0),
+                Some(GridConfig::new(1, 2)),
+            )
+            .unwrap();
+        assert!(config
+            .subdivide(
+                &CellPath::new(0, 1).child(0, 0),
+                Some(GridConfig::new(2, 2))
+            )
+            .is_err());
+
+        let path = CellPath::new(0, 2).child(1, 0);
+        assert_eq!(path.to_string(), "(0,2)/(1,0)");
+        assert_eq!(
+            cell_path_to_rect(&area, &config, &path),
+            Some(Rect::new(800, 100, 1200, 200))
+        );
+        let deepest = CellPath::new(0, 2).child(2, 0).child(0, 1);
+        assert_eq!(
+            cell_path_to_rect(&area, &config, &deepest),
+            Some(Rect::new(1000, 200, 1200, 300))
+        );
+        assert_eq!(
+            cell_path_to_rect(&area, &config, &CellPath::new(0, 1).child(0, 0)),
+            None
+        );
+
+        // A window in the middle third of the corner cell shows up at both levels
+        let window = Rect::new(800, 100, 1200, 200);
+        let paths = window_to_cell_paths(&area, &config, &window);
+        assert_eq!(paths, vec![CellPath::new(0, 2), path.clone()]);
+
+        let owners = assign_subgrid_owners(&area, &config, &[(7, window)]);
+        assert_eq!(owners.len(), 3 + 2);
+        assert!(owners.contains(&(path, Some(7))));
+        assert!(owners.contains(&(deepest.clone(), None)));
+
+        config.subdivide(&CellPath::new(0, 2), None).unwrap();
+        assert_eq!(cell_path_to_rect(&area, &config, &deepest), None);
+    }
+}
@@ -1,18 +1,42 @@
 // Grid module - contains different types of grid implementations
 // Each grid type has specialized functionality and visualization
 
+// AnimationGrid and ZOrderGrid drive live windows and print through display::formatters
+#[cfg(all(feature = "animation", feature = "win32", feature = "tui"))]
 pub mod animation;
 pub mod basic;
+pub mod distribution;
+pub mod group;
 pub mod layout;
+pub mod math;
+#[cfg(feature = "win32")]
 pub mod monitor_grid;
+pub mod navigation;
+pub mod rotation;
+pub mod tiling;
 pub mod traits;
+pub mod workspace;
+#[cfg(all(feature = "win32", feature = "tui"))]
 pub mod zorder;
 
 // Re-export the main grid types for easy access
+#[cfg(all(feature = "animation", feature = "win32", feature = "tui"))]
 pub use animation::AnimationGrid;
 pub use basic::BasicGrid;
+pub use distribution::{
+    distribute, optimal_grid, DistributionConstraints, DistributionPlan, Distributor, MonitorPlan,
+};
+pub use group::{GroupMembership, WindowGroup, MAX_GROUP_MEMBERS};
 pub use layout::LayoutGrid;
+pub use navigation::Direction;
+pub use rotation::RotationDirection;
+pub use tiling::{
+    BinarySpacePartition, Columns, LayoutAlgorithm, MasterStack, Monocle, Rows, Spiral,
+    TilingAlgorithm,
+};
 pub use traits::{CellDisplay, GridError, GridResult, GridTrait};
+pub use workspace::{Workspace, WorkspaceHideMode, WorkspaceSummary, WorkspaceWindow};
+#[cfg(all(feature = "win32", feature = "tui"))]
 pub use zorder::ZOrderGrid;
 
 // Re-export common types used by all grids
@@ -1,9 +1,9 @@
 use dashmap::DashMap;
-use winapi::shared::windef::{HWND, RECT};
+use winapi::shared::windef::HWND;
 
 use crate::{
-    grid::{GridConfig, WindowInfo},
-    util::meets_coverage_threshold,
+    geometry::Rect,
+    grid::{math, GridConfig, WindowInfo},
     CellState,
 };
 
@@ -16,11 +16,16 @@ pub struct MonitorGrid {
 }
 
 impl MonitorGrid {
-    pub fn new(monitor_id: usize, monitor_rect: RECT) -> Self {
+    pub fn new(monitor_id: usize, monitor_rect: impl Into<Rect>) -> Self {
         Self::new_with_config(monitor_id, monitor_rect, GridConfig::default())
     }
 
-    pub fn new_with_config(monitor_id: usize, monitor_rect: RECT, config: GridConfig) -> Self {
+    pub fn new_with_config(
+        monitor_id: usize,
+        monitor_rect: impl Into<Rect>,
+        config: GridConfig,
+    ) -> Self {
+        let monitor_rect: Rect = monitor_rect.into();
         let grid = vec![vec![CellState::Empty; config.cols]; config.rows];
         Self {
             monitor_id,
@@ -35,61 +40,19 @@ impl MonitorGrid {
         }
     }
 
-    pub fn window_to_grid_cells(&self, rect: &RECT) -> Vec<(usize, usize)> {
-        let mut cells = Vec::new();
-
+    pub fn window_to_grid_cells(&self, rect: &Rect) -> Vec<(usize, usize)> {
         // Skip windows with invalid coordinates
         if rect.left < -30000
             || rect.top < -30000
             || rect.right < rect.left
             || rect.bottom < rect.top
         {
-            return cells;
+            return Vec::new();
         }
 
         let (left, top, right, bottom) = self.monitor_rect;
-
-        // Check if window intersects with this monitor
-        if rect.right <= left || rect.left >= right || rect.bottom <= top || rect.top >= bottom {
-            return cells; // Window is not on this monitor
-        }
-
-        let cell_width = (right - left) / self.config.cols as i32;
-        let cell_height = (bottom - top) / self.config.rows as i32;
-
-        if cell_width <= 0 || cell_height <= 0 {
-            return cells;
-        }
-
-        // Calculate potential range of cells that might be affected
-        let start_col = ((rect.left.max(left) - left) / cell_width).max(0) as usize;
-        let end_col =
-            ((rect.right.min(right) - left) / cell_width).min(self.config.cols as i32 - 1) as usize;
-        let start_row = ((rect.top.max(top) - top) / cell_height).max(0) as usize;
-        let end_row = ((rect.bottom.min(bottom) - top) / cell_height)
-            .min(self.config.rows as i32 - 1) as usize;
-
-        // Check coverage for each potentially affected cell
-        for row in start_row..=end_row {
-            for col in start_col..=end_col {
-                if row < self.config.rows && col < self.config.cols {
-                    // Calculate the exact bounds of this grid cell
-                    let cell_rect = RECT {
-                        left: left + (col as i32 * cell_width),
-                        top: top + (row as i32 * cell_height),
-                        right: left + ((col + 1) as i32 * cell_width),
-                        bottom: top + ((row + 1) as i32 * cell_height),
-                    };
-
-                    // Only include cell if window meets coverage threshold
-                    if meets_coverage_threshold(rect, &cell_rect) {
-                        cells.push((row, col));
-                    }
-                }
-            }
-        }
-
-        cells
+        let area = Rect::new(left, top, right, bottom);
+        math::window_to_grid_cells(&area, &self.config, rect)
     }
 
     pub fn update_grid(&mut self, windows: &DashMap<HWND, WindowInfo>) {
@@ -0,0 +1,288 @@
+// Directional navigation - neighbours and span moves in grid cells
+//
+// Spans are inclusive cell rectangles on one grid. WindowTracker looks for neighbours
+// on a window's own monitor grid first and then on the virtual grid, so keyboard
+// navigation carries on across monitor edges.
+
+use crate::geometry::{Rect, UsizeRect};
+use std::fmt;
+
+/// A direction on the grid, for keyboard-driven navigation.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Direction {
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Up => "up",
+            Self::Down => "down",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How far `to` lies past `from` in `direction`, and how much the two overlap across
+/// it (negative for a gap), for intervals given as (start, end, cross_start, cross_end)
+/// along and across the direction. None if `to` isn't further along than `from`.
+fn reach(
+    from: (i64, i64, i64, i64),
+    to: (i64, i64, i64, i64),
+    forward: bool,
+) -> Option<(i64, i64)> {
+    let (from_start, from_end, from_cross_start, from_cross_end) = from;
+    let (to_start, to_end, to_cross_start, to_cross_end) = to;
+    let distance = if forward {
+        (to_start > from_start && to_end > from_end).then_some(to_start - from_end)
+    } else {
+        (to_end < from_end && to_start < from_start).then_some(from_start - to_end)
+    }?;
+    let overlap = from_cross_end.min(to_cross_end) - from_cross_start.max(to_cross_start);
+    Some((distance, overlap))
+}
+
+/// The nearest of `candidates` in `direction`, preferring ones that overlap `from`
+/// across the direction, then the fewest units away, then the most overlap.
+fn nearest<T: Copy + Ord>(
+    from: (i64, i64, i64, i64),
+    candidates: impl Iterator<Item = (T, (i64, i64, i64, i64))>,
+    forward: bool,
+) -> Option<T> {
+    candidates
+        .filter_map(|(id, to)| {
+            let (distance, overlap) = reach(from, to, forward)?;
+            Some(((overlap <= 0, distance, -overlap, id), id))
+        })
+        .min_by_key(|(key, _)| *key)
+        .map(|(_, id)| id)
+}
+
+/// Interval of a cell span along and across `direction`, with exclusive ends.
+fn span_axes(span: &UsizeRect, direction: Direction) -> (i64, i64, i64, i64) {
+    let (left, top) = (span.left as i64, span.top as i64);
+    let (right, bottom) = (span.right as i64 + 1, span.bottom as i64 + 1);
+    if direction.is_horizontal() {
+        (left, right, top, bottom)
+    } else {
+        (top, bottom, left, right)
+    }
+}
+
+/// Interval of a pixel rectangle along and across `direction`.
+fn rect_axes(rect: &Rect, direction: Direction) -> (i64, i64, i64, i64) {
+    let (left, top) = (rect.left as i64, rect.top as i64);
+    let (right, bottom) = (rect.right as i64, rect.bottom as i64);
+    if direction.is_horizontal() {
+        (left, right, top, bottom)
+    } else {
+        (top, bottom, left, right)
+    }
+}
+
+fn is_forward(direction: Direction) -> bool {
+    matches!(direction, Direction::Right | Direction::Down)
+}
+
+/// The window in `candidates` next to `from` in `direction`: one further along than
+/// `from` on both edges, preferring windows level with it, then the closest.
+pub fn neighbour(
+    from: &UsizeRect,
+    candidates: &[(u64, UsizeRect)],
+    direction: Direction,
+) -> Option<u64> {
+    nearest(
+        span_axes(from, direction),
+        candidates
+            .iter()
+            .map(|(hwnd, span)| (*hwnd, span_axes(span, direction))),
+        is_forward(direction),
+    )
+}
+
+/// Index of the monitor next to `monitors[from]` in `direction`.
+pub fn adjacent_monitor(monitors: &[Rect], from: usize, direction: Direction) -> Option<usize> {
+    let origin = monitors.get(from)?;
+    nearest(
+        rect_axes(origin, direction),
+        monitors
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != from)
+            .map(|(index, rect)| (index, rect_axes(rect, direction))),
+        is_forward(direction),
+    )
+}
+
+/// `span` moved one cell in `direction` on a `rows` x `cols` grid, or None at the edge.
+pub fn shift_span(
+    span: &UsizeRect,
+    direction: Direction,
+    rows: usize,
+    cols: usize,
+) -> Option<UsizeRect> {
+    let mut shifted = *span;
+    match direction {
+        Direction::Left => {
+            shifted.left = span.left.checked_sub(1)?;
+            shifted.right = span.right - 1;
+        }
+        Direction::Right if span.right + 1 < cols => {
+            shifted.left = span.left + 1;
+            shifted.right = span.right + 1;
+        }
+        Direction::Up => {
+            shifted.top = span.top.checked_sub(1)?;
+            shifted.bottom = span.bottom - 1;
+        }
+        Direction::Down if span.bottom + 1 < rows => {
+            shifted.top = span.top + 1;
+            shifted.bottom = span.bottom + 1;
+        }
+        _ => return None,
+    }
+    Some(shifted)
+}
+
+/// `span` with its edge in `direction` pushed out one cell, or None at the grid edge.
+pub fn grow_span(
+    span: &UsizeRect,
+    direction: Direction,
+    rows: usize,
+    cols: usize,
+) -> Option<UsizeRect> {
+    let mut grown = *span;
+    match direction {
+        Direction::Left => grown.left = span.left.checked_sub(1)?,
+        Direction::Right if span.right + 1 < cols => grown.right = span.right + 1,
+        Direction::Up => grown.top = span.top.checked_sub(1)?,
+        Direction::Down if span.bottom + 1 < rows => grown.bottom = span.bottom + 1,
+        _ => return None,
+    }
+    Some(grown)
+}
+
+/// `span` with its edge in `direction` pulled in one cell, or None if the span is a
+/// single cell thick that way.
+pub fn shrink_span(span: &UsizeRect, direction: Direction) -> Option<UsizeRect> {
+    let mut shrunk = *span;
+    match direction {
+        Direction::Left if span.right > span.left => shrunk.left = span.left + 1,
+        Direction::Right if span.right > span.left => shrunk.right = span.right - 1,
+        Direction::Up if span.bottom > span.top => shrunk.top = span.top + 1,
+        Direction::Down if span.bottom > span.top => shrunk.bottom = span.bottom - 1,
+        _ => return None,
+    }
+    Some(shrunk)
+}
+
+/// `span` carried over the edge of its grid into a `rows` x `cols` grid lying in
+/// `direction`: against the edge it enters by, keeping its size and its rows (or
+/// columns) where the new grid has them.
+pub fn enter_span(span: &UsizeRect, direction: Direction, rows: usize, cols: usize) -> UsizeRect {
+    let width = (span.right - span.left + 1).min(cols.max(1));
+    let height = (span.bottom - span.top + 1).min(rows.max(1));
+    let left = match direction {
+        Direction::Right => 0,
+        Direction::Left => cols.saturating_sub(width),
+        _ => span.left.min(cols.saturating_sub(width)),
+    };
+    let top = match direction {
+        Direction::Down => 0,
+        Direction::Up => rows.saturating_sub(height),
+        _ => span.top.min(rows.saturating_sub(height)),
+    };
+    UsizeRect::from_bounds(left, top, left + width - 1, top + height - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbour_and_span_moves() {
+        // Left half, top right quarter and bottom right quarter of a 4x4 grid
+        let left = UsizeRect::from_bounds(0, 0, 1, 3);
+        let top_right = UsizeRect::from_bounds(2, 0, 3, 1);
+        let bottom_right = UsizeRect::from_bounds(2, 2, 3, 3);
+        let windows = [(1, left), (2, top_right), (3, bottom_right)];
+        assert_eq!(neighbour(&left, &windows, Direction::Right), Some(2));
+        assert_eq!(neighbour(&top_right, &windows, Direction::Down), Some(3));
+        assert_eq!(neighbour(&bottom_right, &windows, Direction::Left), Some(1));
+        assert_eq!(neighbour(&bottom_right, &windows, Direction::Up), Some(2));
+        assert_eq!(neighbour(&left, &windows, Direction::Left), None);
+
+        // Windows level with the origin win over closer ones off to the side
+        let far_level = UsizeRect::from_bounds(3, 2, 3, 2);
+        let near_above = UsizeRect::from_bounds(1, 0, 1, 0);
+        let origin = UsizeRect::from_bounds(0, 2, 0, 2);
+        assert_eq!(
+            neighbour(
+                &origin,
+                &[(8, near_above), (9, far_level)],
+                Direction::Right
+            ),
+            Some(9)
+        );
+
+        assert_eq!(
+            shift_span(&top_right, Direction::Down, 4, 4),
+            Some(UsizeRect::from_bounds(2, 1, 3, 2))
+        );
+        assert_eq!(shift_span(&top_right, Direction::Right, 4, 4), None);
+        assert_eq!(
+            grow_span(&top_right, Direction::Left, 4, 4),
+            Some(UsizeRect::from_bounds(1, 0, 3, 1))
+        );
+        assert_eq!(grow_span(&top_right, Direction::Up, 4, 4), None);
+        assert_eq!(
+            shrink_span(&left, Direction::Down),
+            Some(UsizeRect::from_bounds(0, 0, 1, 2))
+        );
+        assert_eq!(
+            shrink_span(&UsizeRect::from_bounds(2, 2, 2, 2), Direction::Left),
+            None
+        );
+
+        // Crossing into a smaller grid keeps the rows and enters at the near edge
+        assert_eq!(
+            enter_span(&top_right, Direction::Right, 2, 2),
+            UsizeRect::from_bounds(0, 0, 1, 1)
+        );
+        assert_eq!(
+            enter_span(&bottom_right, Direction::Left, 3, 3),
+            UsizeRect::from_bounds(1, 1, 2, 2)
+        );
+
+        let monitors = [
+            Rect::new(0, 0, 1920, 1080),
+            Rect::new(1920, 0, 3840, 1080),
+            Rect::new(0, -1080, 1920, 0),
+        ];
+        assert_eq!(adjacent_monitor(&monitors, 0, Direction::Right), Some(1));
+        assert_eq!(adjacent_monitor(&monitors, 0, Direction::Up), Some(2));
+        assert_eq!(adjacent_monitor(&monitors, 1, Direction::Left), Some(0));
+        assert_eq!(adjacent_monitor(&monitors, 0, Direction::Down), None);
+    }
+}
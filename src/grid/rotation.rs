@@ -0,0 +1,179 @@
+// Rotation - the order windows take each other's places in
+//
+// Within a monitor, windows are ordered clockwise around the centre of its grid area,
+// starting at 12 o'clock; windows at the same angle go outermost first, then by handle.
+// Across monitors, monitors are ordered left to right, then top to bottom, then by id.
+// Rotating clockwise moves every window into the place of the next one in that order.
+
+use crate::geometry::{Point, Rect, UsizeRect};
+use std::cmp::Ordering;
+
+/// Which way windows rotate. Across monitors, clockwise moves every monitor's windows
+/// to the next monitor to the right, wrapping around.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RotationDirection {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Angle of `rect`'s centre around `centre`, clockwise from straight up, in [0, 2π),
+/// and its squared distance from `centre`.
+fn polar(centre: Point, rect: &Rect) -> (f64, i64) {
+    let dx = (rect.left + rect.right) as i64 - 2 * centre.x as i64;
+    let dy = (rect.top + rect.bottom) as i64 - 2 * centre.y as i64;
+    // Screen y grows downwards, so up is -dy
+    let angle = (dx as f64).atan2(-dy as f64);
+    let angle = if angle < 0.0 {
+        angle + std::f64::consts::TAU
+    } else {
+        angle
+    };
+    (angle, dx * dx + dy * dy)
+}
+
+/// `windows` in clockwise order around `centre`. See the module comment for ties.
+pub fn clockwise_order(centre: Point, windows: &[(u64, Rect)]) -> Vec<(u64, Rect)> {
+    let mut ordered = windows.to_vec();
+    ordered.sort_by(|(a, a_rect), (b, b_rect)| {
+        let (a_angle, a_distance) = polar(centre, a_rect);
+        let (b_angle, b_distance) = polar(centre, b_rect);
+        a_angle
+            .total_cmp(&b_angle)
+            .then(b_distance.cmp(&a_distance))
+            .then(a.cmp(b))
+    });
+    ordered
+}
+
+/// Targets for rotating windows already in order: each window takes the rect of the
+/// next one in `direction`. Fewer than two windows don't move.
+pub fn rotate_targets(ordered: &[(u64, Rect)], direction: RotationDirection) -> Vec<(u64, Rect)> {
+    let count = ordered.len();
+    if count < 2 {
+        return Vec::new();
+    }
+    (0..count)
+        .map(|index| {
+            let next = match direction {
+                RotationDirection::Clockwise => (index + 1) % count,
+                RotationDirection::CounterClockwise => (index + count - 1) % count,
+            };
+            (ordered[index].0, ordered[next].1)
+        })
+        .collect()
+}
+
+/// Indices of `monitors` in rotation order: left to right, then top to bottom.
+pub fn monitor_order(monitors: &[Rect]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..monitors.len()).collect();
+    order.sort_by(|a, b| {
+        let (a_rect, b_rect) = (&monitors[*a], &monitors[*b]);
+        match a_rect.left.cmp(&b_rect.left) {
+            Ordering::Equal => a_rect.top.cmp(&b_rect.top).then(a.cmp(b)),
+            other => other,
+        }
+    });
+    order
+}
+
+/// Monitor each monitor's windows move to when rotating in `direction`, indexed by
+/// monitor. A single monitor maps to itself.
+pub fn rotate_monitors(monitors: &[Rect], direction: RotationDirection) -> Vec<usize> {
+    let order = monitor_order(monitors);
+    let count = order.len();
+    let mut targets = vec![0; count];
+    for (position, monitor) in order.iter().enumerate() {
+        let next = match direction {
+            RotationDirection::Clockwise => (position + 1) % count,
+            RotationDirection::CounterClockwise => (position + count - 1) % count,
+        };
+        targets[*monitor] = order[next];
+    }
+    targets
+}
+
+/// `span` on a `from` (rows, cols) grid scaled onto a `to` grid, covering at least one
+/// cell. Grids of the same size keep the span as it is.
+pub fn scale_span(span: &UsizeRect, from: (usize, usize), to: (usize, usize)) -> UsizeRect {
+    let scale_start = |cell: usize, from: usize, to: usize| (cell * to / from.max(1)).min(to - 1);
+    let scale_end = |cell: usize, from: usize, to: usize| {
+        (((cell + 1) * to).div_ceil(from.max(1))).clamp(1, to) - 1
+    };
+    let (top, left) = (
+        scale_start(span.top, from.0, to.0),
+        scale_start(span.left, from.1, to.1),
+    );
+    UsizeRect::from_bounds(
+        left,
+        top,
+        scale_end(span.right, from.1, to.1).max(left),
+        scale_end(span.bottom, from.0, to.0).max(top),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_order() {
+        // Quarters of a 200x200 monitor, plus a window over the whole top half
+        let top_left = (4, Rect::new(0, 0, 100, 100));
+        let top_right = (3, Rect::new(100, 0, 200, 100));
+        let bottom_right = (2, Rect::new(100, 100, 200, 200));
+        let bottom_left = (1, Rect::new(0, 100, 100, 200));
+        let centre = Point::new(100, 100);
+        let ordered = clockwise_order(centre, &[bottom_left, top_left, bottom_right, top_right]);
+        assert_eq!(
+            ordered.iter().map(|(hwnd, _)| *hwnd).collect::<Vec<_>>(),
+            vec![3, 2, 1, 4]
+        );
+
+        // Straight up comes first; further out wins a tie in angle
+        let top = (9, Rect::new(0, 0, 200, 100));
+        let inner_top = (8, Rect::new(50, 50, 150, 100));
+        let ordered = clockwise_order(centre, &[top_right, inner_top, top]);
+        assert_eq!(
+            ordered.iter().map(|(hwnd, _)| *hwnd).collect::<Vec<_>>(),
+            vec![9, 8, 3]
+        );
+
+        let ordered = clockwise_order(centre, &[top_left, top_right, bottom_right]);
+        assert_eq!(
+            rotate_targets(&ordered, RotationDirection::Clockwise),
+            vec![(3, bottom_right.1), (2, top_left.1), (4, top_right.1)]
+        );
+        assert_eq!(
+            rotate_targets(&ordered, RotationDirection::CounterClockwise),
+            vec![(3, top_left.1), (2, top_right.1), (4, bottom_right.1)]
+        );
+        assert!(rotate_targets(&ordered[..1], RotationDirection::Clockwise).is_empty());
+
+        let monitors = [
+            Rect::new(1920, 0, 3840, 1080),
+            Rect::new(0, 0, 1920, 1080),
+            Rect::new(0, 1080, 1920, 2160),
+        ];
+        assert_eq!(monitor_order(&monitors), vec![1, 2, 0]);
+        assert_eq!(
+            rotate_monitors(&monitors, RotationDirection::Clockwise),
+            vec![1, 2, 0]
+        );
+        assert_eq!(
+            rotate_monitors(&monitors, RotationDirection::CounterClockwise),
+            vec![2, 0, 1]
+        );
+
+        let span = UsizeRect::from_bounds(2, 0, 3, 1);
+        assert_eq!(scale_span(&span, (4, 4), (4, 4)), span);
+        assert_eq!(
+            scale_span(&span, (4, 4), (2, 2)),
+            UsizeRect::from_bounds(1, 0, 1, 0)
+        );
+        assert_eq!(
+            scale_span(&span, (4, 4), (3, 6)),
+            UsizeRect::from_bounds(3, 0, 5, 1)
+        );
+    }
+}
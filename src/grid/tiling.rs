@@ -0,0 +1,324 @@
+// Tiling layouts - arrange an ordered list of windows over a monitor without a grid
+//
+// Each algorithm only decides rectangles; WindowTracker::set_monitor_tiling moves the
+// windows there.
+
+use std::sync::Arc;
+
+use crate::geometry::Rect;
+use crate::grid::math::track_offsets;
+
+/// Splits an area among an ordered list of windows, e.g. most recently focused first.
+pub trait LayoutAlgorithm: Send + Sync {
+    /// Short name for logs and menus.
+    fn name(&self) -> &str;
+
+    /// One rectangle per window, in window order, covering `area`.
+    fn rects(&self, area: &Rect, count: usize) -> Vec<Rect>;
+
+    /// Pair each window with its target rectangle.
+    fn arrange(&self, area: &Rect, windows: &[u64]) -> Vec<(u64, Rect)> {
+        windows
+            .iter()
+            .copied()
+            .zip(self.rects(area, windows.len()))
+            .collect()
+    }
+}
+
+/// `count` side-by-side columns of equal width.
+fn split_columns(area: &Rect, count: usize) -> Vec<Rect> {
+    track_offsets(area.left, area.width(), count, &[])
+        .windows(2)
+        .map(|x| Rect::new(x[0], area.top, x[1], area.bottom))
+        .collect()
+}
+
+/// `count` stacked rows of equal height.
+fn split_rows(area: &Rect, count: usize) -> Vec<Rect> {
+    track_offsets(area.top, area.height(), count, &[])
+        .windows(2)
+        .map(|y| Rect::new(area.left, y[0], area.right, y[1]))
+        .collect()
+}
+
+/// Cut `area` across its longer side, giving the first part `first` of `total` shares.
+fn split_longer(area: &Rect, first: usize, total: usize) -> (Rect, Rect) {
+    let total = total.max(1) as i64;
+    if area.width() >= area.height() {
+        let x = area.left + (area.width() as i64 * first as i64 / total) as i32;
+        (
+            Rect::new(area.left, area.top, x, area.bottom),
+            Rect::new(x, area.top, area.right, area.bottom),
+        )
+    } else {
+        let y = area.top + (area.height() as i64 * first as i64 / total) as i32;
+        (
+            Rect::new(area.left, area.top, area.right, y),
+            Rect::new(area.left, y, area.right, area.bottom),
+        )
+    }
+}
+
+/// One or more master windows in a column on the left, the rest stacked on the right.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MasterStack {
+    /// Share of the width given to the master column, 0.0 to 1.0
+    pub master_ratio: f32,
+    pub master_count: usize,
+}
+
+impl Default for MasterStack {
+    fn default() -> Self {
+        Self {
+            master_ratio: 0.55,
+            master_count: 1,
+        }
+    }
+}
+
+impl LayoutAlgorithm for MasterStack {
+    fn name(&self) -> &str {
+        "Master-Stack"
+    }
+
+    fn rects(&self, area: &Rect, count: usize) -> Vec<Rect> {
+        let masters = self.master_count.max(1);
+        if count <= masters {
+            return split_rows(area, count);
+        }
+        let ratio = self.master_ratio.clamp(0.0, 1.0);
+        let x = area.left + (area.width() as f32 * ratio).round() as i32;
+        let mut rects = split_rows(&Rect::new(area.left, area.top, x, area.bottom), masters);
+        rects.extend(split_rows(
+            &Rect::new(x, area.top, area.right, area.bottom),
+            count - masters,
+        ));
+        rects
+    }
+}
+
+/// Side-by-side columns of equal width.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Columns;
+
+impl LayoutAlgorithm for Columns {
+    fn name(&self) -> &str {
+        "Columns"
+    }
+
+    fn rects(&self, area: &Rect, count: usize) -> Vec<Rect> {
+        split_columns(area, count)
+    }
+}
+
+/// Stacked rows of equal height.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rows;
+
+impl LayoutAlgorithm for Rows {
+    fn name(&self) -> &str {
+        "Rows"
+    }
+
+    fn rects(&self, area: &Rect, count: usize) -> Vec<Rect> {
+        split_rows(area, count)
+    }
+}
+
+/// Binary space partition: the windows are halved, and the area cut across its longer
+/// side in proportion, until each window has a part to itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BinarySpacePartition;
+
+impl LayoutAlgorithm for BinarySpacePartition {
+    fn name(&self) -> &str {
+        "BSP"
+    }
+
+    fn rects(&self, area: &Rect, count: usize) -> Vec<Rect> {
+        if count <= 1 {
+            return vec![*area; count];
+        }
+        let half = count / 2;
+        let (first, second) = split_longer(area, half, count);
+        let mut rects = self.rects(&first, half);
+        rects.extend(self.rects(&second, count - half));
+        rects
+    }
+}
+
+/// Fibonacci spiral: each window takes half of what is left, turning clockwise
+/// (left, top, right, bottom) so the later, smaller windows curl towards the centre.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Spiral;
+
+impl LayoutAlgorithm for Spiral {
+    fn name(&self) -> &str {
+        "Spiral"
+    }
+
+    fn rects(&self, area: &Rect, count: usize) -> Vec<Rect> {
+        let mut rects = Vec::with_capacity(count);
+        let mut rest = *area;
+        for i in 0..count {
+            if i + 1 == count {
+                rects.push(rest);
+                break;
+            }
+            let x = rest.left + rest.width() / 2;
+            let y = rest.top + rest.height() / 2;
+            let (taken, left_over) = match i % 4 {
+                0 => (
+                    Rect::new(rest.left, rest.top, x, rest.bottom),
+                    Rect::new(x, rest.top, rest.right, rest.bottom),
+                ),
+                1 => (
+                    Rect::new(rest.left, rest.top, rest.right, y),
+                    Rect::new(rest.left, y, rest.right, rest.bottom),
+                ),
+                2 => (
+                    Rect::new(x, rest.top, rest.right, rest.bottom),
+                    Rect::new(rest.left, rest.top, x, rest.bottom),
+                ),
+                _ => (
+                    Rect::new(rest.left, y, rest.right, rest.bottom),
+                    Rect::new(rest.left, rest.top, rest.right, y),
+                ),
+            };
+            rects.push(taken);
+            rest = left_over;
+        }
+        rects
+    }
+}
+
+/// Every window fills the whole area, stacked on top of each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Monocle;
+
+impl LayoutAlgorithm for Monocle {
+    fn name(&self) -> &str {
+        "Monocle"
+    }
+
+    fn rects(&self, area: &Rect, count: usize) -> Vec<Rect> {
+        vec![*area; count]
+    }
+}
+
+/// The built-in layout algorithms, for choosing one by name or over IPC.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TilingAlgorithm {
+    MasterStack,
+    Columns,
+    Rows,
+    BinarySpacePartition,
+    Spiral,
+    Monocle,
+}
+
+impl TilingAlgorithm {
+    pub const ALL: [TilingAlgorithm; 6] = [
+        TilingAlgorithm::MasterStack,
+        TilingAlgorithm::Columns,
+        TilingAlgorithm::Rows,
+        TilingAlgorithm::BinarySpacePartition,
+        TilingAlgorithm::Spiral,
+        TilingAlgorithm::Monocle,
+    ];
+
+    /// The algorithm with its default settings.
+    pub fn layout(self) -> Arc<dyn LayoutAlgorithm> {
+        match self {
+            TilingAlgorithm::MasterStack => Arc::new(MasterStack::default()),
+            TilingAlgorithm::Columns => Arc::new(Columns),
+            TilingAlgorithm::Rows => Arc::new(Rows),
+            TilingAlgorithm::BinarySpacePartition => Arc::new(BinarySpacePartition),
+            TilingAlgorithm::Spiral => Arc::new(Spiral),
+            TilingAlgorithm::Monocle => Arc::new(Monocle),
+        }
+    }
+
+    /// Look an algorithm up by its `LayoutAlgorithm::name`, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.layout().name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiling_algorithms() {
+        let area = Rect::new(0, 0, 1200, 800);
+
+        assert_eq!(
+            MasterStack::default().rects(&area, 3),
+            vec![
+                Rect::new(0, 0, 660, 800),
+                Rect::new(660, 0, 1200, 400),
+                Rect::new(660, 400, 1200, 800),
+            ]
+        );
+        assert_eq!(MasterStack::default().rects(&area, 1), vec![area]);
+        assert_eq!(
+            Columns.rects(&area, 3),
+            vec![
+                Rect::new(0, 0, 400, 800),
+                Rect::new(400, 0, 800, 800),
+                Rect::new(800, 0, 1200, 800),
+            ]
+        );
+        assert_eq!(
+            Rows.rects(&area, 2),
+            vec![Rect::new(0, 0, 1200, 400), Rect::new(0, 400, 1200, 800)]
+        );
+        assert_eq!(
+            BinarySpacePartition.rects(&area, 3),
+            vec![
+                Rect::new(0, 0, 400, 800),
+                Rect::new(400, 0, 800, 800),
+                Rect::new(800, 0, 1200, 800),
+            ]
+        );
+        assert_eq!(
+            Spiral.rects(&area, 4),
+            vec![
+                Rect::new(0, 0, 600, 800),
+                Rect::new(600, 0, 1200, 400),
+                Rect::new(900, 400, 1200, 800),
+                Rect::new(600, 400, 900, 800),
+            ]
+        );
+        assert_eq!(Monocle.rects(&area, 2), vec![area, area]);
+        assert_eq!(
+            TilingAlgorithm::from_name("bsp"),
+            Some(TilingAlgorithm::BinarySpacePartition)
+        );
+        assert_eq!(TilingAlgorithm::from_name("grid"), None);
+
+        // Every algorithm gives each window exactly one rectangle
+        let algorithms: [&dyn LayoutAlgorithm; 6] = [
+            &MasterStack::default(),
+            &Columns,
+            &Rows,
+            &BinarySpacePartition,
+            &Spiral,
+            &Monocle,
+        ];
+        for algorithm in algorithms {
+            assert!(algorithm.rects(&area, 0).is_empty(), "{}", algorithm.name());
+            assert_eq!(
+                algorithm.arrange(&area, &[7, 8, 9, 10, 11]).len(),
+                5,
+                "{}",
+                algorithm.name()
+            );
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// Workspaces - named sets of windows that take turns on a monitor
+//
+// Switching a monitor to another workspace puts its windows away and brings the
+// workspace's windows back into the cells they were saved in. Cells are relative to
+// the monitor's grid, so a workspace can be shown on any monitor.
+
+/// A window of a workspace and the cells it covers, from `start` to `end` (row, col),
+/// inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WorkspaceWindow {
+    pub hwnd: u64,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+/// A named set of windows with their grid placements.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    pub windows: Vec<WorkspaceWindow>,
+}
+
+impl Workspace {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            windows: Vec::new(),
+        }
+    }
+
+    /// Workspace holding the windows monitor `monitor_id` showed before it was first
+    /// switched.
+    pub fn default_name(monitor_id: usize) -> String {
+        format!("Monitor {}", monitor_id)
+    }
+
+    pub fn contains(&self, hwnd: u64) -> bool {
+        self.windows.iter().any(|window| window.hwnd == hwnd)
+    }
+
+    /// Add a window, replacing its earlier placement if it already belongs here.
+    pub fn insert(&mut self, window: WorkspaceWindow) {
+        match self.windows.iter_mut().find(|w| w.hwnd == window.hwnd) {
+            Some(existing) => *existing = window,
+            None => self.windows.push(window),
+        }
+    }
+
+    /// Remove a window. Returns true if it belonged here.
+    pub fn remove(&mut self, hwnd: u64) -> bool {
+        let before = self.windows.len();
+        self.windows.retain(|window| window.hwnd != hwnd);
+        self.windows.len() != before
+    }
+}
+
+/// How the windows of a workspace leave the screen when it is switched away.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceHideMode {
+    /// Minimize them, keeping their taskbar buttons
+    #[default]
+    Minimize,
+    /// Hide them entirely
+    Hide,
+}
+
+/// A workspace as reported to clients.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkspaceSummary {
+    pub name: String,
+    /// Monitor showing the workspace, if any
+    pub monitor_id: Option<usize>,
+    pub window_count: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_membership() {
+        let mut workspace = Workspace::new("code");
+        let editor = WorkspaceWindow {
+            hwnd: 1,
+            start: (0, 0),
+            end: (1, 1),
+        };
+        workspace.insert(editor);
+        workspace.insert(WorkspaceWindow {
+            start: (0, 2),
+            end: (1, 3),
+            ..editor
+        });
+        assert_eq!(workspace.windows.len(), 1);
+        assert_eq!(workspace.windows[0].start, (0, 2));
+        assert!(workspace.contains(1));
+        assert!(workspace.remove(1));
+        assert!(!workspace.remove(1));
+        assert_eq!(Workspace::default_name(2), "Monitor 2");
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::config::GridConfig;
 use crate::display::format_hwnd_display;
+use crate::geometry::Rect;
 use crate::grid::traits::{
     CellDisplay, GridError, GridResult, GridTrait, ZOrderGrid as ZOrderGridTrait,
 };
@@ -63,7 +64,7 @@ impl ZOrderGrid {
     /// Calculate which cells a window rect occupies
     fn window_to_cells(
         &self,
-        rect: &RECT,
+        rect: &Rect,
         monitor_bounds: (i32, i32, i32, i32),
     ) -> Vec<(usize, usize, f32)> {
         let (monitor_left, monitor_top, monitor_right, monitor_bottom) = monitor_bounds;
@@ -359,7 +360,7 @@ impl GridTrait for ZOrderGrid {
             };
             if unsafe { GetWindowRect(hwnd as HWND, &mut current_rect) } != 0 {
                 let updated_info = WindowInfo {
-                    window_rect: RectWrapper(current_rect),
+                    window_rect: RectWrapper::from_rect(current_rect),
                     ..window_info
                 };
 
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 /// Comprehensive configuration for GridClient
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GridClientConfig {
     pub grid: GridConfig,
     pub display: DisplayConfig,
@@ -12,18 +12,6 @@ pub struct GridClientConfig {
     pub focus_events: FocusEventConfig,
 }
 
-impl Default for GridClientConfig {
-    fn default() -> Self {
-        Self {
-            grid: GridConfig::default(),
-            display: DisplayConfig::default(),
-            performance: PerformanceConfig::default(),
-            ipc: IpcConfig::default(),
-            focus_events: FocusEventConfig::default(),
-        }
-    }
-}
-
 /// Display and output configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DisplayConfig {
@@ -2,6 +2,7 @@ use crate::grid_client_errors::{
     retry_with_backoff, safe_arc_lock, validate_grid_coordinates, GridClientError,
     GridClientResult, RetryConfig,
 };
+use crate::ipc_protocol::{encode_cell_path, MAX_CELL_PATH_DEPTH, PROTOCOL_VERSION};
 pub use crate::ipc_protocol::{
     HeartbeatMessage, IpcCommand, IpcCommandType, IpcResponse, WindowDetails, WindowEvent,
     WindowFocusEvent, GRID_COMMANDS_SERVICE, GRID_EVENTS_SERVICE, GRID_FOCUS_EVENTS_SERVICE,
@@ -54,6 +55,11 @@ pub struct ClientWindowInfo {
     pub monitor_col_start: u32,
     pub monitor_row_end: u32,
     pub monitor_col_end: u32,
+    pub group_id: u32, // 0 if the window isn't grouped
+    pub group_index: u32,
+    pub group_size: u32,
+    /// False for group members hidden behind the active one
+    pub group_active: bool,
     // pub title_len: u32,
 }
 
@@ -74,6 +80,10 @@ impl From<WindowDetails> for ClientWindowInfo {
             monitor_col_start: details.monitor_col_start,
             monitor_row_end: details.monitor_row_end,
             monitor_col_end: details.monitor_col_end,
+            group_id: details.group_id,
+            group_index: details.group_index,
+            group_size: details.group_size,
+            group_active: details.group_active != 0,
             // title_len: details.title_len,
         }
     }
@@ -87,6 +97,18 @@ pub struct GridClient {
     command_publisher: Publisher<Service, IpcCommand, ()>,
     window_list_subscriber: Option<Subscriber<Service, crate::ipc_protocol::WindowListMessage, ()>>,
     monitor_list_subscriber: Option<Subscriber<Service, crate::ipc_protocol::MonitorList, ()>>,
+    window_rules_subscriber:
+        Option<Subscriber<Service, crate::ipc_protocol::WindowRulesMessage, ()>>,
+    window_filter_subscriber:
+        Option<Subscriber<Service, crate::ipc_protocol::WindowFilterDecisionMessage, ()>>,
+    workspace_events_subscriber:
+        Option<Subscriber<Service, crate::ipc_protocol::WorkspaceChangedEvent, ()>>,
+    workspace_list_subscriber:
+        Option<Subscriber<Service, crate::ipc_protocol::WorkspaceListMessage, ()>>,
+    batch_events_subscriber:
+        Option<Subscriber<Service, crate::ipc_protocol::AnimationBatchCompletedEvent, ()>>,
+    window_groups_subscriber:
+        Option<Subscriber<Service, crate::ipc_protocol::WindowGroupMessage, ()>>,
     // Local grid state
     // windows: Arc<Mutex<HashMap<u64, ClientWindowInfo>>>,
 
@@ -96,6 +118,9 @@ pub struct GridClient {
     // Add the missing monitors field
     pub monitors: Arc<DashMap<u32, MonitorGridInfo>>,
 
+    // Zone layouts to render, by monitor id (display only; see set_zone_layout)
+    pub zone_layouts: Arc<DashMap<u32, crate::config::ZoneLayout>>,
+
     // Control flags
     auto_display: Arc<AtomicBool>,
     running: Arc<AtomicBool>,
@@ -123,11 +148,55 @@ pub struct MonitorGridInfo {
     pub height: i32,
     pub x: i32,
     pub y: i32,
+    // Work area: the monitor minus taskbars and docked app bars
+    pub work_x: i32,
+    pub work_y: i32,
+    pub work_width: i32,
+    pub work_height: i32,
+    pub dpi: u32,
     pub rows: usize, // Add rows and cols fields
     pub cols: usize,
     pub grid: Vec<Vec<Option<u64>>>,
 }
 
+impl MonitorGridInfo {
+    /// Rectangle the server lays grids and zones over: the work area when known,
+    /// otherwise the full bounds.
+    pub fn grid_rect(&self) -> crate::geometry::Rect {
+        if self.work_width > 0 && self.work_height > 0 {
+            crate::geometry::Rect::new(
+                self.work_x,
+                self.work_y,
+                self.work_x + self.work_width,
+                self.work_y + self.work_height,
+            )
+        } else {
+            crate::geometry::Rect::new(self.x, self.y, self.x + self.width, self.y + self.height)
+        }
+    }
+
+    /// Physical pixels per logical pixel, e.g. 1.5 at 150% scaling.
+    pub fn scale_factor(&self) -> f64 {
+        crate::geometry::dpi_to_scale(self.dpi)
+    }
+
+    /// Convert a physical rectangle on this monitor to logical pixels.
+    pub fn to_logical(&self, rect: &crate::geometry::Rect) -> crate::geometry::Rect {
+        rect.scale_about(
+            crate::geometry::Point::new(self.x, self.y),
+            1.0 / self.scale_factor(),
+        )
+    }
+
+    /// Convert a logical rectangle on this monitor to physical pixels.
+    pub fn to_physical(&self, rect: &crate::geometry::Rect) -> crate::geometry::Rect {
+        rect.scale_about(
+            crate::geometry::Point::new(self.x, self.y),
+            self.scale_factor(),
+        )
+    }
+}
+
 impl GridClient {
     /// Register a callback to be called when window move events occur
     pub fn set_move_callback<F>(&mut self, callback: F) -> GridClientResult<()>
@@ -249,6 +318,7 @@ impl GridClient {
             ))
         })?; // First, get the grid configuration from the server
         let config = Self::request_grid_config_from_server()?;
+        crate::ipc_protocol::validate_grid_config(&config).map_err(GridClientError::ConfigError)?;
         let window_list_service = node
             .service_builder(&ServiceName::new(crate::ipc_protocol::GRID_WINDOW_LIST_SERVICE).map_err(|e| {
                     GridClientError::IpcError(format!(
@@ -295,6 +365,190 @@ impl GridClient {
                     ))
                 })?);
 
+        let window_rules_service = node
+            .service_builder(
+                &ServiceName::new(crate::ipc_protocol::GRID_WINDOW_RULES_SERVICE).map_err(|e| {
+                    GridClientError::IpcError(format!(
+                        "Failed to create window rules service name: {}",
+                        e
+                    ))
+                })?,
+            )
+            .publish_subscribe::<crate::ipc_protocol::WindowRulesMessage>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()
+            .map_err(|e| {
+                GridClientError::IpcError(format!("Failed to create window rules service: {}", e))
+            })?;
+        let window_rules_subscriber = Some(
+            window_rules_service
+                .subscriber_builder()
+                .create()
+                .map_err(|e| {
+                    GridClientError::IpcError(format!(
+                        "Failed to create window rules subscriber: {:?}",
+                        e
+                    ))
+                })?,
+        );
+
+        let window_filter_service = node
+            .service_builder(
+                &ServiceName::new(crate::ipc_protocol::GRID_WINDOW_FILTER_SERVICE).map_err(
+                    |e| {
+                        GridClientError::IpcError(format!(
+                            "Failed to create window filter service name: {}",
+                            e
+                        ))
+                    },
+                )?,
+            )
+            .publish_subscribe::<crate::ipc_protocol::WindowFilterDecisionMessage>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .open_or_create()
+            .map_err(|e| {
+                GridClientError::IpcError(format!("Failed to create window filter service: {}", e))
+            })?;
+        let window_filter_subscriber = Some(
+            window_filter_service
+                .subscriber_builder()
+                .create()
+                .map_err(|e| {
+                    GridClientError::IpcError(format!(
+                        "Failed to create window filter subscriber: {:?}",
+                        e
+                    ))
+                })?,
+        );
+
+        let workspace_events_service = node
+            .service_builder(
+                &ServiceName::new(crate::ipc_protocol::GRID_WORKSPACE_EVENTS_SERVICE).map_err(
+                    |e| {
+                        GridClientError::IpcError(format!(
+                            "Failed to create workspace events service name: {}",
+                            e
+                        ))
+                    },
+                )?,
+            )
+            .publish_subscribe::<crate::ipc_protocol::WorkspaceChangedEvent>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()
+            .map_err(|e| {
+                GridClientError::IpcError(format!(
+                    "Failed to create workspace events service: {}",
+                    e
+                ))
+            })?;
+        let workspace_events_subscriber = Some(
+            workspace_events_service
+                .subscriber_builder()
+                .create()
+                .map_err(|e| {
+                    GridClientError::IpcError(format!(
+                        "Failed to create workspace events subscriber: {:?}",
+                        e
+                    ))
+                })?,
+        );
+
+        let workspace_list_service = node
+            .service_builder(
+                &ServiceName::new(crate::ipc_protocol::GRID_WORKSPACE_LIST_SERVICE).map_err(
+                    |e| {
+                        GridClientError::IpcError(format!(
+                            "Failed to create workspace list service name: {}",
+                            e
+                        ))
+                    },
+                )?,
+            )
+            .publish_subscribe::<crate::ipc_protocol::WorkspaceListMessage>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()
+            .map_err(|e| {
+                GridClientError::IpcError(format!("Failed to create workspace list service: {}", e))
+            })?;
+        let workspace_list_subscriber = Some(
+            workspace_list_service
+                .subscriber_builder()
+                .create()
+                .map_err(|e| {
+                    GridClientError::IpcError(format!(
+                        "Failed to create workspace list subscriber: {:?}",
+                        e
+                    ))
+                })?,
+        );
+
+        let batch_events_service = node
+            .service_builder(
+                &ServiceName::new(crate::ipc_protocol::GRID_BATCH_EVENTS_SERVICE).map_err(|e| {
+                    GridClientError::IpcError(format!(
+                        "Failed to create batch events service name: {}",
+                        e
+                    ))
+                })?,
+            )
+            .publish_subscribe::<crate::ipc_protocol::AnimationBatchCompletedEvent>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()
+            .map_err(|e| {
+                GridClientError::IpcError(format!("Failed to create batch events service: {}", e))
+            })?;
+        let batch_events_subscriber = Some(
+            batch_events_service
+                .subscriber_builder()
+                .create()
+                .map_err(|e| {
+                    GridClientError::IpcError(format!(
+                        "Failed to create batch events subscriber: {:?}",
+                        e
+                    ))
+                })?,
+        );
+
+        let window_groups_service = node
+            .service_builder(
+                &ServiceName::new(crate::ipc_protocol::GRID_WINDOW_GROUPS_SERVICE).map_err(
+                    |e| {
+                        GridClientError::IpcError(format!(
+                            "Failed to create window groups service name: {}",
+                            e
+                        ))
+                    },
+                )?,
+            )
+            .publish_subscribe::<crate::ipc_protocol::WindowGroupMessage>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()
+            .map_err(|e| {
+                GridClientError::IpcError(format!("Failed to create window groups service: {}", e))
+            })?;
+        let window_groups_subscriber = Some(
+            window_groups_service
+                .subscriber_builder()
+                .create()
+                .map_err(|e| {
+                    GridClientError::IpcError(format!(
+                        "Failed to create window groups subscriber: {:?}",
+                        e
+                    ))
+                })?,
+        );
+
         // Now initialize with the dynamic config
         let grid_size = (config.rows * config.cols) as usize;
         let virtual_grid = (0..grid_size)
@@ -322,6 +576,7 @@ impl GridClient {
             windows: Arc::new(DashMap::new()),
             virtual_grid: Arc::new(virtual_grid),
             monitors: Arc::new(DashMap::new()),
+            zone_layouts: Arc::new(DashMap::new()),
             auto_display: Arc::new(AtomicBool::new(true)),
             running: Arc::new(AtomicBool::new(true)),
             focus_callback: Arc::new(Mutex::new(None)),
@@ -333,6 +588,12 @@ impl GridClient {
             physical_grids: Arc::new(physical_grids),
             window_list_subscriber,
             monitor_list_subscriber,
+            window_rules_subscriber,
+            window_filter_subscriber,
+            workspace_events_subscriber,
+            workspace_list_subscriber,
+            batch_events_subscriber,
+            window_groups_subscriber,
             highlight_topmost: Arc::new(AtomicBool::new(false)),
         };
 
@@ -373,43 +634,12 @@ impl GridClient {
         Ok(())
     }
     /// Initialize offscreen cells based on monitor bounds (similar to server logic)
-    fn initialize_offscreen_cells(&self, monitor_list: &crate::ipc_protocol::MonitorList) {
-        // Get virtual screen bounds (like server does)
-        let virtual_rect = unsafe {
-            winapi::shared::windef::RECT {
-                left: winapi::um::winuser::GetSystemMetrics(winapi::um::winuser::SM_XVIRTUALSCREEN),
-                top: winapi::um::winuser::GetSystemMetrics(winapi::um::winuser::SM_YVIRTUALSCREEN),
-                right: winapi::um::winuser::GetSystemMetrics(
-                    winapi::um::winuser::SM_XVIRTUALSCREEN,
-                ) + winapi::um::winuser::GetSystemMetrics(
-                    winapi::um::winuser::SM_CXVIRTUALSCREEN,
-                ),
-                bottom: winapi::um::winuser::GetSystemMetrics(
-                    winapi::um::winuser::SM_YVIRTUALSCREEN,
-                ) + winapi::um::winuser::GetSystemMetrics(
-                    winapi::um::winuser::SM_CYVIRTUALSCREEN,
-                ),
-            }
-        };
-
-        let cell_width = (virtual_rect.right - virtual_rect.left) / self.config.cols as i32;
-        let cell_height = (virtual_rect.bottom - virtual_rect.top) / self.config.rows as i32;
-
-        // Collect actual monitor bounds from monitor list
-        let mut actual_monitors = Vec::new();
-        for i in 0..monitor_list.monitor_count as usize {
-            let m = &monitor_list.monitors[i];
-            actual_monitors.push(winapi::shared::windef::RECT {
-                left: m.x,
-                top: m.y,
-                right: m.x + m.width,
-                bottom: m.y + m.height,
-            });
-        }
+    fn initialize_offscreen_cells(&self, monitor_list: &[crate::ipc_protocol::MonitorGridIPC]) {
+        let offscreen = Self::offscreen_cells(monitor_list, &self.config);
 
         // Initialize all cells based on whether they're on an actual monitor
-        for row in 0..self.config.rows {
-            for col in 0..self.config.cols {
+        for (row, cells) in offscreen.iter().enumerate() {
+            for (col, is_offscreen) in cells.iter().enumerate() {
                 let idx = row * self.config.cols + col;
                 let current_cell = self.virtual_grid[idx].load();
 
@@ -418,28 +648,10 @@ impl GridClient {
                     continue;
                 }
 
-                let cell_left = virtual_rect.left + (col as i32 * cell_width);
-                let cell_top = virtual_rect.top + (row as i32 * cell_height);
-                let cell_right = cell_left + cell_width;
-                let cell_bottom = cell_top + cell_height;
-
-                // Check if this cell overlaps with any actual monitor
-                let mut is_on_screen = false;
-                for monitor_rect in &actual_monitors {
-                    if cell_right > monitor_rect.left
-                        && cell_left < monitor_rect.right
-                        && cell_bottom > monitor_rect.top
-                        && cell_top < monitor_rect.bottom
-                    {
-                        is_on_screen = true;
-                        break;
-                    }
-                }
-
-                let new_state = if is_on_screen {
-                    ClientCellState::Empty
-                } else {
+                let new_state = if *is_offscreen {
                     ClientCellState::OffScreen
+                } else {
+                    ClientCellState::Empty
                 };
 
                 self.virtual_grid[idx].store(GridCell {
@@ -452,41 +664,11 @@ impl GridClient {
     }
     /// Static version of initialize_offscreen_cells for use in monitoring loop
     fn initialize_offscreen_cells_static(
-        monitor_list: &crate::ipc_protocol::MonitorList,
+        monitor_list: &[crate::ipc_protocol::MonitorGridIPC],
         virtual_grid: &Arc<Vec<AtomicCell<GridCell>>>,
         config: &GridConfig,
     ) {
-        // Get virtual screen bounds (like server does)
-        let virtual_rect = unsafe {
-            winapi::shared::windef::RECT {
-                left: winapi::um::winuser::GetSystemMetrics(winapi::um::winuser::SM_XVIRTUALSCREEN),
-                top: winapi::um::winuser::GetSystemMetrics(winapi::um::winuser::SM_YVIRTUALSCREEN),
-                right: winapi::um::winuser::GetSystemMetrics(
-                    winapi::um::winuser::SM_XVIRTUALSCREEN,
-                ) + winapi::um::winuser::GetSystemMetrics(
-                    winapi::um::winuser::SM_CXVIRTUALSCREEN,
-                ),
-                bottom: winapi::um::winuser::GetSystemMetrics(
-                    winapi::um::winuser::SM_YVIRTUALSCREEN,
-                ) + winapi::um::winuser::GetSystemMetrics(
-                    winapi::um::winuser::SM_CYVIRTUALSCREEN,
-                ),
-            }
-        };
-
-        let cell_width = (virtual_rect.right - virtual_rect.left) / config.cols as i32;
-        let cell_height = (virtual_rect.bottom - virtual_rect.top) / config.rows as i32;
-
-        // Collect actual monitor bounds from monitor list
-        let mut actual_monitors = Vec::new();
-        for i in 0..monitor_list.monitor_count as usize {
-            let m = &monitor_list.monitors[i];
-            actual_monitors.push(winapi::shared::windef::RECT {
-                left: m.x,
-                top: m.y,
-                right: m.x + m.width,
-                bottom: m.y + m.height,
-            });
+        for m in monitor_list {
             println!(
                 "[OFFSCREEN] Monitor {}: ({}, {}) to ({}, {})",
                 m.monitor_id,
@@ -496,16 +678,11 @@ impl GridClient {
                 m.y + m.height
             );
         }
-
-        println!(
-            "[OFFSCREEN] Virtual screen: ({}, {}) to ({}, {})",
-            virtual_rect.left, virtual_rect.top, virtual_rect.right, virtual_rect.bottom
-        );
-        println!("[OFFSCREEN] Cell size: {}x{}", cell_width, cell_height);
+        let offscreen = Self::offscreen_cells(monitor_list, config);
 
         // Initialize all cells based on whether they're on an actual monitor
-        for row in 0..config.rows {
-            for col in 0..config.cols {
+        for (row, cells) in offscreen.iter().enumerate() {
+            for (col, is_offscreen) in cells.iter().enumerate() {
                 let idx = row * config.cols + col;
                 let current_cell = virtual_grid[idx].load();
 
@@ -514,42 +691,20 @@ impl GridClient {
                     continue;
                 }
 
-                let cell_left = virtual_rect.left + (col as i32 * cell_width);
-                let cell_top = virtual_rect.top + (row as i32 * cell_height);
-                let cell_right = cell_left + cell_width;
-                let cell_bottom = cell_top + cell_height;
-
-                // Check if this cell overlaps with any actual monitor
-                let mut is_on_screen = false;
-                for monitor_rect in &actual_monitors {
-                    if cell_right > monitor_rect.left
-                        && cell_left < monitor_rect.right
-                        && cell_bottom > monitor_rect.top
-                        && cell_top < monitor_rect.bottom
-                    {
-                        is_on_screen = true;
-                        break;
-                    }
-                }
-
-                let new_state = if is_on_screen {
-                    ClientCellState::Empty
-                } else {
+                let new_state = if *is_offscreen {
                     ClientCellState::OffScreen
+                } else {
+                    ClientCellState::Empty
                 };
 
-                if matches!(current_cell.state, ClientCellState::Empty)
-                    || matches!(current_cell.state, ClientCellState::OffScreen)
-                {
-                    virtual_grid[idx].store(GridCell {
-                        state: new_state,
-                        monitor_ids: current_cell.monitor_ids,
-                        monitor_count: current_cell.monitor_count,
-                    });
+                virtual_grid[idx].store(GridCell {
+                    state: new_state,
+                    monitor_ids: current_cell.monitor_ids,
+                    monitor_count: current_cell.monitor_count,
+                });
 
-                    if new_state == ClientCellState::OffScreen {
-                        println!("[OFFSCREEN] Cell [{}, {}] marked as offscreen", row, col);
-                    }
+                if new_state == ClientCellState::OffScreen {
+                    println!("[OFFSCREEN] Cell [{}, {}] marked as offscreen", row, col);
                 }
             }
         }
@@ -557,6 +712,44 @@ impl GridClient {
         println!("[OFFSCREEN] Virtual grid offscreen initialization complete");
     }
 
+    /// Virtual grid cells outside every monitor in `monitor_list`, indexed `[row][col]`.
+    /// The grid spans the virtual desktop entry (monitor 999) when the list has one,
+    /// otherwise the system's virtual screen.
+    fn offscreen_cells(
+        monitor_list: &[crate::ipc_protocol::MonitorGridIPC],
+        config: &GridConfig,
+    ) -> Vec<Vec<bool>> {
+        let bounds = |m: &crate::ipc_protocol::MonitorGridIPC| {
+            crate::geometry::Rect::from_xywh(m.x, m.y, m.width, m.height)
+        };
+        let grid_area = match monitor_list.iter().find(|m| m.monitor_id == 999) {
+            Some(virtual_desktop) => bounds(virtual_desktop),
+            None => unsafe {
+                use winapi::um::winuser::{
+                    GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+                    SM_YVIRTUALSCREEN,
+                };
+                let (left, top) = (
+                    GetSystemMetrics(SM_XVIRTUALSCREEN),
+                    GetSystemMetrics(SM_YVIRTUALSCREEN),
+                );
+                crate::geometry::Rect::new(
+                    left,
+                    top,
+                    left + GetSystemMetrics(SM_CXVIRTUALSCREEN),
+                    top + GetSystemMetrics(SM_CYVIRTUALSCREEN),
+                )
+            },
+        };
+        // The virtual desktop entry covers everything, so it can't count as a monitor
+        let actual_monitors: Vec<crate::geometry::Rect> = monitor_list
+            .iter()
+            .filter(|m| m.monitor_id != 999)
+            .map(bounds)
+            .collect();
+        crate::grid::math::offscreen_cells(&grid_area, config, &actual_monitors)
+    }
+
     pub fn start_background_monitoring(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         let windows = self.windows.clone();
         let virtual_grid = self.virtual_grid.clone();
@@ -587,6 +780,7 @@ impl GridClient {
                         response_subscriber,
                         window_list_subscriber,
                         monitor_list_subscriber,
+                        cell_runs_subscriber,
                     )) => {
                         if connection_retry_count > 0 {
                             info!(
@@ -606,6 +800,7 @@ impl GridClient {
                             &response_subscriber,
                             &window_list_subscriber,
                             &monitor_list_subscriber,
+                            &cell_runs_subscriber,
                             &windows,
                             &virtual_grid,
                             &monitors,
@@ -707,6 +902,7 @@ impl GridClient {
             Subscriber<Service, IpcResponse, ()>,
             Subscriber<Service, crate::ipc_protocol::WindowListMessage, ()>,
             Subscriber<Service, crate::ipc_protocol::MonitorList, ()>,
+            Subscriber<Service, crate::ipc_protocol::GridCellRunsMessage, ()>,
         ),
         Box<dyn std::error::Error>,
     > {
@@ -758,6 +954,14 @@ impl GridClient {
             .open()?;
         let monitor_list_subscriber = monitor_list_service.subscriber_builder().create()?;
 
+        let cell_runs_service = node
+            .service_builder(&ServiceName::new(
+                crate::ipc_protocol::GRID_CELL_RUNS_SERVICE,
+            )?)
+            .publish_subscribe::<crate::ipc_protocol::GridCellRunsMessage>()
+            .open()?;
+        let cell_runs_subscriber = cell_runs_service.subscriber_builder().create()?;
+
         Ok((
             event_subscriber,
             window_details_subscriber,
@@ -766,6 +970,7 @@ impl GridClient {
             response_subscriber,
             window_list_subscriber,
             monitor_list_subscriber,
+            cell_runs_subscriber,
         ))
     }
 
@@ -777,6 +982,7 @@ impl GridClient {
         response_subscriber: &Subscriber<Service, IpcResponse, ()>,
         window_list_subscriber: &Subscriber<Service, crate::ipc_protocol::WindowListMessage, ()>,
         monitor_list_subscriber: &Subscriber<Service, crate::ipc_protocol::MonitorList, ()>,
+        cell_runs_subscriber: &Subscriber<Service, crate::ipc_protocol::GridCellRunsMessage, ()>,
         windows: &Arc<DashMap<u64, ClientWindowInfo>>,
         virtual_grid: &Arc<Vec<AtomicCell<GridCell>>>,
         monitors: &Arc<DashMap<u32, MonitorGridInfo>>,
@@ -794,6 +1000,8 @@ impl GridClient {
         let mut consecutive_empty_cycles = 0;
         let max_empty_cycles = 200;
         let mut pending_window_list: Option<crate::ipc_protocol::WindowListMessage> = None;
+        let mut monitor_list_assembler = crate::ipc_protocol::MonitorListAssembler::default();
+        let mut cell_run_assembler = crate::ipc_protocol::CellRunAssembler::default();
         let mut monitor_request_timer = std::time::Instant::now();
         let mut last_debug_time = std::time::Instant::now();
 
@@ -948,17 +1156,20 @@ impl GridClient {
             // Process monitor list messages FIRST - this is CRITICAL
             while let Some(monitor_list_sample) = monitor_list_subscriber.receive().unwrap_or(None)
             {
-                let monitor_list = (*monitor_list_sample).clone();
                 had_activity = true;
 
+                // Long lists arrive in several chunks; wait for the last one
+                let Some(monitor_list) = monitor_list_assembler.push(&monitor_list_sample) else {
+                    continue;
+                };
+
                 println!(
                     "[MONITOR LIST] 🔥 FINALLY RECEIVED monitor list: {} monitors",
-                    monitor_list.monitor_count
+                    monitor_list.len()
                 );
 
                 // Debug the actual monitor data we received
-                for i in 0..monitor_list.monitor_count as usize {
-                    let m = &monitor_list.monitors[i];
+                for (i, m) in monitor_list.iter().enumerate() {
                     println!(
                         "[MONITOR LIST] 🖥️ Monitor {}: ID={}, {}x{} at ({},{})",
                         i, m.monitor_id, m.width, m.height, m.x, m.y
@@ -966,8 +1177,7 @@ impl GridClient {
                 }
 
                 monitors.clear();
-                for i in 0..monitor_list.monitor_count as usize {
-                    let m = &monitor_list.monitors[i];
+                for m in &monitor_list {
                     let rows = config.rows;
                     let cols = config.cols;
                     let grid = vec![vec![None; cols]; rows];
@@ -981,6 +1191,11 @@ impl GridClient {
                             height: m.height,
                             x: m.x,
                             y: m.y,
+                            work_x: m.work_x,
+                            work_y: m.work_y,
+                            work_width: m.work_width,
+                            work_height: m.work_height,
+                            dpi: m.dpi,
                             rows,
                             cols,
                             grid,
@@ -1013,6 +1228,26 @@ impl GridClient {
                 );
             }
 
+            // Full-size grids; the monitor list itself only has room for 32x32 cells
+            while let Some(cell_runs_sample) = cell_runs_subscriber.receive().unwrap_or(None) {
+                had_activity = true;
+                let Some(cells) = cell_run_assembler.push(&cell_runs_sample) else {
+                    continue;
+                };
+                if let Some(mut monitor) = monitors.get_mut(&cell_runs_sample.monitor_id) {
+                    monitor.rows = cells.len();
+                    monitor.cols = cells.first().map_or(0, Vec::len);
+                    monitor.grid = cells
+                        .into_iter()
+                        .map(|row| {
+                            row.into_iter()
+                                .map(|hwnd| (hwnd != 0).then_some(hwnd))
+                                .collect()
+                        })
+                        .collect();
+                }
+            }
+
             // Process window list messages - but ONLY if we have monitors
             while let Some(window_list_sample) = window_list_subscriber.receive().unwrap_or(None) {
                 let window_list = (*window_list_sample).clone();
@@ -1209,7 +1444,7 @@ impl GridClient {
                 let mut monitor_cells_updated = 0;
                 for row in w.monitor_row_start..=w.monitor_row_end {
                     for col in w.monitor_col_start..=w.monitor_col_end {
-                        if row < config.rows as u32 && col < config.cols as u32 {
+                        if row < monitor.rows as u32 && col < monitor.cols as u32 {
                             let current_hwnd = monitor.grid[row as usize][col as usize];
 
                             // Check if this window should replace the current one based on z-order
@@ -1278,11 +1513,21 @@ impl GridClient {
             hwnd: None,
             target_row: None,
             target_col: None,
+            end_row: None,
+            end_col: None,
             monitor_id: None,
             layout_id: None,
             animation_duration_ms: None,
             easing_type: None,
-            protocol_version: 1,
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
         };
         self.send_command(command)
     }
@@ -1300,11 +1545,21 @@ impl GridClient {
             hwnd: None,
             target_row: None,
             target_col: None,
+            end_row: None,
+            end_col: None,
             monitor_id: None,
             layout_id: None,
             animation_duration_ms: None,
             easing_type: None,
-            protocol_version: 1,
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
         };
         self.send_command(command)
     }
@@ -1315,11 +1570,21 @@ impl GridClient {
             hwnd: None,
             target_row: None,
             target_col: None,
+            end_row: None,
+            end_col: None,
             monitor_id: None,
             layout_id: None,
             animation_duration_ms: None,
             easing_type: None,
-            protocol_version: 1,
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
         };
         self.send_command(command)
     }
@@ -1420,11 +1685,21 @@ impl GridClient {
             hwnd: Some(hwnd),
             target_row: Some(row),
             target_col: Some(col),
+            end_row: None,
+            end_col: None,
             monitor_id: None,
             layout_id: None,
             animation_duration_ms: None,
             easing_type: None,
-            protocol_version: 1,
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
         };
         self.send_command(command).map_err(|e| {
             GridClientError::IpcError(format!("Failed to assign window to virtual cell: {}", e))
@@ -1445,17 +1720,783 @@ impl GridClient {
             hwnd: Some(hwnd),
             target_row: Some(row),
             target_col: Some(col),
+            end_row: None,
+            end_col: None,
             monitor_id: Some(monitor_id),
             layout_id: None,
             animation_duration_ms: None,
             easing_type: None,
-            protocol_version: 1,
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
         };
         self.send_command(command).map_err(|e| {
             GridClientError::IpcError(format!("Failed to assign window to monitor cell: {}", e))
         })
     }
 
+    /// Move and resize a window to cover the cells from `start` to `end` (both
+    /// `(row, col)`, inclusive) on the virtual grid, or on `monitor_id`'s grid.
+    /// `animation` is an optional (duration in ms, easing) pair; `None` moves immediately.
+    pub fn move_window_to_span(
+        &mut self,
+        hwnd: u64,
+        monitor_id: Option<u32>,
+        start: (u32, u32),
+        end: (u32, u32),
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        // Monitor grids may differ in size; the server validates those
+        if monitor_id.is_none() {
+            let (rows, cols) = (self.config.rows as u32, self.config.cols as u32);
+            validate_grid_coordinates(start.0, start.1, rows, cols)?;
+            validate_grid_coordinates(end.0, end.1, rows, cols)?;
+        }
+        let command = IpcCommand {
+            command_type: IpcCommandType::MoveWindowToSpan,
+            hwnd: Some(hwnd),
+            target_row: Some(start.0),
+            target_col: Some(start.1),
+            end_row: Some(end.0),
+            end_col: Some(end.1),
+            monitor_id,
+            layout_id: None,
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to move window to span: {}", e)))
+    }
+
+    /// Move and resize a window to fill the zone called `zone` on monitor `monitor_id`.
+    /// The server must have a zone layout with that zone on the monitor.
+    /// `animation` is an optional (duration in ms, easing) pair; `None` moves immediately.
+    pub fn move_window_to_zone(
+        &mut self,
+        hwnd: u64,
+        monitor_id: u32,
+        zone: &str,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let name = IpcCommand::encode_name(zone).map_err(GridClientError::IpcError)?;
+        let command = IpcCommand {
+            command_type: IpcCommandType::MoveWindowToZone,
+            hwnd: Some(hwnd),
+            target_row: None,
+            target_col: None,
+            end_row: None,
+            end_col: None,
+            monitor_id: Some(monitor_id),
+            layout_id: None,
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name,
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to move window to zone: {}", e)))
+    }
+
+    /// Move and resize a window to fill the cell at `path` on monitor `monitor_id`; the
+    /// path may reach into cells the server has split into sub-grids.
+    /// `animation` is an optional (duration in ms, easing) pair; `None` moves immediately.
+    pub fn move_window_to_cell_path(
+        &mut self,
+        hwnd: u64,
+        monitor_id: u32,
+        path: &crate::config::CellPath,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let (cell_path, cell_path_len) =
+            encode_cell_path(path).map_err(GridClientError::IpcError)?;
+        let command = IpcCommand {
+            command_type: IpcCommandType::MoveWindowToCellPath,
+            hwnd: Some(hwnd),
+            target_row: None,
+            target_col: None,
+            end_row: None,
+            end_col: None,
+            monitor_id: Some(monitor_id),
+            layout_id: None,
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path,
+            cell_path_len,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        self.send_command(command).map_err(|e| {
+            GridClientError::IpcError(format!("Failed to move window to cell path: {}", e))
+        })
+    }
+
+    /// Split the cell at `path` on monitor `monitor_id` into a `rows` x `cols` sub-grid,
+    /// or merge it back into one cell with 0 rows or columns.
+    pub fn subdivide_cell(
+        &mut self,
+        monitor_id: u32,
+        path: &crate::config::CellPath,
+        rows: u32,
+        cols: u32,
+    ) -> GridClientResult<()> {
+        let (cell_path, cell_path_len) =
+            encode_cell_path(path).map_err(GridClientError::IpcError)?;
+        let command = IpcCommand {
+            command_type: IpcCommandType::SubdivideCell,
+            hwnd: None,
+            target_row: Some(rows),
+            target_col: Some(cols),
+            end_row: None,
+            end_col: None,
+            monitor_id: Some(monitor_id),
+            layout_id: None,
+            animation_duration_ms: None,
+            easing_type: None,
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path,
+            cell_path_len,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to subdivide cell: {}", e)))
+    }
+
+    /// Render monitor `monitor_id`'s zones by name in the grid display, or stop with
+    /// `None`. This only affects the display; zones the server tracks are set up on its
+    /// `WindowTracker`.
+    pub fn set_zone_layout(&mut self, monitor_id: u32, zones: Option<crate::config::ZoneLayout>) {
+        match zones {
+            Some(zones) => {
+                self.zone_layouts.insert(monitor_id, zones);
+            }
+            None => {
+                self.zone_layouts.remove(&monitor_id);
+            }
+        }
+    }
+
+    /// Print the zones of `monitor`, if it has a zone layout, with the windows in each.
+    fn print_monitor_zones(&self, monitor: &MonitorGridInfo, topmost_hwnd: Option<u64>) {
+        let Some(zones) = self.zone_layouts.get(&monitor.monitor_id) else {
+            return;
+        };
+        let area = monitor.grid_rect();
+        let windows: Vec<(u64, crate::geometry::Rect)> = self
+            .windows
+            .iter()
+            .filter(|entry| entry.value().monitor_id == monitor.monitor_id)
+            .map(|entry| {
+                let w = entry.value();
+                let rect = crate::geometry::Rect::new(w.x, w.y, w.x + w.width, w.y + w.height);
+                (*entry.key(), rect)
+            })
+            .collect();
+        let highlight = self
+            .highlight_topmost
+            .load(std::sync::atomic::Ordering::Relaxed);
+
+        println!("Zones ({}):", zones.name);
+        for zone in &zones.zones {
+            let rect = crate::grid::math::zone_to_rect(&area, zone);
+            let occupants: Vec<String> = windows
+                .iter()
+                .filter(|(_, window)| {
+                    crate::grid::math::occupies_cell(zones.occupancy, window, &rect)
+                })
+                .map(|(hwnd, _)| {
+                    if highlight && topmost_hwnd == Some(*hwnd) {
+                        format!("\x1b[31m{:02X}\x1b[0m", hwnd & 0xFF)
+                    } else {
+                        format!("{:02X}", hwnd & 0xFF)
+                    }
+                })
+                .collect();
+            println!(
+                "  {:<16} {}x{} at ({}, {}): {}",
+                zone.name,
+                rect.width(),
+                rect.height(),
+                rect.left,
+                rect.top,
+                if occupants.is_empty() {
+                    "..".to_string()
+                } else {
+                    occupants.join(" ")
+                }
+            );
+        }
+    }
+
+    /// Change which cells a window occupies, for one monitor grid or (with `None`)
+    /// for the virtual grid and every monitor grid.
+    pub fn set_occupancy_strategy(
+        &mut self,
+        strategy: crate::config::CellOccupancyStrategy,
+        monitor_id: Option<u32>,
+    ) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::SetOccupancyStrategy,
+            hwnd: None,
+            target_row: None,
+            target_col: None,
+            end_row: None,
+            end_col: None,
+            monitor_id,
+            layout_id: None,
+            animation_duration_ms: None,
+            easing_type: None,
+            occupancy_strategy: Some(strategy),
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        self.send_command(command).map_err(|e| {
+            GridClientError::IpcError(format!("Failed to set occupancy strategy: {}", e))
+        })
+    }
+
+    /// Tile monitor `monitor_id` with `algorithm`, or stop tiling it with `None`.
+    /// Windows floating on the monitor are tiled again.
+    /// `animation` is an optional (duration in ms, easing) pair for moving the windows.
+    pub fn set_monitor_tiling(
+        &mut self,
+        monitor_id: u32,
+        algorithm: Option<crate::grid::TilingAlgorithm>,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::SetMonitorTiling,
+            monitor_id: Some(monitor_id),
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            tiling_algorithm: algorithm,
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to set monitor tiling: {}", e)))
+    }
+
+    /// Turn the server's auto-tile mode on, retiling monitors as windows open, close,
+    /// minimize and restore with the given (duration in ms, easing), or off with `None`.
+    pub fn set_auto_tile(&mut self, animation: Option<(u32, EasingType)>) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: match animation {
+                Some(_) => IpcCommandType::EnableAutoTile,
+                None => IpcCommandType::DisableAutoTile,
+            },
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to set auto-tile: {}", e)))
+    }
+
+    /// Ask the server for its window rules, or with `hwnd` only the rule matching that
+    /// window (an empty list if none does), and wait up to `timeout` for the reply.
+    pub fn query_window_rules(
+        &mut self,
+        hwnd: Option<u64>,
+        timeout: std::time::Duration,
+    ) -> GridClientResult<crate::config::WindowRules> {
+        // Drop replies to earlier queries
+        if let Some(subscriber) = self.window_rules_subscriber.as_ref() {
+            while let Ok(Some(_)) = subscriber.receive() {}
+        }
+        let command = IpcCommand {
+            command_type: IpcCommandType::GetWindowRules,
+            hwnd,
+            ..Default::default()
+        };
+        self.send_command(command).map_err(|e| {
+            GridClientError::IpcError(format!("Failed to query window rules: {}", e))
+        })?;
+
+        let subscriber = self
+            .window_rules_subscriber
+            .as_ref()
+            .ok_or_else(|| GridClientError::IpcError("No window rules subscriber".to_string()))?;
+        let mut assembler = crate::ipc_protocol::WindowRulesAssembler::default();
+        let deadline = std::time::Instant::now() + timeout;
+        while std::time::Instant::now() < deadline {
+            while let Some(sample) = subscriber.receive().map_err(|e| {
+                GridClientError::IpcError(format!("Failed to receive window rules: {:?}", e))
+            })? {
+                if let Some(rules) = assembler.push(&sample) {
+                    return rules.map_err(GridClientError::IpcError);
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        Err(GridClientError::IpcError(format!(
+            "No window rules reply within {:?}",
+            timeout
+        )))
+    }
+
+    /// Ask the server why its manageable-window filter accepts or rejects `hwnd`, and
+    /// wait up to `timeout` for the answer.
+    pub fn explain_window(
+        &mut self,
+        hwnd: u64,
+        timeout: std::time::Duration,
+    ) -> GridClientResult<crate::config::FilterDecision> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::ExplainWindow,
+            hwnd: Some(hwnd),
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to explain window: {}", e)))?;
+
+        let subscriber = self
+            .window_filter_subscriber
+            .as_ref()
+            .ok_or_else(|| GridClientError::IpcError("No window filter subscriber".to_string()))?;
+        let deadline = std::time::Instant::now() + timeout;
+        while std::time::Instant::now() < deadline {
+            while let Some(sample) = subscriber.receive().map_err(|e| {
+                GridClientError::IpcError(format!("Failed to receive window decision: {:?}", e))
+            })? {
+                if sample.hwnd == hwnd {
+                    return Ok(sample.decision());
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        Err(GridClientError::IpcError(format!(
+            "No answer about window 0x{:X} within {:?}",
+            hwnd, timeout
+        )))
+    }
+
+    /// Create an empty workspace named `name` (at most 32 bytes).
+    pub fn create_workspace(&mut self, name: &str) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::CreateWorkspace,
+            name: IpcCommand::encode_name(name).map_err(GridClientError::IpcError)?,
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to create workspace: {}", e)))
+    }
+
+    /// Show workspace `name` on monitor `monitor_id`: the monitor's windows are put away
+    /// and the workspace's windows come back to their saved cells.
+    /// `animation` is an optional (duration in ms, easing) pair for restoring the windows.
+    pub fn switch_workspace(
+        &mut self,
+        monitor_id: u32,
+        name: &str,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::SwitchWorkspace,
+            monitor_id: Some(monitor_id),
+            name: IpcCommand::encode_name(name).map_err(GridClientError::IpcError)?,
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to switch workspace: {}", e)))
+    }
+
+    /// Move a window to workspace `name`, keeping its cells; it is put away unless the
+    /// workspace is shown. `animation` is an optional (duration in ms, easing) pair.
+    pub fn move_window_to_workspace(
+        &mut self,
+        hwnd: u64,
+        name: &str,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::MoveWindowToWorkspace,
+            hwnd: Some(hwnd),
+            name: IpcCommand::encode_name(name).map_err(GridClientError::IpcError)?,
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            ..Default::default()
+        };
+        self.send_command(command).map_err(|e| {
+            GridClientError::IpcError(format!("Failed to move window to workspace: {}", e))
+        })
+    }
+
+    /// Hide a window in the scratchpad called `name` (at most 32 bytes); the server
+    /// remembers the window across restarts.
+    pub fn send_to_scratchpad(&mut self, hwnd: u64, name: &str) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::SendToScratchpad,
+            hwnd: Some(hwnd),
+            name: IpcCommand::encode_name(name).map_err(GridClientError::IpcError)?,
+            ..Default::default()
+        };
+        self.send_command(command).map_err(|e| {
+            GridClientError::IpcError(format!("Failed to send window to scratchpad: {}", e))
+        })
+    }
+
+    /// Drop scratchpad `name`'s window into its cells on the focused monitor, or hide it
+    /// again. `animation` is an optional (duration in ms, easing) pair.
+    pub fn toggle_scratchpad(
+        &mut self,
+        name: &str,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::ToggleScratchpad,
+            name: IpcCommand::encode_name(name).map_err(GridClientError::IpcError)?,
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to toggle scratchpad: {}", e)))
+    }
+
+    /// Send one of the directional commands for `hwnd`, or the foreground window when
+    /// `None`.
+    fn send_direction_command(
+        &mut self,
+        command_type: IpcCommandType,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type,
+            hwnd,
+            direction: Some(direction),
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            ..Default::default()
+        };
+        self.send_command(command).map_err(|e| {
+            GridClientError::IpcError(format!("Failed to send {:?}: {}", command_type, e))
+        })
+    }
+
+    /// Focus the window next to `hwnd` (or the foreground window) in `direction`. The
+    /// server looks across monitor edges when nothing lies that way on the same monitor.
+    pub fn focus_in_direction(
+        &mut self,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+    ) -> GridClientResult<()> {
+        self.send_direction_command(IpcCommandType::FocusNeighbour, hwnd, direction, None)
+    }
+
+    /// Move `hwnd` (or the foreground window) one cell in `direction`, onto the next
+    /// monitor at the edge of its grid. `animation` is an optional (duration in ms,
+    /// easing) pair.
+    pub fn move_in_direction(
+        &mut self,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        self.send_direction_command(
+            IpcCommandType::MoveWindowInDirection,
+            hwnd,
+            direction,
+            animation,
+        )
+    }
+
+    /// Swap `hwnd` (or the foreground window) with its neighbour in `direction`.
+    pub fn swap_in_direction(
+        &mut self,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        self.send_direction_command(
+            IpcCommandType::SwapWindowInDirection,
+            hwnd,
+            direction,
+            animation,
+        )
+    }
+
+    /// Push the edge of `hwnd`'s (or the foreground window's) span in `direction` out
+    /// one cell.
+    pub fn grow_span(
+        &mut self,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        self.send_direction_command(IpcCommandType::GrowWindowSpan, hwnd, direction, animation)
+    }
+
+    /// Pull the edge of `hwnd`'s (or the foreground window's) span in `direction` in one
+    /// cell.
+    pub fn shrink_span(
+        &mut self,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        self.send_direction_command(IpcCommandType::ShrinkWindowSpan, hwnd, direction, animation)
+    }
+
+    /// Swap two windows, each animating into the other's place. The server publishes
+    /// an `AnimationBatchCompletedEvent` when both stop; see
+    /// [`Self::poll_batch_completions`]. `animation` is an optional (duration in ms,
+    /// easing) pair.
+    pub fn swap_windows(
+        &mut self,
+        hwnd_a: u64,
+        hwnd_b: u64,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::SwapWindows,
+            hwnd: Some(hwnd_a),
+            other_hwnd: Some(hwnd_b),
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to swap windows: {}", e)))
+    }
+
+    /// Rotate the windows on a monitor (or the focused monitor) one place clockwise or
+    /// counter-clockwise around it, as one animated batch.
+    pub fn rotate_monitor(
+        &mut self,
+        monitor_id: Option<u32>,
+        rotation: crate::grid::RotationDirection,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::RotateMonitorWindows,
+            monitor_id,
+            rotation: Some(rotation),
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to rotate monitor: {}", e)))
+    }
+
+    /// Move every monitor's windows to the next monitor, left to right for clockwise,
+    /// as one animated batch.
+    pub fn rotate_across_monitors(
+        &mut self,
+        rotation: crate::grid::RotationDirection,
+        animation: Option<(u32, EasingType)>,
+    ) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::RotateWindowsAcrossMonitors,
+            rotation: Some(rotation),
+            animation_duration_ms: animation.map(|(ms, _)| ms),
+            easing_type: animation.map(|(_, easing)| easing),
+            ..Default::default()
+        };
+        self.send_command(command).map_err(|e| {
+            GridClientError::IpcError(format!("Failed to rotate across monitors: {}", e))
+        })
+    }
+
+    /// Add `hwnd` to the window group of `target`, grouping `target` first if it is on
+    /// its own. `hwnd` takes the group's place on screen and the other members hide.
+    pub fn add_to_window_group(&mut self, hwnd: u64, target: u64) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::AddToWindowGroup,
+            hwnd: Some(hwnd),
+            other_hwnd: Some(target),
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to add window to group: {}", e)))
+    }
+
+    /// Take `hwnd` out of its window group, leaving it on screen.
+    pub fn remove_from_window_group(&mut self, hwnd: u64) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::RemoveFromWindowGroup,
+            hwnd: Some(hwnd),
+            ..Default::default()
+        };
+        self.send_command(command).map_err(|e| {
+            GridClientError::IpcError(format!("Failed to remove window from group: {}", e))
+        })
+    }
+
+    /// Show the next member of the group of `hwnd` (or the foreground window), or the
+    /// previous one when `forward` is false.
+    pub fn cycle_window_group(&mut self, hwnd: Option<u64>, forward: bool) -> GridClientResult<()> {
+        let direction = if forward {
+            crate::grid::Direction::Right
+        } else {
+            crate::grid::Direction::Left
+        };
+        self.send_direction_command(IpcCommandType::CycleWindowGroup, hwnd, direction, None)
+    }
+
+    /// Show group member `hwnd` in place of the active one, e.g. when its tab is
+    /// clicked.
+    pub fn activate_window_group_member(&mut self, hwnd: u64) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::ActivateWindowGroupMember,
+            hwnd: Some(hwnd),
+            ..Default::default()
+        };
+        self.send_command(command).map_err(|e| {
+            GridClientError::IpcError(format!("Failed to activate group member: {}", e))
+        })
+    }
+
+    /// Ask the server to publish every window group; they arrive through
+    /// [`Self::poll_window_groups`].
+    pub fn list_window_groups(&mut self) -> GridClientResult<()> {
+        let command = IpcCommand {
+            command_type: IpcCommandType::ListWindowGroups,
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to list window groups: {}", e)))
+    }
+
+    /// Ask the server for its workspaces and wait up to `timeout` for the reply.
+    pub fn list_workspaces(
+        &mut self,
+        timeout: std::time::Duration,
+    ) -> GridClientResult<Vec<crate::grid::WorkspaceSummary>> {
+        // Drop replies to earlier queries
+        if let Some(subscriber) = self.workspace_list_subscriber.as_ref() {
+            while let Ok(Some(_)) = subscriber.receive() {}
+        }
+        let command = IpcCommand {
+            command_type: IpcCommandType::ListWorkspaces,
+            ..Default::default()
+        };
+        self.send_command(command)
+            .map_err(|e| GridClientError::IpcError(format!("Failed to list workspaces: {}", e)))?;
+
+        let subscriber = self
+            .workspace_list_subscriber
+            .as_ref()
+            .ok_or_else(|| GridClientError::IpcError("No workspace list subscriber".to_string()))?;
+        let mut sequence = None;
+        let mut workspaces = Vec::new();
+        let deadline = std::time::Instant::now() + timeout;
+        while std::time::Instant::now() < deadline {
+            while let Some(sample) = subscriber.receive().map_err(|e| {
+                GridClientError::IpcError(format!("Failed to receive workspaces: {:?}", e))
+            })? {
+                // Start over if a newer reply begins
+                if sequence != Some(sample.sequence) {
+                    sequence = Some(sample.sequence);
+                    workspaces.clear();
+                }
+                workspaces.extend(sample.summary());
+                if workspaces.len() >= sample.count as usize {
+                    return Ok(workspaces);
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        Err(GridClientError::IpcError(format!(
+            "No workspace list within {:?}",
+            timeout
+        )))
+    }
+
+    /// Workspace switches published since the last call, oldest first, for showing
+    /// the current workspace of each monitor.
+    pub fn poll_workspace_changes(
+        &self,
+    ) -> GridClientResult<Vec<crate::ipc_protocol::WorkspaceChangedEvent>> {
+        let subscriber = self.workspace_events_subscriber.as_ref().ok_or_else(|| {
+            GridClientError::IpcError("No workspace events subscriber".to_string())
+        })?;
+        let mut changes = Vec::new();
+        while let Some(sample) = subscriber.receive().map_err(|e| {
+            GridClientError::IpcError(format!("Failed to receive workspace change: {:?}", e))
+        })? {
+            changes.push(*sample);
+        }
+        Ok(changes)
+    }
+
+    /// Swaps and rotations that finished since the last call, oldest first.
+    pub fn poll_batch_completions(
+        &self,
+    ) -> GridClientResult<Vec<crate::ipc_protocol::AnimationBatchCompletedEvent>> {
+        let subscriber = self
+            .batch_events_subscriber
+            .as_ref()
+            .ok_or_else(|| GridClientError::IpcError("No batch events subscriber".to_string()))?;
+        let mut completions = Vec::new();
+        while let Some(sample) = subscriber.receive().map_err(|e| {
+            GridClientError::IpcError(format!("Failed to receive batch completion: {:?}", e))
+        })? {
+            completions.push(*sample);
+        }
+        Ok(completions)
+    }
+
+    /// Window groups published since the last call, oldest first: each change to a
+    /// group, and every group after [`Self::list_window_groups`]. A dissolved group
+    /// has no members.
+    pub fn poll_window_groups(
+        &self,
+    ) -> GridClientResult<Vec<crate::ipc_protocol::WindowGroupMessage>> {
+        let subscriber = self
+            .window_groups_subscriber
+            .as_ref()
+            .ok_or_else(|| GridClientError::IpcError("No window groups subscriber".to_string()))?;
+        let mut groups = Vec::new();
+        while let Some(sample) = subscriber.receive().map_err(|e| {
+            GridClientError::IpcError(format!("Failed to receive window group: {:?}", e))
+        })? {
+            groups.push(*sample);
+        }
+        Ok(groups)
+    }
+
     /// Print the current virtual grid (all windows, all monitors combined)
     pub fn print_virtual_grid(&self) {
         let has_valid_data = self
@@ -1664,9 +2705,9 @@ impl GridClient {
 
             println!(
                 "Grid size: {} rows x {} cols ({} cells)",
-                self.config.rows,
-                self.config.cols,
-                self.config.rows * self.config.cols
+                monitor.rows,
+                monitor.cols,
+                monitor.rows * monitor.cols
             );
             println!(
                 "Monitor resolution: {}x{} px",
@@ -1675,17 +2716,17 @@ impl GridClient {
 
             // Print column headers
             print!("   ");
-            for col in 0..self.config.cols {
+            for col in 0..monitor.cols {
                 print!("{:2} ", col);
             }
             println!();
 
             // Print grid rows
-            for row in 0..self.config.rows.min(32) {
+            for (row, cells) in monitor.grid.iter().enumerate() {
                 print!("{:2} ", row);
-                for col in 0..self.config.cols.min(32) {
+                for cell in cells {
                     // Check what window (if any) occupies this cell
-                    if let Some(hwnd) = monitor.grid[row][col] {
+                    if let Some(hwnd) = *cell {
                         if hwnd == 0 || hwnd == u64::MAX {
                             print!("XX ");
                         } else {
@@ -1706,6 +2747,7 @@ impl GridClient {
                 }
                 println!();
             }
+            self.print_monitor_zones(monitor.value(), topmost_hwnd);
         }
 
         // Print legend if highlighting is enabled and we found a topmost window
@@ -1818,11 +2860,21 @@ impl GridClient {
             hwnd: Some(hwnd),
             target_row: Some(row),
             target_col: Some(col),
+            end_row: None,
+            end_col: None,
             monitor_id: None,
             layout_id: None,
             animation_duration_ms: Some(duration_ms),
             easing_type: Some(easing),
-            protocol_version: 1,
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
         };
         self.send_command(command)
             .map_err(|e| GridClientError::IpcError(format!("Failed to move window to cell: {}", e)))
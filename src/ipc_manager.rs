@@ -587,12 +587,12 @@ impl GridIpcManager {
                     hwnd, target_x, target_y, target_width, target_height
                 );
 
-                let target_rect = winapi::shared::windef::RECT {
-                    left: target_x,
-                    top: target_y,
-                    right: target_x + target_width as i32,
-                    bottom: target_y + target_height as i32,
-                };
+                let target_rect = crate::geometry::Rect::from_xywh(
+                    target_x,
+                    target_y,
+                    target_width as i32,
+                    target_height as i32,
+                );
 
                 match self.start_window_animation(hwnd, target_rect, duration_ms, easing_type) {
                     Ok(_) => Ok(GridResponse::Success),
@@ -644,6 +644,182 @@ impl GridIpcManager {
                     ))
                 }
             }
+            GridCommand::MoveWindowToSpan {
+                hwnd,
+                monitor_id,
+                start_row,
+                start_col,
+                end_row,
+                end_col,
+                duration_ms,
+                easing_type,
+            } => {
+                debug!(
+                    "📐 Request to move window {} to cells ({}, {})-({}, {}) on monitor {:?}",
+                    hwnd, start_row, start_col, end_row, end_col, monitor_id
+                );
+
+                let start = (start_row, start_col);
+                let end = (end_row, end_col);
+                match self.tracker.lock() {
+                    Ok(mut tracker) => {
+                        let result = if duration_ms > 0 {
+                            tracker.animate_window_to_span(
+                                hwnd,
+                                monitor_id,
+                                start,
+                                end,
+                                Duration::from_millis(duration_ms as u64),
+                                easing_type,
+                            )
+                        } else {
+                            tracker.move_window_to_span(hwnd, monitor_id, start, end)
+                        };
+                        match result {
+                            Ok(_) => Ok(GridResponse::Success),
+                            Err(e) => Ok(GridResponse::Error(format!(
+                                "Failed to move window to span: {}",
+                                e
+                            ))),
+                        }
+                    }
+                    Err(_) => Ok(GridResponse::Error(
+                        "Failed to access window tracker".to_string(),
+                    )),
+                }
+            }
+            GridCommand::MoveWindowToZone {
+                hwnd,
+                monitor_id,
+                zone_name,
+                duration_ms,
+                easing_type,
+            } => {
+                let len = zone_name
+                    .iter()
+                    .position(|&b| b == 0)
+                    .unwrap_or(zone_name.len());
+                let zone = String::from_utf8_lossy(&zone_name[..len]).to_string();
+                debug!(
+                    "🗂️ Request to move window {} to zone '{}' on monitor {}",
+                    hwnd, zone, monitor_id
+                );
+
+                match self.tracker.lock() {
+                    Ok(mut tracker) => {
+                        let result = if duration_ms > 0 {
+                            tracker.animate_window_to_zone(
+                                hwnd,
+                                monitor_id,
+                                &zone,
+                                Duration::from_millis(duration_ms as u64),
+                                easing_type,
+                            )
+                        } else {
+                            tracker.move_window_to_zone(hwnd, monitor_id, &zone)
+                        };
+                        match result {
+                            Ok(_) => Ok(GridResponse::Success),
+                            Err(e) => Ok(GridResponse::Error(format!(
+                                "Failed to move window to zone: {}",
+                                e
+                            ))),
+                        }
+                    }
+                    Err(_) => Ok(GridResponse::Error(
+                        "Failed to access window tracker".to_string(),
+                    )),
+                }
+            }
+            GridCommand::MoveWindowToCellPath {
+                hwnd,
+                monitor_id,
+                cell_path,
+                cell_path_len,
+                duration_ms,
+                easing_type,
+            } => {
+                let path = decode_cell_path(&cell_path, cell_path_len);
+                debug!(
+                    "🔲 Request to move window {} to cell {} on monitor {}",
+                    hwnd, path, monitor_id
+                );
+
+                match self.tracker.lock() {
+                    Ok(mut tracker) => {
+                        let result = if duration_ms > 0 {
+                            tracker.animate_window_to_cell_path(
+                                hwnd,
+                                monitor_id,
+                                &path,
+                                Duration::from_millis(duration_ms as u64),
+                                easing_type,
+                            )
+                        } else {
+                            tracker.move_window_to_cell_path(hwnd, monitor_id, &path)
+                        };
+                        match result {
+                            Ok(_) => Ok(GridResponse::Success),
+                            Err(e) => Ok(GridResponse::Error(format!(
+                                "Failed to move window to cell path: {}",
+                                e
+                            ))),
+                        }
+                    }
+                    Err(_) => Ok(GridResponse::Error(
+                        "Failed to access window tracker".to_string(),
+                    )),
+                }
+            }
+            GridCommand::SubdivideCell {
+                monitor_id,
+                cell_path,
+                cell_path_len,
+                rows,
+                cols,
+            } => {
+                let path = decode_cell_path(&cell_path, cell_path_len);
+                debug!(
+                    "🔲 Request to split cell {} on monitor {} into {}x{}",
+                    path, monitor_id, rows, cols
+                );
+
+                let grid = (rows > 0 && cols > 0).then(|| GridConfig::new(rows, cols));
+                match self.tracker.lock() {
+                    Ok(mut tracker) => match tracker.subdivide_cell(monitor_id, &path, grid) {
+                        Ok(_) => Ok(GridResponse::Success),
+                        Err(e) => Ok(GridResponse::Error(format!(
+                            "Failed to subdivide cell: {}",
+                            e
+                        ))),
+                    },
+                    Err(_) => Ok(GridResponse::Error(
+                        "Failed to access window tracker".to_string(),
+                    )),
+                }
+            }
+            GridCommand::SetOccupancyStrategy {
+                monitor_id,
+                strategy,
+            } => {
+                debug!(
+                    "🧩 Request to set occupancy strategy {:?} for monitor {:?}",
+                    strategy, monitor_id
+                );
+
+                match self.tracker.lock() {
+                    Ok(mut tracker) => match tracker.set_occupancy_strategy(monitor_id, strategy) {
+                        Ok(_) => Ok(GridResponse::Success),
+                        Err(e) => Ok(GridResponse::Error(format!(
+                            "Failed to set occupancy strategy: {}",
+                            e
+                        ))),
+                    },
+                    Err(_) => Ok(GridResponse::Error(
+                        "Failed to access window tracker".to_string(),
+                    )),
+                }
+            }
             GridCommand::GetGridConfig => {
                 // This command should be handled by the server, not here
                 // Return an error indicating this command is not supported in this context
@@ -1526,14 +1702,14 @@ impl GridIpcManager {
                 tracker.monitor_rect.bottom,
             );
 
-            let cell_width = (right - left) / tracker.config.cols as i32;
-            let cell_height = (bottom - top) / tracker.config.rows as i32;
-
-            if target_row < tracker.config.rows && target_col < tracker.config.cols {
-                let cell_left = left + (target_col as i32 * cell_width);
-                let cell_top = top + (target_row as i32 * cell_height);
-                let cell_right = cell_left + cell_width;
-                let cell_bottom = cell_top + cell_height;
+            if let Some(cell) = crate::grid::math::cell_to_rect(
+                &tracker.monitor_rect,
+                &tracker.config,
+                target_row,
+                target_col,
+            ) {
+                let (cell_left, cell_top, cell_right, cell_bottom) =
+                    (cell.left, cell.top, cell.right, cell.bottom);
                 debug!("🔧 Calculated VIRTUAL GRID cell position for ({}, {}): screen coords ({}, {}) to ({}, {})", 
                     target_row, target_col, cell_left, cell_top, cell_right, cell_bottom);
                 debug!(
@@ -1576,22 +1752,22 @@ impl GridIpcManager {
             }
 
             let monitor = &tracker.monitor_grids[monitor_id];
-            let left = monitor.monitor_rect.left;
-            let top = monitor.monitor_rect.top;
-            let right = monitor.monitor_rect.right;
-            let bottom = monitor.monitor_rect.bottom;
-            let cell_width = (right - left) / monitor.config.cols as i32;
-            let cell_height = (bottom - top) / monitor.config.rows as i32;
-
-            if target_row < monitor.config.rows && target_col < monitor.config.cols {
-                let cell_left = left + (target_col as i32 * cell_width);
-                let cell_top = top + (target_row as i32 * cell_height);
-                let cell_right = cell_left + cell_width;
-                let cell_bottom = cell_top + cell_height;
+            let grid_rect = monitor.grid_rect();
+            let (left, top, right, bottom) = (
+                grid_rect.left,
+                grid_rect.top,
+                grid_rect.right,
+                grid_rect.bottom,
+            );
+            if let Some(cell) =
+                crate::grid::math::cell_to_rect(&grid_rect, &monitor.config, target_row, target_col)
+            {
+                let (cell_left, cell_top, cell_right, cell_bottom) =
+                    (cell.left, cell.top, cell.right, cell.bottom);
                 debug!("🔧 Calculated MONITOR {} GRID cell position for ({}, {}): screen coords ({}, {}) to ({}, {})", 
                     monitor_id, target_row, target_col, cell_left, cell_top, cell_right, cell_bottom);
                 debug!(
-                    "   Monitor {} bounds: ({}, {}) to ({}, {})",
+                    "   Monitor {} grid area: ({}, {}) to ({}, {})",
                     monitor_id, left, top, right, bottom
                 );
 
@@ -1655,6 +1831,7 @@ impl GridIpcManager {
                         }
 
                         // Now recalculate grid cells after releasing the mutable reference
+                        let new_rect: crate::geometry::Rect = new_rect.into();
                         let grid_cells = tracker.window_to_grid_cells(&new_rect);
                         let monitor_cells_map = tracker.calculate_monitor_cells(&new_rect);
                         // Update the window with the new grid assignments
@@ -1700,13 +1877,7 @@ impl GridIpcManager {
     // Helper function to convert WindowInfo to WindowDetails for IPC
     fn window_info_to_details(&self, hwnd: HWND, window_info: &crate::WindowInfo) -> WindowDetails {
         // Calculate grid positions for the window
-        // Construct a RECT from the WindowInfo fields
-        let rect = winapi::shared::windef::RECT {
-            left: window_info.window_rect.left,
-            top: window_info.window_rect.top,
-            right: window_info.window_rect.right,
-            bottom: window_info.window_rect.bottom,
-        };
+        let rect = window_info.window_rect.to_rect();
 
         // Get virtual grid positions
         let (virtual_start_row, virtual_start_col, virtual_end_row, virtual_end_col) =
@@ -1759,6 +1930,10 @@ impl GridIpcManager {
             } else {
                 (0, 0, 0, 0, 0)
             };
+        let group = self.tracker.lock().ok().and_then(|tracker| {
+            let hwnd = hwnd as u64;
+            tracker.window_group_of(hwnd)?.membership(hwnd)
+        });
 
         WindowDetails {
             hwnd: hwnd as u64,
@@ -1775,6 +1950,10 @@ impl GridIpcManager {
             monitor_col_start: monitor_start_col,
             monitor_row_end: monitor_end_row,
             monitor_col_end: monitor_end_col,
+            group_id: group.map_or(0, |group| group.group_id),
+            group_index: group.map_or(0, |group| group.index as u32),
+            group_size: group.map_or(0, |group| group.size as u32),
+            group_active: group.map_or(0, |group| group.is_active as u32),
             // title_len: window_info.title.len().min(255) as u32, // Cap at 255 chars
             // title: {
             //     let s = String::from_utf16_lossy(&window_info.title)
@@ -1825,7 +2004,7 @@ impl GridIpcManager {
     pub fn start_window_animation(
         &mut self,
         hwnd: u64,
-        target_rect: winapi::shared::windef::RECT,
+        target_rect: crate::geometry::Rect,
         duration_ms: u32,
         easing_type: crate::EasingType,
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -1971,12 +2150,12 @@ impl GridIpcManager {
             match anim_cmd.command_type {
                 0 => {
                     // start_animation
-                    let target_rect = winapi::shared::windef::RECT {
-                        left: anim_cmd.target_x,
-                        top: anim_cmd.target_y,
-                        right: anim_cmd.target_x + anim_cmd.target_width as i32,
-                        bottom: anim_cmd.target_y + anim_cmd.target_height as i32,
-                    };
+                    let target_rect = crate::geometry::Rect::from_xywh(
+                        anim_cmd.target_x,
+                        anim_cmd.target_y,
+                        anim_cmd.target_width as i32,
+                        anim_cmd.target_height as i32,
+                    );
 
                     let easing_type = match anim_cmd.easing_type {
                         0 => crate::EasingType::Linear,
@@ -7,6 +7,12 @@ use iceoryx2::prelude::ZeroCopySend;
 use crate::EasingType;
 
 pub const MAX_WINDOWS: usize = 20;
+/// Version of the command and response layout; the server rejects commands of any
+/// other version. Bump it whenever `IpcCommand` or `IpcResponse` change shape.
+pub const PROTOCOL_VERSION: u32 = 2;
+// Grid limits live with `GridConfig` so the tracker enforces them whatever features
+// are enabled; the protocol's fixed-size fields are sized from them.
+pub use crate::config::{validate_grid_config, MAX_CELL_PATH_DEPTH, MAX_GRID_COLS, MAX_GRID_ROWS};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(C)]
@@ -21,6 +27,79 @@ pub enum IpcCommandType {
     AssignToVirtualCell,
     AssignToMonitorCell,
     MoveWindowToCell,
+    /// Change the cell-occupancy rule of one monitor grid (`monitor_id`) or of all grids
+    SetOccupancyStrategy,
+    /// Move and resize a window over the cells from (target_row, target_col) to
+    /// (end_row, end_col) on the virtual grid, or on `monitor_id`'s grid
+    MoveWindowToSpan,
+    /// Move and resize a window to fill the zone named `name` on `monitor_id`
+    MoveWindowToZone,
+    /// Move and resize a window to fill the cell at `cell_path` on `monitor_id`
+    MoveWindowToCellPath,
+    /// Split the cell at `cell_path` on `monitor_id` into a `target_row` x `target_col`
+    /// sub-grid; 0 rows or columns merges it back into one cell
+    SubdivideCell,
+    /// Tile `monitor_id` with `tiling_algorithm`, or stop tiling it with `None`
+    SetMonitorTiling,
+    /// Retile monitors whenever windows open, close, minimize or restore, animating
+    /// with `animation_duration_ms` and `easing_type`
+    EnableAutoTile,
+    DisableAutoTile,
+    /// Publish the window rules on `GRID_WINDOW_RULES_SERVICE`; with `hwnd`, only the
+    /// rule matching that window
+    GetWindowRules,
+    /// Publish why the manageable-window filter accepts or rejects `hwnd` on
+    /// `GRID_WINDOW_FILTER_SERVICE`
+    ExplainWindow,
+    /// Create an empty workspace named `name`
+    CreateWorkspace,
+    /// Show workspace `name` on `monitor_id`, animating with
+    /// `animation_duration_ms` and `easing_type`; publishes a `WorkspaceChangedEvent`
+    SwitchWorkspace,
+    /// Move `hwnd` to workspace `name`, putting it away if the workspace is
+    /// not shown
+    MoveWindowToWorkspace,
+    /// Publish every workspace on `GRID_WORKSPACE_LIST_SERVICE`
+    ListWorkspaces,
+    /// Hide `hwnd` in the scratchpad called `name`
+    SendToScratchpad,
+    /// Show scratchpad `name`'s window on the focused monitor, animating with
+    /// `animation_duration_ms` and `easing_type`, or hide it if it is shown
+    ToggleScratchpad,
+    /// Focus the window next to `hwnd` (default: the foreground window) in `direction`,
+    /// crossing onto other monitors when none lies that way on its own
+    FocusNeighbour,
+    /// Move `hwnd` (default: the foreground window) one cell in `direction`, onto the
+    /// next monitor at the edge of its grid
+    MoveWindowInDirection,
+    /// Swap `hwnd` (default: the foreground window) with its neighbour in `direction`
+    SwapWindowInDirection,
+    /// Push the edge of `hwnd`'s (default: the foreground window's) span in `direction`
+    /// out one cell
+    GrowWindowSpan,
+    /// Pull the edge of `hwnd`'s (default: the foreground window's) span in `direction`
+    /// in one cell
+    ShrinkWindowSpan,
+    /// Swap `hwnd` and `other_hwnd`, each animating into the other's rect
+    SwapWindows,
+    /// Rotate the windows on `monitor_id` (default: the focused monitor) one place
+    /// `rotation` around the monitor
+    RotateMonitorWindows,
+    /// Move every monitor's windows to the next monitor in `rotation`, keeping their
+    /// cells
+    RotateWindowsAcrossMonitors,
+    /// Add `hwnd` to the window group of `other_hwnd`, grouping `other_hwnd` first if
+    /// it is on its own; `hwnd` takes the group's place and the other members hide
+    AddToWindowGroup,
+    /// Take `hwnd` out of its window group, leaving it on screen
+    RemoveFromWindowGroup,
+    /// Show the next member of `hwnd`'s (default: the foreground window's) group, or the
+    /// previous one when `direction` is `Left` or `Up`
+    CycleWindowGroup,
+    /// Show group member `hwnd` in place of the active one
+    ActivateWindowGroupMember,
+    /// Publish every window group on `GRID_WINDOW_GROUPS_SERVICE`
+    ListWindowGroups,
     // Add any other variants needed by client/server
 }
 
@@ -31,10 +110,20 @@ pub struct IpcCommand {
     pub hwnd: Option<u64>,
     pub target_row: Option<u32>,
     pub target_col: Option<u32>,
+    pub end_row: Option<u32>, // Opposite corner for MoveWindowToSpan
+    pub end_col: Option<u32>,
     pub monitor_id: Option<u32>,
     pub layout_id: Option<u32>,
     pub animation_duration_ms: Option<u32>,
     pub easing_type: Option<EasingType>,
+    pub occupancy_strategy: Option<crate::config::CellOccupancyStrategy>,
+    pub tiling_algorithm: Option<crate::grid::TilingAlgorithm>,
+    pub direction: Option<crate::grid::Direction>,
+    pub other_hwnd: Option<u64>, // Second window for SwapWindows
+    pub rotation: Option<crate::grid::RotationDirection>,
+    pub name: [u8; 32], // Zero-padded UTF-8 zone, workspace or scratchpad name
+    pub cell_path: [[u32; 2]; MAX_CELL_PATH_DEPTH], // (row, col) per level, outermost first
+    pub cell_path_len: u32,
     pub protocol_version: u32,
 }
 
@@ -45,14 +134,95 @@ impl Default for IpcCommand {
             hwnd: None,
             target_row: None,
             target_col: None,
+            end_row: None,
+            end_col: None,
             monitor_id: None,
             layout_id: None,
             animation_duration_ms: None,
             easing_type: None,
-            protocol_version: 1,
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
         }
     }
 }
+
+impl IpcCommand {
+    /// Encode a zone, workspace or scratchpad name for `name`; fails if it is longer
+    /// than 32 bytes.
+    pub fn encode_name(name: &str) -> Result<[u8; 32], String> {
+        if name.len() > 32 {
+            return Err(format!("Name '{}' is longer than 32 bytes", name));
+        }
+        Ok(truncate_name(name))
+    }
+
+    /// Decoded `name`.
+    pub fn name(&self) -> String {
+        decode_name(&self.name)
+    }
+
+    /// Decoded `cell_path`.
+    pub fn cell_path(&self) -> crate::config::CellPath {
+        decode_cell_path(&self.cell_path, self.cell_path_len)
+    }
+}
+
+/// Zero-padded UTF-8 for a 32-byte name field, cutting longer names at a character
+/// boundary.
+fn truncate_name(name: &str) -> [u8; 32] {
+    let mut len = name.len().min(32);
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+    let mut encoded = [0u8; 32];
+    encoded[..len].copy_from_slice(&name.as_bytes()[..len]);
+    encoded
+}
+
+/// Name from a zero-padded 32-byte field.
+pub fn decode_name(name: &[u8; 32]) -> String {
+    let len = name.iter().position(|&b| b == 0).unwrap_or(32);
+    String::from_utf8_lossy(&name[..len]).into_owned()
+}
+
+/// Encode a cell path for `IpcCommand::cell_path` / `GridCommand`; fails if it is
+/// deeper than `MAX_CELL_PATH_DEPTH`.
+pub fn encode_cell_path(
+    path: &crate::config::CellPath,
+) -> Result<([[u32; 2]; MAX_CELL_PATH_DEPTH], u32), String> {
+    if path.depth() > MAX_CELL_PATH_DEPTH {
+        return Err(format!(
+            "Cell path {} is deeper than {} levels",
+            path, MAX_CELL_PATH_DEPTH
+        ));
+    }
+    let mut cells = [[0; 2]; MAX_CELL_PATH_DEPTH];
+    for (cell, (row, col)) in cells.iter_mut().zip(path.cells()) {
+        *cell = [*row as u32, *col as u32];
+    }
+    Ok((cells, path.depth() as u32))
+}
+
+/// Cell path from its encoded cells and length.
+pub fn decode_cell_path(
+    cells: &[[u32; 2]; MAX_CELL_PATH_DEPTH],
+    len: u32,
+) -> crate::config::CellPath {
+    crate::config::CellPath(
+        cells
+            .iter()
+            .take((len as usize).min(MAX_CELL_PATH_DEPTH))
+            .map(|[row, col]| (*row as usize, *col as usize))
+            .collect(),
+    )
+}
 unsafe impl ZeroCopySend for IpcCommand {}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ZeroCopySend)]
@@ -96,6 +266,13 @@ pub struct Monitor {
     pub height: i32,
     pub x: i32,
     pub y: i32,
+    // Work area: the monitor minus taskbars and docked app bars
+    pub work_x: i32,
+    pub work_y: i32,
+    pub work_width: i32,
+    pub work_height: i32,
+    /// Effective DPI; 96 is 100% scaling
+    pub dpi: u32,
     pub rows: u32,
     pub cols: u32,
 }
@@ -132,7 +309,28 @@ impl Default for IpcResponse {
             has_error_message: 0,
             error_message_len: 0,
             error_message: [0; 256],
-            protocol_version: 1,
+            protocol_version: PROTOCOL_VERSION,
+        }
+    }
+}
+
+impl IpcResponse {
+    /// Acknowledge a command that succeeded.
+    pub fn ack() -> Self {
+        Self::default()
+    }
+
+    /// Reject a command, carrying `message` (truncated to 256 bytes) back to the client.
+    pub fn error(message: &str) -> Self {
+        let mut error_message = [0u8; 256];
+        let len = message.len().min(256);
+        error_message[..len].copy_from_slice(&message.as_bytes()[..len]);
+        Self {
+            response_type: IpcResponseType::Error,
+            has_error_message: 1,
+            error_message_len: len as u32,
+            error_message,
+            ..Self::default()
         }
     }
 }
@@ -170,12 +368,16 @@ impl core::fmt::Debug for MonitorList {
 pub const GRIDSTATE_MAX_ROWS: usize = 32;
 pub const GRIDSTATE_MAX_COLS: usize = 32;
 
+/// Grid snapshot limited to the top-left 32x32 cells. Larger grids are sent in full as
+/// `GridCellRunsMessage`s.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(C)]
 pub struct GridState {
     pub rows: u32,
     pub cols: u32,
     pub grid: [[u64; GRIDSTATE_MAX_COLS]; GRIDSTATE_MAX_ROWS], // 0 means empty cell
+    /// Window group of the window in each cell, 0 if it isn't grouped
+    pub groups: [[u32; GRIDSTATE_MAX_COLS]; GRIDSTATE_MAX_ROWS],
 }
 
 impl Default for GridState {
@@ -184,6 +386,7 @@ impl Default for GridState {
             rows: 0,
             cols: 0,
             grid: [[0; GRIDSTATE_MAX_COLS]; GRIDSTATE_MAX_ROWS],
+            groups: [[0; GRIDSTATE_MAX_COLS]; GRIDSTATE_MAX_ROWS],
         }
     }
 }
@@ -212,11 +415,20 @@ pub struct MonitorGridIPC {
     pub height: i32,
     pub x: i32,
     pub y: i32,
+    // Work area: the monitor minus taskbars and docked app bars
+    pub work_x: i32,
+    pub work_y: i32,
+    pub work_width: i32,
+    pub work_height: i32,
+    /// Effective DPI; 96 is 100% scaling
+    pub dpi: u32,
     pub rows: u32,
     pub cols: u32,
     pub name_len: u32,
-    pub name: [u8; 64],                    // Fixed-size array for name
-    pub grid: [[u64; MAX_COLS]; MAX_ROWS], // 0 means empty cell
+    pub name: [u8; 64], // Fixed-size array for name
+    /// Top-left 32x32 cells only (0 means empty cell); the full grid is sent as
+    /// `GridCellRunsMessage`s
+    pub grid: [[u64; MAX_COLS]; MAX_ROWS],
 }
 
 impl Default for MonitorGridIPC {
@@ -228,6 +440,11 @@ impl Default for MonitorGridIPC {
             height: 0,
             x: 0,
             y: 0,
+            work_x: 0,
+            work_y: 0,
+            work_width: 0,
+            work_height: 0,
+            dpi: crate::geometry::DEFAULT_DPI,
             rows: 0,
             cols: 0,
             name_len: 0,
@@ -237,33 +454,604 @@ impl Default for MonitorGridIPC {
     }
 }
 
-const MAX_MONITORS: usize = 16;
+impl MonitorGridIPC {
+    /// Physical pixels per logical pixel, e.g. 1.5 at 150% scaling.
+    pub fn scale_factor(&self) -> f64 {
+        crate::geometry::dpi_to_scale(self.dpi)
+    }
+
+    /// Convert a physical rectangle on this monitor to logical pixels.
+    pub fn to_logical(&self, rect: &crate::geometry::Rect) -> crate::geometry::Rect {
+        rect.scale_about(
+            crate::geometry::Point::new(self.x, self.y),
+            1.0 / self.scale_factor(),
+        )
+    }
+
+    /// Copy as much of `grid` as fits into the fixed-size `grid` field.
+    pub fn set_grid(&mut self, grid: &[Vec<u64>]) {
+        self.grid = [[0; MAX_COLS]; MAX_ROWS];
+        for (row, cells) in self.grid.iter_mut().zip(grid) {
+            for (cell, hwnd) in row.iter_mut().zip(cells) {
+                *cell = *hwnd;
+            }
+        }
+    }
 
+    /// Convert a logical rectangle on this monitor to physical pixels.
+    pub fn to_physical(&self, rect: &crate::geometry::Rect) -> crate::geometry::Rect {
+        rect.scale_about(
+            crate::geometry::Point::new(self.x, self.y),
+            self.scale_factor(),
+        )
+    }
+}
+
+/// Monitors per `MonitorList` message; longer lists are split across several messages
+pub const MAX_MONITORS: usize = 16;
+
+/// One chunk of the monitor list: `monitors[..monitor_count]` are entries
+/// `first_index..first_index + monitor_count` of a list `total_count` long. Chunks of
+/// one publish share a `sequence`.
 #[derive(Clone, Copy, ZeroCopySend)]
 #[repr(C)]
 pub struct MonitorList {
     pub monitor_count: u32,
     pub monitors: [MonitorGridIPC; MAX_MONITORS], // 0..N = physical, N+1 = virtual, N+2+ = dynamic
+    pub first_index: u32,
+    pub total_count: u32,
+    pub sequence: u64,
 }
 
 impl Default for MonitorList {
     fn default() -> Self {
         Self {
             monitor_count: 0,
-            monitors: [MonitorGridIPC {
-                monitor_id: 0,
-                grid_type: GridType::Physical,
-                width: 0,
-                height: 0,
-                x: 0,
-                y: 0,
-                rows: 0,
-                cols: 0,
-                name_len: 0,
-                name: [0; 64],
-                grid: [[0; MAX_COLS]; MAX_ROWS],
-            }; MAX_MONITORS],
+            monitors: [MonitorGridIPC::default(); MAX_MONITORS],
+            first_index: 0,
+            total_count: 0,
+            sequence: 0,
+        }
+    }
+}
+
+impl MonitorList {
+    /// Split `monitors` into as many messages of publish `sequence` as needed; always
+    /// at least one, so an empty list is still announced.
+    pub fn chunks(monitors: &[MonitorGridIPC], sequence: u64) -> Vec<MonitorList> {
+        let mut chunks: Vec<MonitorList> = monitors
+            .chunks(MAX_MONITORS)
+            .enumerate()
+            .map(|(i, entries)| {
+                let mut list = MonitorList {
+                    monitor_count: entries.len() as u32,
+                    first_index: (i * MAX_MONITORS) as u32,
+                    total_count: monitors.len() as u32,
+                    sequence,
+                    ..Default::default()
+                };
+                list.monitors[..entries.len()].copy_from_slice(entries);
+                list
+            })
+            .collect();
+        if chunks.is_empty() {
+            chunks.push(MonitorList {
+                sequence,
+                ..Default::default()
+            });
+        }
+        chunks
+    }
+
+    /// The monitors carried by this message.
+    pub fn entries(&self) -> &[MonitorGridIPC] {
+        &self.monitors[..(self.monitor_count as usize).min(MAX_MONITORS)]
+    }
+}
+
+/// Rebuilds the full monitor list from the `MonitorList` chunks of one publish.
+#[derive(Debug, Default)]
+pub struct MonitorListAssembler {
+    monitors: Vec<MonitorGridIPC>,
+    total_count: usize,
+    /// Newest publish seen
+    sequence: u64,
+}
+
+impl MonitorListAssembler {
+    /// Add one chunk; returns the whole list once its last chunk arrives. A chunk out
+    /// of order discards the partial list until the next publish starts, and chunks of
+    /// a publish older than the newest one seen are ignored.
+    pub fn push(&mut self, chunk: &MonitorList) -> Option<Vec<MonitorGridIPC>> {
+        if chunk.sequence < self.sequence {
+            return None;
+        }
+        if chunk.sequence > self.sequence {
+            self.sequence = chunk.sequence;
+            self.monitors.clear();
+        }
+        if chunk.first_index == 0 {
+            self.monitors.clear();
+            self.total_count = chunk.total_count as usize;
+        } else if chunk.first_index as usize != self.monitors.len() || self.monitors.is_empty() {
+            self.monitors.clear();
+            return None;
+        }
+        self.monitors.extend_from_slice(chunk.entries());
+        if self.monitors.len() >= self.total_count {
+            self.total_count = 0;
+            return Some(std::mem::take(&mut self.monitors));
+        }
+        None
+    }
+}
+
+/// Cell runs per `GridCellRunsMessage`
+pub const MAX_CELL_RUNS: usize = 64;
+
+/// `len` consecutive cells of one row, starting at (row, col), that all hold `hwnd`
+/// (`u64::MAX` for off-screen cells).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ZeroCopySend)]
+#[repr(C)]
+pub struct CellRun {
+    pub row: u16,
+    pub col: u16,
+    pub len: u16,
+    pub hwnd: u64,
+}
+
+/// One chunk of a grid snapshot of any size, sent as runs of non-empty cells. Cells
+/// no run covers are empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ZeroCopySend)]
+#[repr(C)]
+pub struct GridCellRunsMessage {
+    pub monitor_id: u32, // 999 = virtual desktop grid
+    pub rows: u32,
+    pub cols: u32,
+    /// Snapshot the chunk belongs to; chunks of different snapshots are never mixed
+    pub sequence: u64,
+    pub chunk_index: u32,
+    pub chunk_count: u32,
+    pub run_count: u32,
+    pub runs: [CellRun; MAX_CELL_RUNS],
+}
+
+impl Default for GridCellRunsMessage {
+    fn default() -> Self {
+        Self {
+            monitor_id: 0,
+            rows: 0,
+            cols: 0,
+            sequence: 0,
+            chunk_index: 0,
+            chunk_count: 1,
+            run_count: 0,
+            runs: [CellRun::default(); MAX_CELL_RUNS],
+        }
+    }
+}
+
+impl GridCellRunsMessage {
+    /// Split `grid` (rows of window handles, 0 = empty) into as many messages as
+    /// needed; always at least one, so an empty grid still reports its size.
+    pub fn encode(monitor_id: u32, sequence: u64, grid: &[Vec<u64>]) -> Vec<Self> {
+        let rows = grid.len().min(MAX_GRID_ROWS);
+        let cols = grid.first().map_or(0, |row| row.len()).min(MAX_GRID_COLS);
+        let mut runs = Vec::new();
+        for (row, cells) in grid.iter().take(rows).enumerate() {
+            let mut col = 0;
+            while col < cols.min(cells.len()) {
+                let hwnd = cells[col];
+                let start = col;
+                while col < cols.min(cells.len()) && cells[col] == hwnd {
+                    col += 1;
+                }
+                if hwnd != 0 {
+                    runs.push(CellRun {
+                        row: row as u16,
+                        col: start as u16,
+                        len: (col - start) as u16,
+                        hwnd,
+                    });
+                }
+            }
+        }
+
+        let chunk_count = runs.len().div_ceil(MAX_CELL_RUNS).max(1);
+        (0..chunk_count)
+            .map(|i| {
+                let chunk = runs
+                    .get(i * MAX_CELL_RUNS..((i + 1) * MAX_CELL_RUNS).min(runs.len()))
+                    .unwrap_or(&[]);
+                let mut message = Self {
+                    monitor_id,
+                    rows: rows as u32,
+                    cols: cols as u32,
+                    sequence,
+                    chunk_index: i as u32,
+                    chunk_count: chunk_count as u32,
+                    run_count: chunk.len() as u32,
+                    ..Default::default()
+                };
+                message.runs[..chunk.len()].copy_from_slice(chunk);
+                message
+            })
+            .collect()
+    }
+
+    /// The runs carried by this message.
+    pub fn cell_runs(&self) -> &[CellRun] {
+        &self.runs[..(self.run_count as usize).min(MAX_CELL_RUNS)]
+    }
+}
+
+/// Rebuilds grids from `GridCellRunsMessage` chunks, tracking one snapshot per monitor.
+#[derive(Debug, Default)]
+pub struct CellRunAssembler {
+    pending: std::collections::HashMap<u32, PendingCellRuns>,
+    /// Newest snapshot seen per monitor
+    latest: std::collections::HashMap<u32, u64>,
+}
+
+#[derive(Debug)]
+struct PendingCellRuns {
+    sequence: u64,
+    received: Vec<bool>,
+    grid: Vec<Vec<u64>>,
+}
+
+impl CellRunAssembler {
+    /// Add one chunk; returns the monitor's complete grid once the last chunk of its
+    /// snapshot arrives. A chunk from a newer snapshot drops the older, partial one,
+    /// and chunks of snapshots older than the newest one seen are ignored.
+    pub fn push(&mut self, message: &GridCellRunsMessage) -> Option<Vec<Vec<u64>>> {
+        let chunk_count = message.chunk_count.max(1) as usize;
+        let chunk_index = message.chunk_index as usize;
+        if chunk_index >= chunk_count {
+            return None;
+        }
+        let latest = self.latest.entry(message.monitor_id).or_default();
+        if message.sequence < *latest {
+            return None;
+        }
+        *latest = message.sequence;
+        let rows = (message.rows as usize).min(MAX_GRID_ROWS);
+        let cols = (message.cols as usize).min(MAX_GRID_COLS);
+
+        let pending = self
+            .pending
+            .entry(message.monitor_id)
+            .or_insert_with(|| PendingCellRuns {
+                sequence: message.sequence,
+                received: Vec::new(),
+                grid: Vec::new(),
+            });
+        if pending.sequence != message.sequence || pending.received.len() != chunk_count {
+            *pending = PendingCellRuns {
+                sequence: message.sequence,
+                received: vec![false; chunk_count],
+                grid: vec![vec![0; cols]; rows],
+            };
+        }
+
+        pending.received[chunk_index] = true;
+        for run in message.cell_runs() {
+            if let Some(cells) = pending.grid.get_mut(run.row as usize) {
+                let start = (run.col as usize).min(cells.len());
+                let end = (start + run.len as usize).min(cells.len());
+                cells[start..end].fill(run.hwnd);
+            }
+        }
+
+        if pending.received.iter().all(|received| *received) {
+            self.pending
+                .remove(&message.monitor_id)
+                .map(|pending| pending.grid)
+        } else {
+            None
+        }
+    }
+}
+
+/// Bytes of JSON per `WindowRulesMessage`
+pub const MAX_WINDOW_RULES_CHUNK: usize = 1024;
+
+/// One chunk of a `WindowRules` reply, serialized as JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ZeroCopySend)]
+#[repr(C)]
+pub struct WindowRulesMessage {
+    /// Reply the chunk belongs to; chunks of different replies are never mixed
+    pub sequence: u64,
+    pub chunk_index: u32,
+    pub chunk_count: u32,
+    pub len: u32,
+    pub data: [u8; MAX_WINDOW_RULES_CHUNK],
+}
+
+impl Default for WindowRulesMessage {
+    fn default() -> Self {
+        Self {
+            sequence: 0,
+            chunk_index: 0,
+            chunk_count: 1,
+            len: 0,
+            data: [0; MAX_WINDOW_RULES_CHUNK],
+        }
+    }
+}
+
+impl WindowRulesMessage {
+    /// Split `rules` into as many messages as needed; always at least one.
+    pub fn encode(sequence: u64, rules: &crate::config::WindowRules) -> Result<Vec<Self>, String> {
+        let json = serde_json::to_vec(rules).map_err(|e| e.to_string())?;
+        let chunk_count = json.len().div_ceil(MAX_WINDOW_RULES_CHUNK).max(1);
+        Ok((0..chunk_count)
+            .map(|i| {
+                let chunk = json
+                    .get(
+                        i * MAX_WINDOW_RULES_CHUNK
+                            ..((i + 1) * MAX_WINDOW_RULES_CHUNK).min(json.len()),
+                    )
+                    .unwrap_or(&[]);
+                let mut message = Self {
+                    sequence,
+                    chunk_index: i as u32,
+                    chunk_count: chunk_count as u32,
+                    len: chunk.len() as u32,
+                    ..Default::default()
+                };
+                message.data[..chunk.len()].copy_from_slice(chunk);
+                message
+            })
+            .collect())
+    }
+
+    /// The JSON bytes carried by this message.
+    pub fn bytes(&self) -> &[u8] {
+        &self.data[..(self.len as usize).min(MAX_WINDOW_RULES_CHUNK)]
+    }
+}
+
+/// Rebuilds `WindowRules` from `WindowRulesMessage` chunks.
+#[derive(Debug, Default)]
+pub struct WindowRulesAssembler {
+    sequence: u64,
+    chunks: Vec<Option<Vec<u8>>>,
+}
+
+impl WindowRulesAssembler {
+    /// Add one chunk; returns the rules once the last chunk of the reply arrives. A
+    /// chunk from a newer reply drops the older, partial one.
+    pub fn push(
+        &mut self,
+        message: &WindowRulesMessage,
+    ) -> Option<Result<crate::config::WindowRules, String>> {
+        let chunk_count = message.chunk_count.max(1) as usize;
+        let chunk_index = message.chunk_index as usize;
+        if chunk_index >= chunk_count {
+            return None;
+        }
+        if self.sequence != message.sequence || self.chunks.len() != chunk_count {
+            self.sequence = message.sequence;
+            self.chunks = vec![None; chunk_count];
         }
+        self.chunks[chunk_index] = Some(message.bytes().to_vec());
+        if self.chunks.iter().any(|chunk| chunk.is_none()) {
+            return None;
+        }
+        let json: Vec<u8> = std::mem::take(&mut self.chunks)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect();
+        Some(serde_json::from_slice(&json).map_err(|e| format!("Invalid window rules: {}", e)))
+    }
+}
+
+/// Why the manageable-window filter accepts or rejects one window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ZeroCopySend)]
+#[repr(C)]
+pub struct WindowFilterDecisionMessage {
+    pub hwnd: u64,
+    pub accepted: bool,
+    /// Name of the rule that decided, zero-padded UTF-8; empty if none did
+    pub rule: [u8; 128],
+    pub rule_len: u32,
+}
+
+impl WindowFilterDecisionMessage {
+    /// Encode `decision`, cutting long rule names to 128 bytes.
+    pub fn new(hwnd: u64, decision: &crate::config::FilterDecision) -> Self {
+        let name = decision.rule.as_deref().unwrap_or_default().as_bytes();
+        let len = name.len().min(128);
+        let mut rule = [0u8; 128];
+        rule[..len].copy_from_slice(&name[..len]);
+        Self {
+            hwnd,
+            accepted: decision.accepted,
+            rule,
+            rule_len: len as u32,
+        }
+    }
+
+    /// Decoded decision.
+    pub fn decision(&self) -> crate::config::FilterDecision {
+        let len = (self.rule_len as usize).min(128);
+        crate::config::FilterDecision {
+            accepted: self.accepted,
+            rule: (len > 0).then(|| String::from_utf8_lossy(&self.rule[..len]).into_owned()),
+        }
+    }
+}
+
+/// Published on `GRID_WORKSPACE_EVENTS_SERVICE` whenever a monitor switches workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ZeroCopySend)]
+#[repr(C)]
+pub struct WorkspaceChangedEvent {
+    pub monitor_id: u32,
+    /// Workspace now shown, zero-padded UTF-8
+    pub name: [u8; 32],
+    /// Workspace switched away from, zero-padded UTF-8
+    pub previous: [u8; 32],
+    /// Windows brought back by the switch
+    pub window_count: u32,
+    pub timestamp: u64,
+}
+
+impl WorkspaceChangedEvent {
+    /// Encode a switch, cutting names longer than 32 bytes.
+    pub fn new(monitor_id: u32, name: &str, previous: &str, window_count: u32) -> Self {
+        Self {
+            monitor_id,
+            name: truncate_name(name),
+            previous: truncate_name(previous),
+            window_count,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }
+    }
+
+    /// Decoded `name`.
+    pub fn name(&self) -> String {
+        decode_name(&self.name)
+    }
+
+    /// Decoded `previous`.
+    pub fn previous(&self) -> String {
+        decode_name(&self.previous)
+    }
+}
+
+/// Published on `GRID_BATCH_EVENTS_SERVICE` once every window moved by a swap or
+/// rotation command has stopped animating. Batches complete in the order they started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct AnimationBatchCompletedEvent {
+    pub batch_id: u64,
+    /// Command that started the batch
+    pub operation: IpcCommandType,
+    /// Windows that animated; 0 if every window was already in place
+    pub window_count: u32,
+    pub timestamp: u64,
+}
+
+unsafe impl ZeroCopySend for AnimationBatchCompletedEvent {}
+
+impl AnimationBatchCompletedEvent {
+    pub fn new(batch_id: u64, operation: IpcCommandType, window_count: u32) -> Self {
+        Self {
+            batch_id,
+            operation,
+            window_count,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }
+    }
+}
+
+/// Members a `WindowGroupMessage` lists; the tracker refuses to grow a group past it.
+pub use crate::grid::MAX_GROUP_MEMBERS;
+
+/// A window group, published on `GRID_WINDOW_GROUPS_SERVICE` whenever it changes and
+/// for `ListWindowGroups`, for drawing tab strips. A group that was dissolved is sent
+/// once with `member_count` 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ZeroCopySend)]
+#[repr(C)]
+pub struct WindowGroupMessage {
+    pub group_id: u32,
+    /// Members in tab order, of which the first `member_count` are valid
+    pub members: [u64; MAX_GROUP_MEMBERS],
+    pub member_count: u32,
+    /// Index into `members` of the member on screen
+    pub active: u32,
+    pub timestamp: u64,
+}
+
+impl WindowGroupMessage {
+    pub fn new(group: &crate::grid::WindowGroup) -> Self {
+        let mut members = [0; MAX_GROUP_MEMBERS];
+        let member_count = group.members.len().min(MAX_GROUP_MEMBERS);
+        members[..member_count].copy_from_slice(&group.members[..member_count]);
+        Self {
+            member_count: member_count as u32,
+            members,
+            active: group.active.min(member_count.saturating_sub(1)) as u32,
+            ..Self::dissolved(group.id)
+        }
+    }
+
+    /// Message for a group that no longer exists.
+    pub fn dissolved(group_id: u32) -> Self {
+        Self {
+            group_id,
+            members: [0; MAX_GROUP_MEMBERS],
+            member_count: 0,
+            active: 0,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }
+    }
+
+    /// The valid members.
+    pub fn members(&self) -> &[u64] {
+        &self.members[..(self.member_count as usize).min(MAX_GROUP_MEMBERS)]
+    }
+}
+
+/// One workspace of a `ListWorkspaces` reply. The `count` messages of a reply share a
+/// `sequence`; a reply without workspaces is a single message with `count` 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ZeroCopySend)]
+#[repr(C)]
+pub struct WorkspaceListMessage {
+    pub sequence: u64,
+    pub index: u32,
+    pub count: u32,
+    pub name: [u8; 32],
+    /// Monitor showing the workspace, or `u32::MAX` if it is put away
+    pub monitor_id: u32,
+    pub window_count: u32,
+}
+
+impl WorkspaceListMessage {
+    /// Messages of one reply, cutting names longer than 32 bytes.
+    pub fn encode(sequence: u64, workspaces: &[crate::grid::WorkspaceSummary]) -> Vec<Self> {
+        if workspaces.is_empty() {
+            return vec![Self {
+                sequence,
+                index: 0,
+                count: 0,
+                name: [0; 32],
+                monitor_id: u32::MAX,
+                window_count: 0,
+            }];
+        }
+        workspaces
+            .iter()
+            .enumerate()
+            .map(|(index, workspace)| Self {
+                sequence,
+                index: index as u32,
+                count: workspaces.len() as u32,
+                name: truncate_name(&workspace.name),
+                monitor_id: workspace.monitor_id.map_or(u32::MAX, |id| id as u32),
+                window_count: workspace.window_count as u32,
+            })
+            .collect()
+    }
+
+    /// Decoded workspace, or None for the message of an empty reply.
+    pub fn summary(&self) -> Option<crate::grid::WorkspaceSummary> {
+        (self.count > 0).then(|| crate::grid::WorkspaceSummary {
+            name: decode_name(&self.name),
+            monitor_id: (self.monitor_id != u32::MAX).then_some(self.monitor_id as usize),
+            window_count: self.window_count as usize,
+        })
     }
 }
 
@@ -290,6 +1078,13 @@ pub const ANIMATION_COMMANDS_SERVICE: &str = "e_grid_animations"; // Animation c
 pub const ANIMATION_STATUS_SERVICE: &str = "e_grid_animation_status"; // Animation status updates
 pub const GRID_FOCUS_EVENTS_SERVICE: &str = "e_grid_focus_events"; // Window focus/defocus events
 pub const GRID_HEARTBEAT_SERVICE: &str = "e_grid_heartbeat"; // Server heartbeat messages
+pub const GRID_CELL_RUNS_SERVICE: &str = "e_grid_cell_runs"; // Full-size grid snapshots
+pub const GRID_WINDOW_RULES_SERVICE: &str = "e_grid_window_rules"; // Window rules queries
+pub const GRID_WINDOW_FILTER_SERVICE: &str = "e_grid_window_filter"; // Manageable-window filter decisions
+pub const GRID_WORKSPACE_EVENTS_SERVICE: &str = "e_grid_workspace_events"; // Workspace switches
+pub const GRID_WORKSPACE_LIST_SERVICE: &str = "e_grid_workspaces"; // Workspace list queries
+pub const GRID_BATCH_EVENTS_SERVICE: &str = "e_grid_batch_events"; // Swap and rotation completions
+pub const GRID_WINDOW_GROUPS_SERVICE: &str = "e_grid_window_groups"; // Window group changes and lists
 
 // Zero-copy compatible data types for iceoryx2
 // Using only basic types that work with iceoryx2's zero-copy requirements
@@ -480,6 +1275,13 @@ pub struct WindowDetails {
     pub monitor_col_start: u32,
     pub monitor_row_end: u32, // Bottom-right grid position in monitor grid
     pub monitor_col_end: u32,
+    /// Window group the window belongs to, 0 if it isn't grouped
+    pub group_id: u32,
+    /// Its tab in the group, and the number of members shown or hidden
+    pub group_index: u32,
+    pub group_size: u32,
+    /// 1 for the member on screen; hidden members are reported at its position
+    pub group_active: u32,
     // pub title: [u8; 256],
     // pub title_len: u32, // Length of title
 }
@@ -501,6 +1303,10 @@ impl Default for WindowDetails {
             monitor_col_start: 0,
             monitor_row_end: 0,
             monitor_col_end: 0,
+            group_id: 0,
+            group_index: 0,
+            group_size: 0,
+            group_active: 0,
             // title: [0; 256],
             // title_len: 0,
         }
@@ -709,6 +1515,42 @@ pub enum GridCommand {
     GetAnimationStatus {
         hwnd: u64, // 0 for all windows
     },
+    MoveWindowToSpan {
+        hwnd: u64,
+        monitor_id: Option<usize>, // None for the virtual grid
+        start_row: usize,
+        start_col: usize,
+        end_row: usize,
+        end_col: usize,
+        duration_ms: u32, // 0 moves immediately
+        easing_type: crate::EasingType,
+    },
+    MoveWindowToZone {
+        hwnd: u64,
+        monitor_id: usize,
+        zone_name: [u8; 32], // Zero-padded UTF-8
+        duration_ms: u32,    // 0 moves immediately
+        easing_type: crate::EasingType,
+    },
+    MoveWindowToCellPath {
+        hwnd: u64,
+        monitor_id: usize,
+        cell_path: [[u32; 2]; MAX_CELL_PATH_DEPTH], // see encode_cell_path
+        cell_path_len: u32,
+        duration_ms: u32, // 0 moves immediately
+        easing_type: crate::EasingType,
+    },
+    SubdivideCell {
+        monitor_id: usize,
+        cell_path: [[u32; 2]; MAX_CELL_PATH_DEPTH], // see encode_cell_path
+        cell_path_len: u32,
+        rows: usize, // 0 rows or columns merges the cell back
+        cols: usize,
+    },
+    SetOccupancyStrategy {
+        monitor_id: Option<usize>, // None for every grid
+        strategy: crate::config::CellOccupancyStrategy,
+    },
     GetGridState,
     GetGridConfig,
     GetWindowList,
@@ -8,7 +8,9 @@ use crate::ipc_protocol::{
     GRID_RESPONSE_SERVICE, GRID_WINDOW_DETAILS_SERVICE, GRID_WINDOW_LIST_SERVICE,
 };
 // use crate::GridConfig;
+use crate::backend::WindowBackend;
 use crate::config::grid_config::GridConfig;
+use crate::grid::distribution::{DistributionConstraints, Distributor};
 use crate::{
     heartbeat::HeartbeatService,
     window_events::{self, WindowEventConfig},
@@ -50,6 +52,23 @@ pub struct GridIpcServer {
     heartbeat_publisher: Option<Publisher<Service, HeartbeatMessage, ()>>,
     window_list_publisher: Option<Publisher<Service, WindowListMessage, ()>>,
     monitor_list_publisher: Option<Publisher<Service, crate::ipc_protocol::MonitorList, ()>>,
+    monitor_list_sequence: u64,
+    cell_runs_publisher: Option<Publisher<Service, crate::ipc_protocol::GridCellRunsMessage, ()>>,
+    cell_runs_sequence: u64,
+    window_rules_publisher: Option<Publisher<Service, crate::ipc_protocol::WindowRulesMessage, ()>>,
+    window_rules_sequence: u64,
+    window_filter_publisher:
+        Option<Publisher<Service, crate::ipc_protocol::WindowFilterDecisionMessage, ()>>,
+    workspace_events_publisher:
+        Option<Publisher<Service, crate::ipc_protocol::WorkspaceChangedEvent, ()>>,
+    workspace_list_publisher:
+        Option<Publisher<Service, crate::ipc_protocol::WorkspaceListMessage, ()>>,
+    workspace_list_sequence: u64,
+    batch_events_publisher:
+        Option<Publisher<Service, crate::ipc_protocol::AnimationBatchCompletedEvent, ()>>,
+    batch_operations: std::collections::HashMap<u64, IpcCommandType>, // Command of each pending batch
+    window_groups_publisher:
+        Option<Publisher<Service, crate::ipc_protocol::WindowGroupMessage, ()>>,
 
     // IPC Subscribers
     command_subscriber: Option<Subscriber<Service, IpcCommand, ()>>,
@@ -60,6 +79,11 @@ pub struct GridIpcServer {
     is_running: bool,
     event_listeners: Vec<Box<dyn Fn(&GridEvent) + Send + Sync>>,
     last_monitor_list_publish: std::time::Instant,
+    last_monitor_area_check: std::time::Instant,
+    distributor: Option<Distributor>, // All-monitor distribution mode, see set_distribution
+    last_distribution_check: std::time::Instant,
+    auto_tile: Option<(Duration, crate::EasingType)>, // Event-driven retiling, see set_auto_tile
+    lifecycle_event_receiver: Option<mpsc::Receiver<GridEvent>>, // Window lifecycle events for rules and auto-tile
 
     // New library-based event handling
     heartbeat_service: Option<HeartbeatService>,
@@ -118,6 +142,11 @@ impl GridIpcServer {
         let (window_event_direct_sender, window_event_direct_receiver) =
             std::sync::mpsc::channel::<crate::ipc_protocol::WindowEvent>();
         self.window_event_direct_receiver = Some(window_event_direct_receiver);
+        // Window rules and auto-tile get their own copy of window lifecycle events,
+        // since callers may take event_receiver for themselves
+        let (lifecycle_event_sender, lifecycle_event_receiver) =
+            std::sync::mpsc::channel::<GridEvent>();
+        self.lifecycle_event_receiver = Some(lifecycle_event_receiver);
         // --- NEW: Setup WindowEventSystem for move/resize ---
         let hwnd_map: std::sync::Arc<
             DashMap<*mut winapi::shared::windef::HWND__, crate::WindowInfo>,
@@ -130,14 +159,25 @@ impl GridIpcServer {
         let mut wes = crate::WindowEventSystem::new(hwnd_map.clone());
         wes.event_dispatch_mode = mode;
         let event_sender_for_wes = event_sender.clone();
+        let lifecycle_event_sender_for_wes = lifecycle_event_sender.clone();
         wes.set_event_callback(move |event: crate::ipc_protocol::GridEvent| {
             println!("[SERVER CALLBACK] Window event: {:?}", event);
+            if is_lifecycle_event(&event) {
+                let _ = lifecycle_event_sender_for_wes.send(event.clone());
+            }
             let _ = event_sender_for_wes.send(event.clone());
         });
         let event_sender_for_config = event_sender.clone();
         let window_event_direct_sender_clone = window_event_direct_sender.clone();
+        let backend = self
+            .tracker
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .backend
+            .clone();
         let config = WindowEventConfig {
             tracker: self.tracker.clone(),
+            backend,
             focus_callback: Some(Box::new(move |hwnd: u64, is_focused: bool| {
                 info!(
                     "🎯 Focus event: HWND {} - {}",
@@ -151,6 +191,9 @@ impl GridIpcServer {
             })),
             event_callback: Some(Box::new(move |event: crate::ipc_protocol::GridEvent| {
                 debug!("[event_callback] Received event: {:?}", event);
+                if is_lifecycle_event(&event) {
+                    let _ = lifecycle_event_sender.send(event.clone());
+                }
                 if let Err(e) = event_sender_for_config.send(event.clone()) {
                     error!("❌ Failed to send event via channel: {:?}", e);
                 }
@@ -185,6 +228,8 @@ impl GridIpcServer {
             tracker_guard.config.clone()
         };
 
+        crate::ipc_protocol::validate_grid_config(&config)?;
+
         let windows = {
             let tracker_guard = tracker.lock().unwrap();
             Arc::new(tracker_guard.windows.clone())
@@ -204,6 +249,18 @@ impl GridIpcServer {
             heartbeat_publisher: None,
             window_list_publisher: None,
             monitor_list_publisher: None,
+            monitor_list_sequence: 0,
+            cell_runs_publisher: None,
+            cell_runs_sequence: 0,
+            window_rules_publisher: None,
+            window_rules_sequence: 0,
+            window_filter_publisher: None,
+            workspace_events_publisher: None,
+            workspace_list_publisher: None,
+            workspace_list_sequence: 0,
+            batch_events_publisher: None,
+            batch_operations: std::collections::HashMap::new(),
+            window_groups_publisher: None,
             command_subscriber: None,
             layout_subscriber: None,
             cell_assignment_subscriber: None,
@@ -211,6 +268,11 @@ impl GridIpcServer {
             is_running: false,
             event_listeners: Vec::new(),
             last_monitor_list_publish: std::time::Instant::now(),
+            last_monitor_area_check: std::time::Instant::now(),
+            distributor: None,
+            last_distribution_check: std::time::Instant::now(),
+            auto_tile: None,
+            lifecycle_event_receiver: None,
             heartbeat_service: None,
             focus_event_receiver: None,
             event_receiver: None,
@@ -233,8 +295,11 @@ impl GridIpcServer {
         use crate::ipc_protocol::{WindowDetails, WindowListMessage};
         const MAX_WINDOWS: usize = 20; // Define locally since we removed it from imports
 
-        let windows_snapshot = if let Ok(tracker) = self.tracker.lock() {
-            tracker.windows.clone()
+        let (windows_snapshot, memberships) = if let Ok(tracker) = self.tracker.lock() {
+            (
+                Self::windows_with_group_members(&tracker),
+                tracker.group_memberships(),
+            )
         } else {
             return Err("Failed to lock window tracker".into());
         };
@@ -247,7 +312,8 @@ impl GridIpcServer {
         };
         for (i, entry) in windows_snapshot.iter().enumerate().take(MAX_WINDOWS) {
             let (hwnd, window_info) = entry.pair();
-            msg.windows[i] = self.create_window_details_safe(*hwnd, &*window_info);
+            msg.windows[i] =
+                self.create_window_details_safe(*hwnd, &*window_info, memberships.get(hwnd));
             msg.window_count += 1;
 
             // Debug: Print each window being published
@@ -432,26 +498,101 @@ impl GridIpcServer {
             .open_or_create()?;
         self.monitor_list_publisher = Some(monitor_list_service.publisher_builder().create()?);
 
+        // Full-size grids, which the fixed 32x32 arrays in MonitorGridIPC can't hold
+        let cell_runs_service = node
+            .service_builder(&ServiceName::new(
+                crate::ipc_protocol::GRID_CELL_RUNS_SERVICE,
+            )?)
+            .publish_subscribe::<crate::ipc_protocol::GridCellRunsMessage>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(256)
+            .open_or_create()?;
+        self.cell_runs_publisher = Some(cell_runs_service.publisher_builder().create()?);
+
+        // Replies to GetWindowRules, as chunked JSON
+        let window_rules_service = node
+            .service_builder(&ServiceName::new(
+                crate::ipc_protocol::GRID_WINDOW_RULES_SERVICE,
+            )?)
+            .publish_subscribe::<crate::ipc_protocol::WindowRulesMessage>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()?;
+        self.window_rules_publisher = Some(window_rules_service.publisher_builder().create()?);
+
+        // Replies to ExplainWindow
+        let window_filter_service = node
+            .service_builder(&ServiceName::new(
+                crate::ipc_protocol::GRID_WINDOW_FILTER_SERVICE,
+            )?)
+            .publish_subscribe::<crate::ipc_protocol::WindowFilterDecisionMessage>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .open_or_create()?;
+        self.window_filter_publisher = Some(window_filter_service.publisher_builder().create()?);
+
+        // Workspace switches, for client UIs showing the current workspace
+        let workspace_events_service = node
+            .service_builder(&ServiceName::new(
+                crate::ipc_protocol::GRID_WORKSPACE_EVENTS_SERVICE,
+            )?)
+            .publish_subscribe::<crate::ipc_protocol::WorkspaceChangedEvent>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()?;
+        self.workspace_events_publisher =
+            Some(workspace_events_service.publisher_builder().create()?);
+
+        // Replies to ListWorkspaces, one message per workspace
+        let workspace_list_service = node
+            .service_builder(&ServiceName::new(
+                crate::ipc_protocol::GRID_WORKSPACE_LIST_SERVICE,
+            )?)
+            .publish_subscribe::<crate::ipc_protocol::WorkspaceListMessage>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()?;
+        self.workspace_list_publisher = Some(workspace_list_service.publisher_builder().create()?);
+
+        // Completions of swaps and rotations, once all of their windows stop moving
+        let batch_events_service = node
+            .service_builder(&ServiceName::new(
+                crate::ipc_protocol::GRID_BATCH_EVENTS_SERVICE,
+            )?)
+            .publish_subscribe::<crate::ipc_protocol::AnimationBatchCompletedEvent>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()?;
+        self.batch_events_publisher = Some(batch_events_service.publisher_builder().create()?);
+
+        // Window group changes, and replies to ListWindowGroups, for drawing tab strips
+        let window_groups_service = node
+            .service_builder(&ServiceName::new(
+                crate::ipc_protocol::GRID_WINDOW_GROUPS_SERVICE,
+            )?)
+            .publish_subscribe::<crate::ipc_protocol::WindowGroupMessage>()
+            .max_publishers(8)
+            .max_subscribers(8)
+            .subscriber_max_buffer_size(64)
+            .open_or_create()?;
+        self.window_groups_publisher = Some(window_groups_service.publisher_builder().create()?);
+
         // Publish initial monitor list for clients
-        let initial_monitor_list = self.enumerate_monitors();
-        println!(
-            "🔍 [DEBUG] Enumerated {} monitors for initial publish",
-            initial_monitor_list.monitor_count
-        );
-        if let Some(ref mut publisher) = self.monitor_list_publisher {
-            match publisher.send_copy(initial_monitor_list) {
-                Ok(_) => {
-                    println!(
-                        "📡 [STARTUP] Published initial monitor list with {} monitors",
-                        initial_monitor_list.monitor_count
-                    );
-                }
-                Err(e) => {
-                    println!("❌ Failed to publish initial monitor list: {}", e);
-                }
+        match self.publish_monitor_list() {
+            Ok(count) => {
+                println!(
+                    "📡 [STARTUP] Published initial monitor list with {} monitors",
+                    count
+                );
+            }
+            Err(e) => {
+                println!("❌ Failed to publish initial monitor list: {}", e);
             }
-        } else {
-            println!("❌ Monitor list publisher is None!");
         }
 
         self.is_running = true;
@@ -486,20 +627,29 @@ impl GridIpcServer {
                 self.window_event_direct_receiver = Some(receiver);
             }
 
+            // Rebuild monitor grids when a taskbar or app bar appears, moves or resizes
+            if self.last_monitor_area_check.elapsed() >= Duration::from_millis(500) {
+                self.last_monitor_area_check = std::time::Instant::now();
+                self.check_monitor_areas();
+            }
+
+            // Keep the all-monitor distribution current as windows open and close
+            self.update_distribution();
+
+            // Retile monitors whose windows changed
+            self.process_lifecycle_events();
+
             // Publish monitor list periodically for new clients (every 5 seconds)
             if self.last_monitor_list_publish.elapsed().as_secs() >= 5 {
-                let monitor_list = self.enumerate_monitors();
-                if let Some(ref mut publisher) = self.monitor_list_publisher {
-                    match publisher.send_copy(monitor_list) {
-                        Ok(_) => {
-                            debug!(
-                                "📡 [PERIODIC] Published monitor list with {} monitors",
-                                monitor_list.monitor_count
-                            );
-                        }
-                        Err(e) => {
-                            debug!("❌ Failed to publish periodic monitor list: {}", e);
-                        }
+                match self.publish_monitor_list() {
+                    Ok(count) => {
+                        debug!(
+                            "📡 [PERIODIC] Published monitor list with {} monitors",
+                            count
+                        );
+                    }
+                    Err(e) => {
+                        debug!("❌ Failed to publish periodic monitor list: {}", e);
                     }
                 }
                 self.last_monitor_list_publish = std::time::Instant::now();
@@ -510,6 +660,68 @@ impl GridIpcServer {
         }
         Ok(())
     }
+    /// Re-read monitor work areas and, if any changed, publish the new monitor list
+    /// right away instead of waiting for the periodic publish.
+    fn check_monitor_areas(&mut self) {
+        let changed = match self.tracker.try_lock() {
+            Ok(mut tracker) => {
+                let changed = tracker.refresh_monitor_areas();
+                if let (false, Some(distributor)) = (changed.is_empty(), &self.distributor) {
+                    tracker.apply_distribution(distributor);
+                }
+                changed
+            }
+            Err(_) => return, // Try again on the next check
+        };
+        if changed.is_empty() {
+            return;
+        }
+        info!(
+            "🖥️ [MONITOR AREA] Work area changed on monitors {:?}, rebuilt their grids",
+            changed
+        );
+
+        match self.publish_monitor_list() {
+            Ok(count) => {
+                info!(
+                    "📡 [MONITOR AREA] Published monitor list with {} monitors",
+                    count
+                );
+            }
+            Err(e) => {
+                error!("❌ Failed to publish monitor list: {}", e);
+            }
+        }
+        self.last_monitor_list_publish = std::time::Instant::now();
+    }
+
+    /// Publish the monitor list, split into as many `MonitorList` messages as it needs,
+    /// then every grid in full as cell runs. Returns the number of monitors.
+    fn publish_monitor_list(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
+        let monitors = self.enumerate_monitors();
+        self.monitor_list_sequence += 1;
+        let publisher = self
+            .monitor_list_publisher
+            .as_mut()
+            .ok_or("Monitor list publisher is None")?;
+        let sequence = self.monitor_list_sequence;
+        for chunk in crate::ipc_protocol::MonitorList::chunks(&monitors, sequence) {
+            publisher.send_copy(chunk)?;
+        }
+
+        if let Some(publisher) = self.cell_runs_publisher.as_mut() {
+            self.cell_runs_sequence += 1;
+            let messages = match self.tracker.lock() {
+                Ok(tracker) => tracker.get_ipc_cell_runs(self.cell_runs_sequence),
+                Err(_) => Vec::new(),
+            };
+            for message in messages {
+                publisher.send_copy(message)?;
+            }
+        }
+        Ok(monitors.len())
+    }
+
     /// Start the server event loop in a background thread
     /// Note: This is a simplified version that doesn't use actual background threading
     /// due to HWND thread safety constraints
@@ -550,6 +762,18 @@ impl GridIpcServer {
         &mut self,
         command: IpcCommand,
     ) -> Result<Box<IpcResponse>, Box<dyn std::error::Error>> {
+        if command.protocol_version != crate::ipc_protocol::PROTOCOL_VERSION {
+            let message = format!(
+                "Client speaks protocol version {}, the server version {}",
+                command.protocol_version,
+                crate::ipc_protocol::PROTOCOL_VERSION
+            );
+            error!(
+                "❌ Rejected command {:?}: {}",
+                command.command_type, message
+            );
+            return Ok(Box::new(IpcResponse::error(&message)));
+        }
         match command.command_type {
             IpcCommandType::GetWindowList => {
                 self.publish_window_list_message()?;
@@ -605,23 +829,18 @@ impl GridIpcServer {
                 } else {
                     // GetMonitorList: enumerate real monitors and publish them
                     println!("🔍 [COMMAND] Received GetMonitorList command");
-                    let monitor_list = self.enumerate_monitors();
 
                     // Publish the monitor list data
-                    if let Some(ref mut publisher) = self.monitor_list_publisher {
-                        match publisher.send_copy(monitor_list) {
-                            Ok(_) => {
-                                println!(
-                                    "📡 [MONITOR LIST] Published monitor list with {} monitors",
-                                    monitor_list.monitor_count
-                                );
-                            }
-                            Err(e) => {
-                                println!("❌ Failed to publish monitor list: {}", e);
-                            }
+                    match self.publish_monitor_list() {
+                        Ok(count) => {
+                            println!(
+                                "📡 [MONITOR LIST] Published monitor list with {} monitors",
+                                count
+                            );
+                        }
+                        Err(e) => {
+                            println!("❌ Failed to publish monitor list: {}", e);
                         }
-                    } else {
-                        println!("❌ Monitor list publisher is None!");
                     }
 
                     Ok(Box::new(IpcResponse {
@@ -644,6 +863,450 @@ impl GridIpcServer {
                     protocol_version: command.protocol_version,
                 }))
             }
+            IpcCommandType::MoveWindowToSpan => {
+                let result = match (
+                    command.hwnd,
+                    command.target_row,
+                    command.target_col,
+                    command.end_row,
+                    command.end_col,
+                ) {
+                    (Some(hwnd), Some(row0), Some(col0), Some(row1), Some(col1)) => {
+                        let monitor_id = command.monitor_id.map(|id| id as usize);
+                        let start = (row0 as usize, col0 as usize);
+                        let end = (row1 as usize, col1 as usize);
+                        match self.tracker.lock() {
+                            Ok(mut tracker) => match command.animation_duration_ms {
+                                Some(ms) if ms > 0 => tracker.animate_window_to_span(
+                                    hwnd,
+                                    monitor_id,
+                                    start,
+                                    end,
+                                    Duration::from_millis(ms as u64),
+                                    command.easing_type.unwrap_or(crate::EasingType::Linear),
+                                ),
+                                _ => tracker.move_window_to_span(hwnd, monitor_id, start, end),
+                            },
+                            Err(_) => Err("Failed to acquire tracker lock".to_string()),
+                        }
+                    }
+                    _ => Err(
+                        "MoveWindowToSpan requires hwnd, target_row/col and end_row/col"
+                            .to_string(),
+                    ),
+                };
+                match result {
+                    Ok(()) => Ok(Box::new(IpcResponse::ack())),
+                    Err(e) => {
+                        error!("❌ Failed to move window to span: {}", e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::MoveWindowToZone => {
+                let zone = command.name();
+                let result = match (command.hwnd, command.monitor_id) {
+                    (Some(hwnd), Some(monitor_id)) => match self.tracker.lock() {
+                        Ok(mut tracker) => match command.animation_duration_ms {
+                            Some(ms) if ms > 0 => tracker.animate_window_to_zone(
+                                hwnd,
+                                monitor_id as usize,
+                                &zone,
+                                Duration::from_millis(ms as u64),
+                                command.easing_type.unwrap_or(crate::EasingType::Linear),
+                            ),
+                            _ => tracker.move_window_to_zone(hwnd, monitor_id as usize, &zone),
+                        },
+                        Err(_) => Err("Failed to acquire tracker lock".to_string()),
+                    },
+                    _ => Err("MoveWindowToZone requires hwnd and monitor_id".to_string()),
+                };
+                match result {
+                    Ok(()) => Ok(Box::new(IpcResponse::ack())),
+                    Err(e) => {
+                        error!("❌ Failed to move window to zone '{}': {}", zone, e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::MoveWindowToCellPath | IpcCommandType::SubdivideCell => {
+                let path = command.cell_path();
+                let result = match (command.command_type, command.monitor_id) {
+                    (_, None) => Err("Cell path commands require monitor_id".to_string()),
+                    (IpcCommandType::SubdivideCell, Some(monitor_id)) => {
+                        let rows = command.target_row.unwrap_or(0) as usize;
+                        let cols = command.target_col.unwrap_or(0) as usize;
+                        let grid = (rows > 0 && cols > 0).then(|| GridConfig::new(rows, cols));
+                        match self.tracker.lock() {
+                            Ok(mut tracker) => {
+                                tracker.subdivide_cell(monitor_id as usize, &path, grid)
+                            }
+                            Err(_) => Err("Failed to acquire tracker lock".to_string()),
+                        }
+                    }
+                    (_, Some(monitor_id)) => match (command.hwnd, self.tracker.lock()) {
+                        (None, _) => Err("MoveWindowToCellPath requires hwnd".to_string()),
+                        (Some(hwnd), Ok(mut tracker)) => match command.animation_duration_ms {
+                            Some(ms) if ms > 0 => tracker.animate_window_to_cell_path(
+                                hwnd,
+                                monitor_id as usize,
+                                &path,
+                                Duration::from_millis(ms as u64),
+                                command.easing_type.unwrap_or(crate::EasingType::Linear),
+                            ),
+                            _ => tracker.move_window_to_cell_path(hwnd, monitor_id as usize, &path),
+                        },
+                        (Some(_), Err(_)) => Err("Failed to acquire tracker lock".to_string()),
+                    },
+                };
+                match result {
+                    Ok(()) => Ok(Box::new(IpcResponse::ack())),
+                    Err(e) => {
+                        error!(
+                            "❌ Failed {:?} for cell {}: {}",
+                            command.command_type, path, e
+                        );
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::SetOccupancyStrategy => {
+                let result = match command.occupancy_strategy {
+                    Some(strategy) => match self.tracker.lock() {
+                        Ok(mut tracker) => tracker.set_occupancy_strategy(
+                            command.monitor_id.map(|id| id as usize),
+                            strategy,
+                        ),
+                        Err(_) => Err("Failed to acquire tracker lock".to_string()),
+                    },
+                    None => Err("SetOccupancyStrategy requires occupancy_strategy".to_string()),
+                };
+                match result {
+                    Ok(()) => {
+                        info!(
+                            "🧩 [COMMAND] Occupancy strategy set to {:?} for monitor {:?}",
+                            command.occupancy_strategy, command.monitor_id
+                        );
+                        Ok(Box::new(IpcResponse::ack()))
+                    }
+                    Err(e) => {
+                        error!("❌ Failed to set occupancy strategy: {}", e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::SetMonitorTiling => {
+                let animation = command
+                    .animation_duration_ms
+                    .filter(|ms| *ms > 0)
+                    .map(|ms| {
+                        (
+                            Duration::from_millis(ms as u64),
+                            command.easing_type.unwrap_or(crate::EasingType::Linear),
+                        )
+                    });
+                let result = match command.monitor_id {
+                    Some(monitor_id) => self.set_monitor_tiling(
+                        monitor_id as usize,
+                        command.tiling_algorithm,
+                        animation,
+                    ),
+                    None => Err("SetMonitorTiling requires monitor_id".to_string()),
+                };
+                match result {
+                    Ok(started) => {
+                        info!(
+                            "🧱 [COMMAND] Monitor {:?} tiling set to {:?}, moving {} windows",
+                            command.monitor_id, command.tiling_algorithm, started
+                        );
+                        Ok(Box::new(IpcResponse::ack()))
+                    }
+                    Err(e) => {
+                        error!("❌ Failed to set monitor tiling: {}", e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::EnableAutoTile | IpcCommandType::DisableAutoTile => {
+                let animation =
+                    (command.command_type == IpcCommandType::EnableAutoTile).then(|| {
+                        (
+                            Duration::from_millis(
+                                command.animation_duration_ms.unwrap_or(300) as u64
+                            ),
+                            command.easing_type.unwrap_or(crate::EasingType::EaseInOut),
+                        )
+                    });
+                self.set_auto_tile(animation);
+                Ok(Box::new(IpcResponse::ack()))
+            }
+            IpcCommandType::ExplainWindow => {
+                let result = match command.hwnd {
+                    Some(hwnd) => self.publish_window_decision(hwnd),
+                    None => Err("ExplainWindow requires hwnd".to_string()),
+                };
+                match result {
+                    Ok(decision) => {
+                        info!(
+                            "🔎 [COMMAND] Window 0x{:X} {}",
+                            command.hwnd.unwrap_or(0),
+                            decision
+                        );
+                        Ok(Box::new(IpcResponse::ack()))
+                    }
+                    Err(e) => {
+                        error!("❌ Failed to explain window: {}", e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::CreateWorkspace
+            | IpcCommandType::SwitchWorkspace
+            | IpcCommandType::MoveWindowToWorkspace => {
+                let name = command.name();
+                let animation = command
+                    .animation_duration_ms
+                    .filter(|ms| *ms > 0)
+                    .map(|ms| {
+                        (
+                            Duration::from_millis(ms as u64),
+                            command.easing_type.unwrap_or(crate::EasingType::Linear),
+                        )
+                    });
+                let result = match (command.command_type, command.monitor_id, command.hwnd) {
+                    (IpcCommandType::CreateWorkspace, _, _) => self
+                        .create_workspace(&name)
+                        .map(|()| format!("Created workspace '{}'", name)),
+                    (IpcCommandType::SwitchWorkspace, Some(monitor_id), _) => self
+                        .switch_workspace(monitor_id as usize, &name, animation)
+                        .map(|restored| {
+                            format!(
+                                "Monitor {} shows workspace '{}', {} windows restored",
+                                monitor_id, name, restored
+                            )
+                        }),
+                    (IpcCommandType::SwitchWorkspace, None, _) => {
+                        Err("SwitchWorkspace requires monitor_id".to_string())
+                    }
+                    (_, _, Some(hwnd)) => self
+                        .move_window_to_workspace(hwnd, &name, animation)
+                        .map(|()| format!("Window 0x{:X} moved to workspace '{}'", hwnd, name)),
+                    (_, _, None) => Err("MoveWindowToWorkspace requires hwnd".to_string()),
+                };
+                match result {
+                    Ok(message) => {
+                        info!("🗂️ [COMMAND] {}", message);
+                        Ok(Box::new(IpcResponse::ack()))
+                    }
+                    Err(e) => {
+                        error!("❌ Workspace command failed: {}", e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::SendToScratchpad | IpcCommandType::ToggleScratchpad => {
+                let name = command.name();
+                let animation = command
+                    .animation_duration_ms
+                    .filter(|ms| *ms > 0)
+                    .map(|ms| {
+                        (
+                            Duration::from_millis(ms as u64),
+                            command.easing_type.unwrap_or(crate::EasingType::EaseOut),
+                        )
+                    });
+                let result = match (command.command_type, command.hwnd) {
+                    (IpcCommandType::ToggleScratchpad, _) => {
+                        self.toggle_scratchpad(&name, animation).map(|shown| {
+                            format!(
+                                "Scratchpad '{}' {}",
+                                name,
+                                if shown { "shown" } else { "hidden" }
+                            )
+                        })
+                    }
+                    (_, Some(hwnd)) => self
+                        .send_to_scratchpad(hwnd, &name)
+                        .map(|()| format!("Window 0x{:X} sent to scratchpad '{}'", hwnd, name)),
+                    (_, None) => Err("SendToScratchpad requires hwnd".to_string()),
+                };
+                match result {
+                    Ok(message) => {
+                        info!("📋 [COMMAND] {}", message);
+                        Ok(Box::new(IpcResponse::ack()))
+                    }
+                    Err(e) => {
+                        error!("❌ Scratchpad command failed: {}", e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::FocusNeighbour
+            | IpcCommandType::MoveWindowInDirection
+            | IpcCommandType::SwapWindowInDirection
+            | IpcCommandType::GrowWindowSpan
+            | IpcCommandType::ShrinkWindowSpan => {
+                let animation = command
+                    .animation_duration_ms
+                    .filter(|ms| *ms > 0)
+                    .map(|ms| {
+                        (
+                            Duration::from_millis(ms as u64),
+                            command.easing_type.unwrap_or(crate::EasingType::EaseOut),
+                        )
+                    });
+                let result = match (command.command_type, command.direction) {
+                    (_, None) => Err(format!("{:?} requires direction", command.command_type)),
+                    (IpcCommandType::FocusNeighbour, Some(direction)) => self
+                        .focus_neighbour(command.hwnd, direction)
+                        .map(|hwnd| format!("Focused window 0x{:X} ({})", hwnd, direction)),
+                    (IpcCommandType::MoveWindowInDirection, Some(direction)) => self
+                        .move_window_in_direction(command.hwnd, direction, animation)
+                        .map(|monitor_id| {
+                            format!("Moved window {} onto monitor {}", direction, monitor_id)
+                        }),
+                    (IpcCommandType::SwapWindowInDirection, Some(direction)) => self
+                        .swap_window_in_direction(command.hwnd, direction, animation)
+                        .map(|hwnd| format!("Swapped window with 0x{:X} ({})", hwnd, direction)),
+                    (command_type, Some(direction)) => {
+                        let grow = command_type == IpcCommandType::GrowWindowSpan;
+                        self.resize_window_span(command.hwnd, direction, grow, animation)
+                            .map(|()| {
+                                format!(
+                                    "{} window span {}",
+                                    if grow { "Grew" } else { "Shrank" },
+                                    direction
+                                )
+                            })
+                    }
+                };
+                match result {
+                    Ok(message) => {
+                        info!("🧭 [COMMAND] {}", message);
+                        Ok(Box::new(IpcResponse::ack()))
+                    }
+                    Err(e) => {
+                        error!("❌ Navigation command failed: {}", e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::SwapWindows
+            | IpcCommandType::RotateMonitorWindows
+            | IpcCommandType::RotateWindowsAcrossMonitors => {
+                let animation = command
+                    .animation_duration_ms
+                    .filter(|ms| *ms > 0)
+                    .map(|ms| {
+                        (
+                            Duration::from_millis(ms as u64),
+                            command.easing_type.unwrap_or(crate::EasingType::EaseInOut),
+                        )
+                    });
+                let rotation = command
+                    .rotation
+                    .unwrap_or(crate::grid::RotationDirection::Clockwise);
+                let result = match (command.command_type, command.hwnd, command.other_hwnd) {
+                    (IpcCommandType::SwapWindows, Some(hwnd_a), Some(hwnd_b)) => {
+                        self.swap_windows(hwnd_a, hwnd_b, animation).map(|batch| {
+                            format!(
+                                "Swapping windows 0x{:X} and 0x{:X} (batch {})",
+                                hwnd_a, hwnd_b, batch
+                            )
+                        })
+                    }
+                    (IpcCommandType::SwapWindows, _, _) => {
+                        Err("SwapWindows requires hwnd and other_hwnd".to_string())
+                    }
+                    (IpcCommandType::RotateMonitorWindows, _, _) => self
+                        .rotate_monitor_windows(
+                            command.monitor_id.map(|id| id as usize),
+                            rotation,
+                            animation,
+                        )
+                        .map(|batch| format!("Rotating windows {:?} (batch {})", rotation, batch)),
+                    _ => self
+                        .rotate_windows_across_monitors(rotation, animation)
+                        .map(|batch| {
+                            format!(
+                                "Rotating windows {:?} across monitors (batch {})",
+                                rotation, batch
+                            )
+                        }),
+                };
+                match result {
+                    Ok(message) => {
+                        info!("🔄 [COMMAND] {}", message);
+                        Ok(Box::new(IpcResponse::ack()))
+                    }
+                    Err(e) => {
+                        error!("❌ Swap or rotation failed: {}", e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::AddToWindowGroup
+            | IpcCommandType::RemoveFromWindowGroup
+            | IpcCommandType::CycleWindowGroup
+            | IpcCommandType::ActivateWindowGroupMember
+            | IpcCommandType::ListWindowGroups => {
+                let result = match (command.command_type, command.hwnd, command.other_hwnd) {
+                    (IpcCommandType::AddToWindowGroup, Some(hwnd), Some(target)) => self
+                        .add_to_window_group(hwnd, target)
+                        .map(|id| format!("Window 0x{:X} joined group {}", hwnd, id)),
+                    (IpcCommandType::AddToWindowGroup, _, _) => {
+                        Err("AddToWindowGroup requires hwnd and other_hwnd".to_string())
+                    }
+                    (IpcCommandType::RemoveFromWindowGroup, Some(hwnd), _) => self
+                        .remove_from_window_group(hwnd)
+                        .map(|id| format!("Window 0x{:X} left group {}", hwnd, id)),
+                    (IpcCommandType::ActivateWindowGroupMember, Some(hwnd), _) => self
+                        .activate_window_group_member(hwnd)
+                        .map(|id| format!("Showing window 0x{:X} in group {}", hwnd, id)),
+                    (IpcCommandType::CycleWindowGroup, hwnd, _) => {
+                        let forward = !matches!(
+                            command.direction,
+                            Some(crate::grid::Direction::Left | crate::grid::Direction::Up)
+                        );
+                        self.cycle_window_group(hwnd, forward)
+                            .map(|shown| format!("Showing window 0x{:X} in its group", shown))
+                    }
+                    (IpcCommandType::ListWindowGroups, _, _) => self
+                        .publish_window_groups()
+                        .map(|count| format!("Published {} window groups", count)),
+                    (command_type, _, _) => Err(format!("{:?} requires hwnd", command_type)),
+                };
+                match result {
+                    Ok(message) => {
+                        info!("📑 [COMMAND] {}", message);
+                        Ok(Box::new(IpcResponse::ack()))
+                    }
+                    Err(e) => {
+                        error!("❌ Window group command failed: {}", e);
+                        Ok(Box::new(IpcResponse::error(&e)))
+                    }
+                }
+            }
+            IpcCommandType::ListWorkspaces => match self.publish_workspace_list() {
+                Ok(count) => {
+                    info!("🗂️ [COMMAND] Published {} workspaces", count);
+                    Ok(Box::new(IpcResponse::ack()))
+                }
+                Err(e) => {
+                    error!("❌ Failed to publish workspaces: {}", e);
+                    Ok(Box::new(IpcResponse::error(&e)))
+                }
+            },
+            IpcCommandType::GetWindowRules => match self.publish_window_rules(command.hwnd) {
+                Ok(count) => {
+                    info!("📏 [COMMAND] Published {} window rules", count);
+                    Ok(Box::new(IpcResponse::ack()))
+                }
+                Err(e) => {
+                    error!("❌ Failed to publish window rules: {}", e);
+                    Ok(Box::new(IpcResponse::error(&e)))
+                }
+            },
             IpcCommandType::MoveWindowToCell => {
                 // Handle MoveWindowToCell command
                 let command_clone = command.clone();
@@ -763,12 +1426,20 @@ impl GridIpcServer {
         }
         for event in events {
             info!("[process_window_events] Publishing event: {:?}", event);
+            let dropped_hwnd = match &event {
+                GridEvent::WindowMoveStop { hwnd, .. }
+                | GridEvent::WindowResizeStop { hwnd, .. } => Some(*hwnd),
+                _ => None,
+            };
             if let Err(e) = self.publish_event(event) {
                 error!(
                     "❌ [process_window_events] Failed to publish event: {:?}",
                     e
                 );
             }
+            if let Some(hwnd) = dropped_hwnd {
+                self.snap_dropped_window(hwnd);
+            }
         }
         if event_count > 0 {
             info!(
@@ -779,6 +1450,657 @@ impl GridIpcServer {
         Ok(())
     }
 
+    /// Turn snap-to-grid on or off. When on, windows are moved into the grid as soon as
+    /// the user finishes dragging or resizing them.
+    pub fn set_snap_config(&mut self, config: crate::config::SnapConfig) {
+        if let Ok(mut tracker) = self.tracker.lock() {
+            info!(
+                "🧲 Snap-to-grid {} ({:?})",
+                if config.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                },
+                config.policy
+            );
+            tracker.set_snap_config(config);
+        }
+    }
+
+    /// Snap a window the user just let go of, if snap mode is on.
+    fn snap_dropped_window(&mut self, hwnd: u64) {
+        let cursor = unsafe {
+            let mut point: winapi::shared::windef::POINT = std::mem::zeroed();
+            (winapi::um::winuser::GetCursorPos(&mut point) != 0)
+                .then(|| crate::geometry::Point::new(point.x, point.y))
+        };
+        let Ok(mut tracker) = self.tracker.lock() else {
+            return;
+        };
+        match tracker.snap_window(hwnd, cursor) {
+            Ok(Some(rect)) => info!(
+                "🧲 [SNAP] Snapped window 0x{:X} to ({}, {}, {}, {})",
+                hwnd, rect.left, rect.top, rect.right, rect.bottom
+            ),
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ [SNAP] Could not snap window 0x{:X}: {}", hwnd, e),
+        }
+    }
+
+    /// Spread every window over all monitors in optimally sized grids, regrowing the
+    /// grids as windows open and close, or with `None` put the windows back where they
+    /// started. The server loop calls `update_distribution` to keep it current.
+    pub fn set_distribution(&mut self, constraints: Option<DistributionConstraints>) {
+        let Ok(mut tracker) = self.tracker.lock() else {
+            return;
+        };
+        if let Some(previous) = self.distributor.take() {
+            info!(
+                "🧮 [DISTRIBUTE] Restoring {} windows to their original positions",
+                previous.windows().len()
+            );
+            tracker.restore_distribution(&previous);
+            tracker.finish_animations(Duration::from_secs(2));
+        }
+        if let Some(constraints) = constraints {
+            let mut distributor = Distributor::new(constraints);
+            tracker.sync_distribution(&mut distributor);
+            let plan = tracker.apply_distribution(&distributor);
+            info!(
+                "🧮 [DISTRIBUTE] Distributed {} windows across {} monitors",
+                plan.targets.len(),
+                plan.monitors.len()
+            );
+            self.distributor = Some(distributor);
+        }
+    }
+
+    /// Re-plan the distribution if windows opened or closed since the last check.
+    pub fn update_distribution(&mut self) {
+        if self.distributor.is_none()
+            || self.last_distribution_check.elapsed() < Duration::from_millis(250)
+        {
+            return;
+        }
+        self.last_distribution_check = std::time::Instant::now();
+        let (Some(distributor), Ok(mut tracker)) =
+            (self.distributor.as_mut(), self.tracker.try_lock())
+        else {
+            return; // Try again on the next check
+        };
+        if tracker.sync_distribution(distributor) {
+            let plan = tracker.apply_distribution(distributor);
+            info!(
+                "🧮 [DISTRIBUTE] Windows changed, redistributed {} windows",
+                plan.targets.len()
+            );
+        }
+    }
+
+    /// Turn auto-tile mode on, animating windows into place with `animation`, or off
+    /// with `None`. While it is on, windows opening, closing, minimizing or restoring
+    /// retile their monitor, and tiled windows the user drags away start floating.
+    /// The server loop calls `process_lifecycle_events` to react to those events.
+    pub fn set_auto_tile(&mut self, animation: Option<(Duration, crate::EasingType)>) {
+        self.auto_tile = animation;
+        let Some((duration, easing)) = animation else {
+            info!("🧱 [AUTO-TILE] Disabled");
+            return;
+        };
+        info!("🧱 [AUTO-TILE] Enabled ({:?}, {:?})", duration, easing);
+        if let Ok(mut tracker) = self.tracker.lock() {
+            let retiled = tracker.retile_changed_monitors(duration, easing);
+            if !retiled.is_empty() {
+                info!("🧱 [AUTO-TILE] Retiled monitors {:?}", retiled);
+            }
+        }
+    }
+
+    /// Tile monitor `monitor_id` with `algorithm`, or stop tiling it with `None`.
+    /// Windows move with `animation`, or with the auto-tile animation if not given.
+    pub fn set_monitor_tiling(
+        &mut self,
+        monitor_id: usize,
+        algorithm: Option<crate::grid::TilingAlgorithm>,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<usize, String> {
+        let (duration, easing) = animation
+            .or(self.auto_tile)
+            .unwrap_or((Duration::from_millis(300), crate::EasingType::EaseInOut));
+        let mut tracker = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+        tracker.set_monitor_tiling(
+            monitor_id,
+            algorithm.map(|algorithm| algorithm.layout()),
+            duration,
+            easing,
+        )
+    }
+
+    /// Replace the window rules and apply them to every window right away. New windows
+    /// are matched as they are created.
+    pub fn set_window_rules(&mut self, rules: crate::config::WindowRules) -> Result<usize, String> {
+        let mut tracker = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+        tracker.set_window_rules(rules);
+        Ok(tracker.apply_window_rules_to_all())
+    }
+
+    /// Load window rules from a JSON file and apply them, see `set_window_rules`.
+    pub fn load_window_rules(&mut self, path: &str) -> Result<usize, String> {
+        let rules = crate::config::WindowRules::load_from_file(path)
+            .map_err(|e| format!("Failed to load window rules from {}: {}", path, e))?;
+        self.set_window_rules(rules)
+    }
+
+    /// Publish the window rules on `GRID_WINDOW_RULES_SERVICE`, or with `hwnd` only the
+    /// rule matching that window (none if no rule does). Returns how many rules were sent.
+    pub fn publish_window_rules(&mut self, hwnd: Option<u64>) -> Result<usize, String> {
+        let rules = {
+            let tracker = self
+                .tracker
+                .lock()
+                .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+            match hwnd {
+                Some(hwnd) => crate::config::WindowRules {
+                    rules: tracker.matching_rule(hwnd).cloned().into_iter().collect(),
+                },
+                None => tracker.window_rules.clone(),
+            }
+        };
+        let publisher = self
+            .window_rules_publisher
+            .as_mut()
+            .ok_or("Window rules publisher is None")?;
+        self.window_rules_sequence += 1;
+        for message in
+            crate::ipc_protocol::WindowRulesMessage::encode(self.window_rules_sequence, &rules)?
+        {
+            publisher.send_copy(message).map_err(|e| e.to_string())?;
+        }
+        Ok(rules.rules.len())
+    }
+
+    /// Publish why the manageable-window filter accepts or rejects `hwnd` on
+    /// `GRID_WINDOW_FILTER_SERVICE`.
+    pub fn publish_window_decision(
+        &mut self,
+        hwnd: u64,
+    ) -> Result<crate::config::FilterDecision, String> {
+        let decision = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?
+            .explain_window(hwnd);
+        let publisher = self
+            .window_filter_publisher
+            .as_mut()
+            .ok_or("Window filter publisher is None")?;
+        publisher
+            .send_copy(crate::ipc_protocol::WindowFilterDecisionMessage::new(
+                hwnd, &decision,
+            ))
+            .map_err(|e| e.to_string())?;
+        Ok(decision)
+    }
+
+    /// Create an empty workspace named `name`.
+    pub fn create_workspace(&mut self, name: &str) -> Result<(), String> {
+        self.tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?
+            .create_workspace(name)
+    }
+
+    /// Show workspace `name` on monitor `monitor_id`, animating the restored windows
+    /// with `animation` or a default, and publish a `WorkspaceChangedEvent` on
+    /// `GRID_WORKSPACE_EVENTS_SERVICE`. Returns how many windows came back.
+    pub fn switch_workspace(
+        &mut self,
+        monitor_id: usize,
+        name: &str,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<usize, String> {
+        let (duration, easing) =
+            animation.unwrap_or((Duration::from_millis(300), crate::EasingType::EaseInOut));
+        let (previous, restored) = {
+            let mut tracker = self
+                .tracker
+                .lock()
+                .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+            let previous = tracker
+                .active_workspaces
+                .get(&monitor_id)
+                .cloned()
+                .unwrap_or_else(|| crate::grid::Workspace::default_name(monitor_id));
+            let restored = tracker.switch_workspace(monitor_id, name, duration, easing)?;
+            (previous, restored)
+        };
+        if previous == name {
+            return Ok(restored);
+        }
+        let publisher = self
+            .workspace_events_publisher
+            .as_mut()
+            .ok_or("Workspace events publisher is None")?;
+        publisher
+            .send_copy(crate::ipc_protocol::WorkspaceChangedEvent::new(
+                monitor_id as u32,
+                name,
+                &previous,
+                restored as u32,
+            ))
+            .map_err(|e| e.to_string())?;
+        Ok(restored)
+    }
+
+    /// Move `hwnd` to workspace `name`, animating with `animation` or a default if the
+    /// workspace is shown on another monitor.
+    pub fn move_window_to_workspace(
+        &mut self,
+        hwnd: u64,
+        name: &str,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<(), String> {
+        let (duration, easing) =
+            animation.unwrap_or((Duration::from_millis(300), crate::EasingType::EaseInOut));
+        self.tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?
+            .move_window_to_workspace(hwnd, name, duration, easing)
+    }
+
+    /// Publish every workspace on `GRID_WORKSPACE_LIST_SERVICE`. Returns how many were
+    /// sent.
+    pub fn publish_workspace_list(&mut self) -> Result<usize, String> {
+        let workspaces = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?
+            .list_workspaces();
+        let publisher = self
+            .workspace_list_publisher
+            .as_mut()
+            .ok_or("Workspace list publisher is None")?;
+        self.workspace_list_sequence += 1;
+        for message in crate::ipc_protocol::WorkspaceListMessage::encode(
+            self.workspace_list_sequence,
+            &workspaces,
+        ) {
+            publisher.send_copy(message).map_err(|e| e.to_string())?;
+        }
+        Ok(workspaces.len())
+    }
+
+    /// Hide `hwnd` in scratchpad `name`, then save the scratchpads to
+    /// `Scratchpads::default_path()` so the window is found again after a restart.
+    pub fn send_to_scratchpad(&mut self, hwnd: u64, name: &str) -> Result<(), String> {
+        let scratchpads = {
+            let mut tracker = self
+                .tracker
+                .lock()
+                .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+            tracker.send_to_scratchpad(hwnd, name)?;
+            tracker.scratchpads.clone()
+        };
+        let path = crate::config::Scratchpads::default_path();
+        scratchpads
+            .save_to_file(&path)
+            .map_err(|e| format!("Failed to save scratchpads to {}: {}", path, e))
+    }
+
+    /// Show scratchpad `name`'s window on the focused monitor, dropping in with
+    /// `animation` or a default, or hide it if it is shown. Returns true if it is shown.
+    pub fn toggle_scratchpad(
+        &mut self,
+        name: &str,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<bool, String> {
+        let (duration, easing) =
+            animation.unwrap_or((Duration::from_millis(200), crate::EasingType::EaseOut));
+        self.tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?
+            .toggle_scratchpad(name, duration, easing)
+    }
+
+    /// `hwnd`, or the foreground window when the command names none.
+    fn command_window(tracker: &WindowTracker, hwnd: Option<u64>) -> Result<u64, String> {
+        hwnd.or_else(|| tracker.backend.get_foreground_window())
+            .ok_or_else(|| "No window given and none has focus".to_string())
+    }
+
+    /// Focus the window next to `hwnd`, or the foreground window, in `direction`.
+    /// Returns the focused window.
+    pub fn focus_neighbour(
+        &mut self,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+    ) -> Result<u64, String> {
+        let mut tracker = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+        let hwnd = Self::command_window(&tracker, hwnd)?;
+        tracker.focus_neighbour(hwnd, direction)
+    }
+
+    /// Move `hwnd`, or the foreground window, one cell in `direction`, animating with
+    /// `animation` or a default. Returns the monitor it ends up on.
+    pub fn move_window_in_direction(
+        &mut self,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<usize, String> {
+        let (duration, easing) =
+            animation.unwrap_or((Duration::from_millis(150), crate::EasingType::EaseOut));
+        let mut tracker = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+        let hwnd = Self::command_window(&tracker, hwnd)?;
+        tracker.move_window_in_direction(hwnd, direction, duration, easing)
+    }
+
+    /// Swap `hwnd`, or the foreground window, with its neighbour in `direction`,
+    /// animating with `animation` or a default. Returns the neighbour; the swap's
+    /// completion is published like [`Self::swap_windows`].
+    pub fn swap_window_in_direction(
+        &mut self,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<u64, String> {
+        let (duration, easing) =
+            animation.unwrap_or((Duration::from_millis(150), crate::EasingType::EaseOut));
+        let mut tracker = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+        let hwnd = Self::command_window(&tracker, hwnd)?;
+        let (neighbour, batch) =
+            tracker.swap_window_in_direction(hwnd, direction, duration, easing)?;
+        self.batch_operations
+            .insert(batch, IpcCommandType::SwapWindowInDirection);
+        Ok(neighbour)
+    }
+
+    /// Swap two windows, animating with `animation` or a default. Returns the batch id
+    /// published on `GRID_BATCH_EVENTS_SERVICE` when both have stopped moving.
+    pub fn swap_windows(
+        &mut self,
+        hwnd_a: u64,
+        hwnd_b: u64,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<u64, String> {
+        let (duration, easing) =
+            animation.unwrap_or((Duration::from_millis(300), crate::EasingType::EaseInOut));
+        let batch = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?
+            .swap_windows(hwnd_a, hwnd_b, duration, easing)?;
+        self.batch_operations
+            .insert(batch, IpcCommandType::SwapWindows);
+        Ok(batch)
+    }
+
+    /// Rotate the windows on `monitor_id`, or the focused monitor, one place in
+    /// `direction`, animating with `animation` or a default. Returns the batch id.
+    pub fn rotate_monitor_windows(
+        &mut self,
+        monitor_id: Option<usize>,
+        direction: crate::grid::RotationDirection,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<u64, String> {
+        let (duration, easing) =
+            animation.unwrap_or((Duration::from_millis(300), crate::EasingType::EaseInOut));
+        let mut tracker = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+        let monitor_id = monitor_id.unwrap_or_else(|| tracker.focused_monitor());
+        let batch = tracker.rotate_monitor_windows(monitor_id, direction, duration, easing)?;
+        self.batch_operations
+            .insert(batch, IpcCommandType::RotateMonitorWindows);
+        Ok(batch)
+    }
+
+    /// Move every monitor's windows to the next monitor in `direction`, animating with
+    /// `animation` or a default. Returns the batch id.
+    pub fn rotate_windows_across_monitors(
+        &mut self,
+        direction: crate::grid::RotationDirection,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<u64, String> {
+        let (duration, easing) =
+            animation.unwrap_or((Duration::from_millis(300), crate::EasingType::EaseInOut));
+        let batch = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?
+            .rotate_windows_across_monitors(direction, duration, easing)?;
+        self.batch_operations
+            .insert(batch, IpcCommandType::RotateWindowsAcrossMonitors);
+        Ok(batch)
+    }
+
+    /// Grow or shrink the span of `hwnd`, or the foreground window, by one cell at its
+    /// edge in `direction`, animating with `animation` or a default.
+    pub fn resize_window_span(
+        &mut self,
+        hwnd: Option<u64>,
+        direction: crate::grid::Direction,
+        grow: bool,
+        animation: Option<(Duration, crate::EasingType)>,
+    ) -> Result<(), String> {
+        let (duration, easing) =
+            animation.unwrap_or((Duration::from_millis(150), crate::EasingType::EaseOut));
+        let mut tracker = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+        let hwnd = Self::command_window(&tracker, hwnd)?;
+        tracker.resize_window_span(hwnd, direction, grow, duration, easing)
+    }
+
+    /// Message describing group `id` as it is now, dissolved if it no longer exists.
+    fn window_group_message(
+        tracker: &WindowTracker,
+        id: u32,
+    ) -> crate::ipc_protocol::WindowGroupMessage {
+        tracker.window_groups.get(&id).map_or_else(
+            || crate::ipc_protocol::WindowGroupMessage::dissolved(id),
+            crate::ipc_protocol::WindowGroupMessage::new,
+        )
+    }
+
+    fn publish_window_group_messages(
+        &mut self,
+        messages: &[crate::ipc_protocol::WindowGroupMessage],
+    ) -> Result<(), String> {
+        let publisher = self
+            .window_groups_publisher
+            .as_mut()
+            .ok_or("Window groups publisher is None")?;
+        for message in messages {
+            publisher.send_copy(*message).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// `windows` and every member of their groups.
+    fn grouped_with(tracker: &WindowTracker, windows: &[u64]) -> Vec<u64> {
+        let mut grouped = windows.to_vec();
+        for hwnd in windows {
+            if let Some(group) = tracker.window_group_of(*hwnd) {
+                grouped.extend(&group.members);
+            }
+        }
+        grouped
+    }
+
+    /// Publish groups that changed, then the details of their members and of the
+    /// `former` members that left them, whose group fields and positions changed.
+    fn publish_window_group_change(
+        &mut self,
+        messages: &[crate::ipc_protocol::WindowGroupMessage],
+        former: &[u64],
+    ) -> Result<(), String> {
+        self.publish_window_group_messages(messages)?;
+        let mut windows: Vec<u64> = messages
+            .iter()
+            .flat_map(|message| message.members())
+            .chain(former)
+            .copied()
+            .collect();
+        windows.sort_unstable();
+        windows.dedup();
+        for hwnd in windows {
+            self.publish_window_details(hwnd)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Add `hwnd` to the window group of `target` and publish the groups that changed.
+    /// Returns the group id.
+    pub fn add_to_window_group(&mut self, hwnd: u64, target: u64) -> Result<u32, String> {
+        let (messages, former) = {
+            let mut tracker = self
+                .tracker
+                .lock()
+                .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+            let previous = tracker.window_group_of(hwnd).map(|group| group.id);
+            let former = Self::grouped_with(&tracker, &[hwnd, target]);
+            let id = tracker.add_to_window_group(hwnd, target)?;
+            let mut messages = vec![Self::window_group_message(&tracker, id)];
+            if let Some(previous) = previous {
+                messages.push(Self::window_group_message(&tracker, previous));
+            }
+            (messages, former)
+        };
+        self.publish_window_group_change(&messages, &former)?;
+        Ok(messages[0].group_id)
+    }
+
+    /// Take `hwnd` out of its window group and publish the group. Returns the group id.
+    pub fn remove_from_window_group(&mut self, hwnd: u64) -> Result<u32, String> {
+        let (message, former) = {
+            let mut tracker = self
+                .tracker
+                .lock()
+                .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+            let former = Self::grouped_with(&tracker, &[hwnd]);
+            let id = tracker.remove_from_window_group(hwnd)?;
+            (Self::window_group_message(&tracker, id), former)
+        };
+        self.publish_window_group_change(&[message], &former)?;
+        Ok(message.group_id)
+    }
+
+    /// Show the next (or previous) member of the group of `hwnd`, or the foreground
+    /// window, and publish the group. Returns the member now shown.
+    pub fn cycle_window_group(&mut self, hwnd: Option<u64>, forward: bool) -> Result<u64, String> {
+        let (shown, message) = {
+            let mut tracker = self
+                .tracker
+                .lock()
+                .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+            let hwnd = Self::command_window(&tracker, hwnd)?;
+            let id = tracker
+                .window_group_of(hwnd)
+                .map(|group| group.id)
+                .ok_or_else(|| format!("Window 0x{:X} is not in a group", hwnd))?;
+            let shown = tracker.cycle_window_group(hwnd, forward);
+            // Publish even on failure: closed members may have been pruned
+            (shown, Self::window_group_message(&tracker, id))
+        };
+        self.publish_window_group_change(&[message], &[])?;
+        shown
+    }
+
+    /// Show group member `hwnd` in place of the active one and publish the group.
+    /// Returns the group id.
+    pub fn activate_window_group_member(&mut self, hwnd: u64) -> Result<u32, String> {
+        let message = {
+            let mut tracker = self
+                .tracker
+                .lock()
+                .map_err(|_| "Failed to acquire tracker lock".to_string())?;
+            let id = tracker.activate_window_group_member(hwnd)?;
+            Self::window_group_message(&tracker, id)
+        };
+        self.publish_window_group_change(&[message], &[])?;
+        Ok(message.group_id)
+    }
+
+    /// Publish every window group on `GRID_WINDOW_GROUPS_SERVICE`. Returns how many
+    /// were sent.
+    pub fn publish_window_groups(&mut self) -> Result<usize, String> {
+        let messages: Vec<_> = self
+            .tracker
+            .lock()
+            .map_err(|_| "Failed to acquire tracker lock".to_string())?
+            .window_groups
+            .values()
+            .map(crate::ipc_protocol::WindowGroupMessage::new)
+            .collect();
+        self.publish_window_group_messages(&messages)?;
+        Ok(messages.len())
+    }
+
+    /// React to the window lifecycle events since the last call: apply the window rules
+    /// to new windows, then, in auto-tile mode, float tiled windows the user dragged and
+    /// retile monitors whose windows changed.
+    pub fn process_lifecycle_events(&mut self) {
+        let Some(receiver) = self.lifecycle_event_receiver.as_ref() else {
+            return;
+        };
+        let events: Vec<GridEvent> = receiver.try_iter().collect();
+        if events.is_empty() {
+            return;
+        }
+        let Ok(mut tracker) = self.tracker.lock() else {
+            return;
+        };
+        for event in &events {
+            if let GridEvent::WindowCreated { hwnd, .. } = event {
+                match tracker.apply_window_rules(*hwnd) {
+                    Ok(Some(rule)) => {
+                        info!("📏 [RULES] Window 0x{:X} matched rule '{}'", hwnd, rule)
+                    }
+                    Ok(None) => {}
+                    Err(e) => error!("❌ Window rule failed for 0x{:X}: {}", hwnd, e),
+                }
+            }
+        }
+        let Some((duration, easing)) = self.auto_tile else {
+            return;
+        };
+        for event in &events {
+            // Move events also fire for our own animations, so only windows that
+            // ended up off their tile count as dragged out
+            if let GridEvent::WindowMoveStop { hwnd, .. } = event {
+                if let Some(monitor_id) = tracker.float_if_moved_off_tile(*hwnd) {
+                    info!(
+                        "🧱 [AUTO-TILE] Window 0x{:X} dragged out of monitor {}'s tiling, now floating",
+                        hwnd, monitor_id
+                    );
+                }
+            }
+        }
+        let retiled = tracker.retile_changed_monitors(duration, easing);
+        if !retiled.is_empty() {
+            info!("🧱 [AUTO-TILE] Retiled monitors {:?}", retiled);
+        }
+    }
+
     /// Publish a window event to all connected clients
     pub fn publish_event(&mut self, event: GridEvent) -> Result<(), Box<dyn std::error::Error>> {
         // Convert high-level event to zero-copy format
@@ -819,9 +2141,20 @@ impl GridIpcServer {
     pub fn publish_window_details(&mut self, hwnd: u64) -> Result<(), Box<dyn std::error::Error>> {
         // Use try_lock to avoid blocking if the tracker is locked elsewhere.
         if let Ok(tracker) = self.tracker.try_lock() {
-            if let Some(window_info) = tracker.windows.get(&hwnd) {
+            // Hidden group members aren't tracked but are still reported for tab strips
+            let window_info = tracker.windows.get(&hwnd).map(|info| *info).or_else(|| {
+                tracker
+                    .hidden_group_member_infos()
+                    .into_iter()
+                    .find(|info| info.hwnd == hwnd)
+            });
+            if let Some(window_info) = window_info {
                 // Create the details first (immutable borrow)
-                let details = self.create_window_details_safe(hwnd, &*window_info);
+                let details = self.create_window_details_safe(
+                    hwnd,
+                    &window_info,
+                    tracker.group_memberships().get(&hwnd),
+                );
 
                 // Then publish (mutable borrow)
                 if let Some(ref mut publisher) = self.window_details_publisher {
@@ -840,9 +2173,12 @@ impl GridIpcServer {
     /// Publish details for all current windows
     pub fn publish_all_window_details(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         // Get a snapshot of windows to avoid holding the lock during publishing
-        let windows_snapshot = if let Ok(tracker) = self.tracker.lock() {
+        let (windows_snapshot, memberships) = if let Ok(tracker) = self.tracker.lock() {
             info!("📤 Publishing details for {} windows (already filtered by is_manageable_window)...", tracker.windows.len());
-            tracker.windows.clone()
+            (
+                Self::windows_with_group_members(&tracker),
+                tracker.group_memberships(),
+            )
         } else {
             return Err("Failed to lock window tracker".into());
         };
@@ -856,7 +2192,8 @@ impl GridIpcServer {
             // This ensures client and server see the same set of windows
 
             // Create details without holding tracker lock to avoid deadlock
-            let details = self.create_window_details_safe(*hwnd, &*window_info);
+            let details =
+                self.create_window_details_safe(*hwnd, &*window_info, memberships.get(hwnd));
 
             // Publish the details
             if let Some(ref mut publisher) = self.window_details_publisher {
@@ -1036,11 +2373,22 @@ impl GridIpcServer {
         }
         hash
     }
+    /// Snapshot of the tracked windows plus the group members hidden behind them, so
+    /// clients get details for every tab of a group.
+    fn windows_with_group_members(tracker: &WindowTracker) -> DashMap<u64, crate::WindowInfo> {
+        let windows = tracker.windows.clone();
+        for info in tracker.hidden_group_member_infos() {
+            windows.insert(info.hwnd, info);
+        }
+        windows
+    }
+
     /// Create window details without holding the tracker lock to avoid deadlocks
     fn create_window_details_safe(
         &self,
         hwnd: u64,
         window_info: &crate::WindowInfo,
+        group: Option<&crate::grid::GroupMembership>,
     ) -> WindowDetails {
         // Use WindowInfo rect fields directly
         let left = window_info.window_rect.left;
@@ -1160,6 +2508,12 @@ impl GridIpcServer {
             monitor_col_start,
             monitor_row_end,
             monitor_col_end,
+
+            // Window group, so clients can draw its tab strip
+            group_id: group.map_or(0, |group| group.group_id),
+            group_index: group.map_or(0, |group| group.index as u32),
+            group_size: group.map_or(0, |group| group.size as u32),
+            group_active: group.map_or(0, |group| group.is_active as u32),
             // // Title field (convert UTF-16 to UTF-8 and fit into [u8; 256])
             // title: {
             //     let utf8 = String::from_utf16_lossy(&window_info.title);
@@ -1849,12 +3203,29 @@ impl GridIpcServer {
         Ok(())
     }
 
-    /// Update all active animations
+    /// Update all active animations, publishing a completion for each swap or rotation
+    /// whose windows have all stopped
     pub fn update_animations(
         &mut self,
     ) -> Result<(Vec<u64>, Vec<u64>), Box<dyn std::error::Error>> {
         if let Ok(mut tracker) = self.tracker.lock() {
             let (completed, failed) = tracker.update_animations();
+            for batch in tracker.finished_animation_batches() {
+                let Some(operation) = self.batch_operations.remove(&batch.id) else {
+                    continue;
+                };
+                info!(
+                    "🔄 [BATCH] {:?} batch {} finished ({} windows)",
+                    operation, batch.id, batch.window_count
+                );
+                if let Some(publisher) = self.batch_events_publisher.as_ref() {
+                    publisher.send_copy(crate::ipc_protocol::AnimationBatchCompletedEvent::new(
+                        batch.id,
+                        operation,
+                        batch.window_count as u32,
+                    ))?;
+                }
+            }
             Ok((
                 completed.into_iter().map(|hwnd| hwnd as u64).collect(),
                 failed.into_iter().map(|hwnd| hwnd as u64).collect(),
@@ -1884,13 +3255,15 @@ impl GridIpcServer {
         Ok(())
     }
 
-    /// Enumerate monitors and build a MonitorList (stub: single monitor for now)
-    fn enumerate_monitors(&self) -> crate::ipc_protocol::MonitorList {
-        use crate::ipc_protocol::{GridType, MonitorGridIPC, MonitorList};
+    /// Enumerate monitors, followed by the virtual desktop as monitor 999
+    fn enumerate_monitors(&self) -> Vec<crate::ipc_protocol::MonitorGridIPC> {
+        use crate::ipc_protocol::{GridType, MonitorGridIPC};
         use std::ffi::OsString;
         use std::os::windows::ffi::OsStringExt;
         use std::ptr;
         use winapi::shared::windef::{HDC, LPRECT};
+        use winapi::shared::winerror::S_OK;
+        use winapi::um::shellscalingapi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
         use winapi::um::winuser::{EnumDisplayMonitors, GetMonitorInfoW, MONITORINFOEXW};
 
         struct MonitorEnumContext {
@@ -1917,6 +3290,16 @@ impl GridIpcServer {
             mi.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
             if GetMonitorInfoW(hmonitor, &mut mi as *mut _ as *mut _) != 0 {
                 let rect = mi.rcMonitor;
+                let work = mi.rcWork;
+                let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+                let dpi = if GetDpiForMonitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y)
+                    == S_OK
+                    && dpi_x > 0
+                {
+                    dpi_x
+                } else {
+                    crate::geometry::DEFAULT_DPI
+                };
                 let width = rect.right - rect.left;
                 let height = rect.bottom - rect.top;
                 let name = OsString::from_wide(&mi.szDevice)
@@ -1931,6 +3314,11 @@ impl GridIpcServer {
                     height,
                     x: rect.left,
                     y: rect.top,
+                    work_x: work.left,
+                    work_y: work.top,
+                    work_width: work.right - work.left,
+                    work_height: work.bottom - work.top,
+                    dpi,
                     rows: context.grid_rows as u32,
                     cols: context.grid_cols as u32,
                     name: {
@@ -1941,7 +3329,7 @@ impl GridIpcServer {
                         arr
                     },
                     name_len,
-                    grid: [[0u64; 32]; 32], // No grid data
+                    ..Default::default() // Grid data is published as cell runs
                 });
                 context.next_id += 1;
             }
@@ -1977,29 +3365,26 @@ impl GridIpcServer {
 
         // Add virtual desktop as a monitor entry (with special ID)
         // Populate the virtual desktop grid with actual grid data from the tracker
+        // (only the top-left 32x32 cells fit; clients get the full grid as cell runs)
         let virtual_grid_data = if let Ok(tracker) = self.tracker.lock() {
-            let mut grid_data = [[0u64; 32]; 32];
-            for (row_idx, row) in tracker.grid.iter().enumerate().take(context.grid_rows) {
-                for (col_idx, cell) in row.iter().enumerate().take(context.grid_cols) {
-                    grid_data[row_idx][col_idx] = match cell {
-                        crate::CellState::Occupied(hwnd) => *hwnd,
-                        crate::CellState::Empty => 0u64,
-                        crate::CellState::OffScreen => u64::MAX, // Special value for offscreen areas
-                    };
-                }
-            }
-            grid_data
+            WindowTracker::ipc_cells(&tracker.grid)
         } else {
-            [[0u64; 32]; 32] // Fallback to empty grid if tracker is locked
+            Vec::new() // Fallback to empty grid if tracker is locked
         };
 
-        context.monitors.push(MonitorGridIPC {
+        let mut virtual_monitor = MonitorGridIPC {
             monitor_id: 999, // Special ID for virtual desktop
             grid_type: GridType::Virtual,
             width: virtual_rect.right - virtual_rect.left,
             height: virtual_rect.bottom - virtual_rect.top,
             x: virtual_rect.left,
             y: virtual_rect.top,
+            work_x: virtual_rect.left,
+            work_y: virtual_rect.top,
+            work_width: virtual_rect.right - virtual_rect.left,
+            work_height: virtual_rect.bottom - virtual_rect.top,
+            // The virtual desktop spans monitors; it is reported in raw pixels
+            dpi: crate::geometry::DEFAULT_DPI,
             rows: context.grid_rows as u32,
             cols: context.grid_cols as u32,
             name: {
@@ -2011,18 +3396,12 @@ impl GridIpcServer {
                 arr
             },
             name_len: "VIRTUAL_DESKTOP".len() as u32,
-            grid: virtual_grid_data, // Populated virtual desktop grid
-        });
+            ..Default::default()
+        };
+        virtual_monitor.set_grid(&virtual_grid_data);
+        context.monitors.push(virtual_monitor);
 
-        let mut monitors_array: [MonitorGridIPC; 16] = [MonitorGridIPC::default(); 16];
-        let count = context.monitors.len().min(16);
-        for (i, monitor) in context.monitors.into_iter().take(16).enumerate() {
-            monitors_array[i] = monitor;
-        }
-        MonitorList {
-            monitors: monitors_array,
-            monitor_count: count as u32,
-        }
+        context.monitors
     }
 
     /// Determine which monitor a window is primarily on based on its coordinates
@@ -2049,11 +3428,11 @@ impl GridIpcServer {
         0
     }
 
-    /// Get the bounds of a specific monitor
+    /// Get the rectangle a specific monitor's grid is laid over (bounds or work area)
     fn get_monitor_bounds(&self, monitor_id: u32) -> Option<(i32, i32, i32, i32)> {
         if let Ok(tracker) = self.tracker.lock() {
             if let Some(monitor_grid) = tracker.monitor_grids.get(monitor_id as usize) {
-                let rect = monitor_grid.monitor_rect;
+                let rect = monitor_grid.grid_rect();
                 return Some((rect.left, rect.top, rect.right, rect.bottom));
             }
         }
@@ -2061,8 +3440,28 @@ impl GridIpcServer {
     }
 }
 
+/// Whether `event` matters to window rules or can change what a tiled monitor holds: a
+/// window opening, closing, minimizing, restoring, or being dropped after a drag.
+fn is_lifecycle_event(event: &GridEvent) -> bool {
+    match event {
+        GridEvent::WindowCreated { .. }
+        | GridEvent::WindowDestroyed { .. }
+        | GridEvent::WindowMoveStop { .. } => true,
+        GridEvent::WindowStateChanged { state, .. } => state.starts_with("minimize"),
+        _ => false,
+    }
+}
+
 impl Drop for GridIpcServer {
     fn drop(&mut self) {
+        // Put distributed windows straight back before going away: nothing steps
+        // animations once the server is gone
+        if let Some(distributor) = self.distributor.take() {
+            let mut tracker = self.tracker.lock().unwrap_or_else(|e| e.into_inner());
+            for (hwnd, e) in tracker.restore_distribution_now(&distributor) {
+                error!("❌ [DISTRIBUTE] Failed to restore window {:?}: {}", hwnd, e);
+            }
+        }
         // Cleanup window events using the library system
         window_events::cleanup_hooks();
     }
@@ -2107,6 +3506,8 @@ where
         let _ = ipc_server.process_layout_commands();
         let _ = ipc_server.process_animation_commands();
         let _ = ipc_server.update_animations();
+        ipc_server.update_distribution();
+        ipc_server.process_lifecycle_events();
         tick_callback(); // Call the user-provided callback
         true
     })?;
@@ -2145,6 +3546,8 @@ where
         let _ = ipc_server.process_layout_commands();
         let _ = ipc_server.process_animation_commands();
         let _ = ipc_server.update_animations();
+        ipc_server.update_distribution();
+        ipc_server.process_lifecycle_events();
         true
     })?;
     Ok(())
@@ -7,15 +7,30 @@ pub enum EventDispatchMode {
     AutoTrack,
 }
 
+#[cfg(feature = "ipc")]
 use crossbeam_queue::SegQueue;
+#[cfg(feature = "ipc")]
 use dashmap::DashMap;
+#[cfg(feature = "ipc")]
 use ringbuf::wrap::Prod;
+#[cfg(feature = "ipc")]
 use ringbuf::HeapRb;
-use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "ipc")]
+use std::sync::atomic::AtomicBool;
+#[cfg(feature = "win32")]
+use std::sync::atomic::Ordering;
+#[cfg(feature = "ipc")]
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+#[cfg(feature = "animation")]
+use std::time::Duration;
+use std::time::Instant;
+#[cfg(feature = "win32")]
 use winapi::shared::minwindef::LPARAM;
-use winapi::shared::windef::{HWND, RECT};
+#[cfg(feature = "win32")]
+use winapi::shared::windef::HWND;
+#[cfg(feature = "ipc")]
+use winapi::shared::windef::RECT;
+#[cfg(feature = "ipc")]
 use winapi::um::winuser::*;
 
 // Import our error handling module
@@ -26,36 +41,58 @@ pub use grid_client_errors::{
 };
 
 // Import the centralized grid display module
+#[cfg(feature = "tui")]
 pub mod grid_display;
 
+#[cfg(feature = "grid-math")]
+pub mod backend;
 pub mod config;
 pub mod display;
+pub mod geometry;
+#[cfg(feature = "grid-math")]
 pub mod grid;
 pub mod grid_client_config;
+#[cfg(feature = "ipc")]
 pub mod monitor;
 pub mod performance_monitor;
 pub mod util;
+#[cfg(feature = "grid-math")]
 pub mod window;
-pub use crate::grid::GridConfig;
+#[cfg(feature = "grid-math")]
+pub use crate::backend::{MonitorAreas, SimulatedDesktop, WindowBackend, WindowShowState};
+pub use crate::config::{
+    CellOccupancyStrategy, CellPath, GridArea, GridConfig, GridUnits, SnapConfig, SnapPolicy,
+    SnapRule, SubGrid, TrackSize, WindowFilter, WindowMatcher, WindowRule, WindowRules, Zone,
+    ZoneLayout,
+};
+pub use crate::geometry::{Insets, Point, Rect, Size};
 pub use crate::grid_client_config::GridClientConfig;
+#[cfg(feature = "ipc")]
 use crate::ipc_client::IpcCommand;
+#[cfg(feature = "ipc")]
 use crate::ipc_protocol::GridEvent;
 pub use crate::performance_monitor::{EventType, OperationTimer, PerformanceMonitor};
+#[cfg(feature = "animation")]
+pub use crate::window::animation::EasingType;
+#[cfg(feature = "grid-math")]
 pub use crate::window::WindowInfo;
+#[cfg(feature = "grid-math")]
 pub use crate::window_tracker::WindowTracker;
-pub use grid::animation::EasingType;
 
 // Import the heartbeat service module
 pub mod heartbeat;
 pub use heartbeat::HeartbeatService;
 
 // Import window events module with unified hook management
+#[cfg(feature = "ipc")]
 pub mod window_events;
+#[cfg(feature = "ipc")]
 pub use window_events::{setup_window_events, WindowEventConfig};
 
-// Coverage threshold: percentage of cell area that must be covered by window
-// to consider the window as occupying that cell (0.0 to 1.0)
-pub const COVERAGE_THRESHOLD: f32 = 0.01; // 30% coverage required
+// Coverage threshold: fraction of cell area that must be covered by window
+// to consider the window as occupying that cell (0.0 to 1.0). Default for
+// `CellOccupancyStrategy::MinCoverage`; grids can pick another strategy.
+pub const COVERAGE_THRESHOLD: f32 = 0.01; // 1% coverage required
 pub const MAX_WINDOW_GRID_CELLS: usize = 64;
 // Animation and Tweening System
 // #[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -84,6 +121,7 @@ pub const EVENT_TYPE_WINDOW_DEFOCUSED: u8 = 11;
 pub const EVENT_TYPE_WINDOW_STATE_CHANGED: u8 = 12;
 
 // --- Mapping method for event type codes ---
+#[cfg(feature = "ipc")]
 pub fn grid_event_type_code(event: &GridEvent) -> u8 {
     match event {
         GridEvent::WindowCreated { .. } => EVENT_TYPE_WINDOW_CREATED,
@@ -139,29 +177,31 @@ pub fn type_code_grid_event_str(code: u8) -> &'static str {
     }
 }
 
+#[cfg(feature = "animation")]
 #[derive(Clone)]
 pub struct WindowAnimation {
     pub hwnd: u64,
-    pub start_rect: RECT,
-    pub target_rect: RECT,
+    pub start_rect: Rect,
+    pub target_rect: Rect,
     pub start_time: Instant,
     pub duration: Duration,
     pub easing: EasingType,
     pub completed: bool,
 }
 
+#[cfg(feature = "animation")]
 impl WindowAnimation {
     pub fn new(
         hwnd: u64,
-        start_rect: RECT,
-        target_rect: RECT,
+        start_rect: impl Into<Rect>,
+        target_rect: impl Into<Rect>,
         duration: Duration,
         easing: EasingType,
     ) -> Self {
         Self {
             hwnd,
-            start_rect,
-            target_rect,
+            start_rect: start_rect.into(),
+            target_rect: target_rect.into(),
             start_time: Instant::now(),
             duration,
             easing,
@@ -169,7 +209,7 @@ impl WindowAnimation {
         }
     }
 
-    pub fn get_current_rect(&self) -> RECT {
+    pub fn get_current_rect(&self) -> Rect {
         if self.completed {
             return self.target_rect;
         }
@@ -182,7 +222,7 @@ impl WindowAnimation {
         let progress = elapsed.as_secs_f32() / self.duration.as_secs_f32();
         let eased_progress = self.apply_easing(progress);
 
-        RECT {
+        Rect {
             left: self.lerp(self.start_rect.left, self.target_rect.left, eased_progress),
             top: self.lerp(self.start_rect.top, self.target_rect.top, eased_progress),
             right: self.lerp(
@@ -349,20 +389,23 @@ pub enum CellState {
 // }
 
 // Move/resize detection state (per window)
+#[cfg(feature = "ipc")]
 pub struct MoveResizeState {
     pub last_event: Instant,
     pub in_progress: AtomicBool, // Thread-safe atomic boolean
-    pub last_rect: RECT,         // Track last known window rectangle
+    pub last_rect: Rect,         // Track last known window rectangle
     pub last_type: Option<MoveResizeEventType>, // Track last event type
 }
 
 // Move/resize tracker (shared across threads)
+#[cfg(feature = "ipc")]
 pub struct MoveResizeTracker {
     pub states: Arc<DashMap<isize, MoveResizeState>>, // Use Arc for sharing
     pub timeout: Duration,
     pub event_queue: Arc<SegQueue<(isize, MoveResizeEventType)>>,
 }
 
+#[cfg(feature = "ipc")]
 impl MoveResizeTracker {
     pub fn new(
         timeout: Duration,
@@ -429,12 +472,7 @@ impl MoveResizeTracker {
                 let mut entry = states.entry(hwnd_val).or_insert(MoveResizeState {
                     last_event: Instant::now(),
                     in_progress: AtomicBool::new(false), // Initialize as atomic
-                    last_rect: RECT {
-                        left: 0,
-                        top: 0,
-                        right: 0,
-                        bottom: 0,
-                    },
+                    last_rect: Rect::default(),
                     last_type: None,
                 });
 
@@ -478,6 +516,7 @@ impl MoveResizeTracker {
 }
 
 // Event callback system for WindowTracker
+#[cfg(feature = "grid-math")]
 pub trait WindowEventCallback: Send + Sync {
     fn on_window_created(&self, hwnd: u64, window_info: &WindowInfo);
     fn on_window_destroyed(&self, hwnd: u64);
@@ -491,24 +530,35 @@ pub trait WindowEventCallback: Send + Sync {
 }
 
 // Box wrapper for dynamic dispatch
+#[cfg(feature = "grid-math")]
 pub type WindowEventCallbackBox = Box<dyn WindowEventCallback>;
 
 // iceoryx2 IPC integration for command and control
+#[cfg(feature = "ipc")]
 pub mod ipc;
+#[cfg(feature = "ipc")]
 pub mod ipc_manager;
 /// Protocol definitions and message types for IPC communication
+#[cfg(feature = "ipc")]
 pub mod ipc_protocol;
+#[cfg(feature = "grid-math")]
 pub mod window_tracker;
 // Client module for real-time grid reconstruction and monitoring
+#[cfg(feature = "ipc")]
 pub mod ipc_client;
+#[cfg(feature = "ipc")]
 pub use ipc_client::GridClient;
+#[cfg(feature = "grid-math")]
 pub mod monitor_grid;
 
 // Server module for IPC server functionality
+#[cfg(feature = "ipc")]
 pub mod ipc_server;
+#[cfg(feature = "ipc")]
 pub use crate::ipc_server::start_server;
 
 // Window enumeration callback function
+#[cfg(feature = "win32")]
 unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> i32 {
     let tracker = &mut *(lparam as *mut WindowTracker);
     let counter = tracker.enum_counter.fetch_add(1, Ordering::SeqCst) + 1;
@@ -532,6 +582,7 @@ unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> i32 {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "win32")]
     #[test]
     fn test_window_tracker_creation() {
         let tracker = WindowTracker::new();
@@ -563,6 +614,7 @@ pub enum MoveResizeEventType {
     BothStop,
 }
 
+#[cfg(feature = "ipc")]
 pub struct WindowEventSystem {
     pub move_resize_tracker: Arc<MoveResizeTracker>,
     pub windows: Arc<DashMap<HWND, WindowInfo>>,
@@ -582,6 +634,7 @@ pub struct WindowEventSystem {
     pub event_dispatch_mode: EventDispatchMode,
 }
 
+#[cfg(feature = "ipc")]
 impl WindowEventSystem {
     pub fn new(windows: Arc<DashMap<HWND, WindowInfo>>) -> Self {
         let event_queue = Arc::new(crossbeam_queue::SegQueue::new());
@@ -736,12 +789,7 @@ impl WindowEventSystem {
                             MoveResizeState {
                                 last_event: now,
                                 in_progress: AtomicBool::new(true), // Initialize as atomic
-                                last_rect: RECT {
-                                    left: 0,
-                                    top: 0,
-                                    right: 0,
-                                    bottom: 0,
-                                },
+                                last_rect: Rect::default(),
                                 last_type: Some(event_type),
                             },
                         );
@@ -1007,25 +1055,16 @@ impl WindowEventSystem {
         let screen_height =
             unsafe { winapi::um::winuser::GetSystemMetrics(winapi::um::winuser::SM_CYSCREEN) };
 
-        // Assuming 8x12 grid (or get from config)
-        let grid_rows = 8;
-        let grid_cols = 12;
-
-        let cell_width = screen_width / grid_cols;
-        let cell_height = screen_height / grid_rows;
-
-        let target_x = target_col as i32 * cell_width;
-        let target_y = target_row as i32 * cell_height;
-        let target_width = cell_width;
-        let target_height = cell_height;
-
-        // Move and resize window with animation
-        let target_rect = winapi::shared::windef::RECT {
-            left: target_x,
-            top: target_y,
-            right: target_x + target_width,
-            bottom: target_y + target_height,
-        };
+        // The event system has no tracker; place on the default 8x12 grid
+        let screen = Rect::new(0, 0, screen_width, screen_height);
+        let target_rect: RECT = crate::grid::math::cell_to_rect(
+            &screen,
+            &GridConfig::default(),
+            target_row,
+            target_col,
+        )
+        .ok_or("Target cell is outside the grid")?
+        .into();
 
         // Start the animation
         self.start_window_animation(
@@ -1076,6 +1115,7 @@ impl WindowEventSystem {
 }
 
 // Replace the mutex HashMap with a simple lock-free global queue
+#[cfg(feature = "ipc")]
 lazy_static::lazy_static! {
     static ref GLOBAL_EVENT_QUEUE: crossbeam_queue::SegQueue<(isize, MoveResizeEventType)> = crossbeam_queue::SegQueue::new();
 }
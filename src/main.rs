@@ -161,8 +161,9 @@ fn is_tracked_manageable_window(hwnd: u64) -> bool {
     false
 }
 
-/// Start the IPC server with integrated window tracking and focus events
-fn start_server() -> Result<(), Box<dyn std::error::Error>> {
+/// Start the IPC server with integrated window tracking and focus events. With
+/// `distribute`, also spread every window across all monitors until shutdown.
+fn start_server(distribute: bool) -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 Starting E-Grid Server");
     println!("=========================");
 
@@ -179,8 +180,30 @@ fn start_server() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create the window tracker
     let mut tracker = WindowTracker::new();
+    // Manageable-window filter from E_GRID_FILTER or e_grid_filter.json
+    match e_grid::WindowFilter::load_default() {
+        Ok(filter) => {
+            tracker.backend.set_window_filter(filter);
+        }
+        Err(e) => println!("⚠️ Failed to load window filter: {}", e),
+    }
+    // Window rules from E_GRID_RULES or e_grid_rules.json, applied during the scan
+    match e_grid::WindowRules::load_default() {
+        Ok(rules) if !rules.is_empty() => tracker.set_window_rules(rules),
+        Ok(_) => {}
+        Err(e) => println!("⚠️ Failed to load window rules: {}", e),
+    }
     println!("📊 Initializing window tracking...");
     tracker.scan_existing_windows();
+    // Scratchpads from E_GRID_SCRATCHPADS or e_grid_scratchpads.json; their windows are
+    // found again by identity and hidden
+    match e_grid::config::Scratchpads::load_default() {
+        Ok(scratchpads) if !scratchpads.scratchpads.is_empty() => {
+            tracker.set_scratchpads(scratchpads);
+        }
+        Ok(_) => {}
+        Err(e) => println!("⚠️ Failed to load scratchpads: {}", e),
+    }
     tracker.update_grid();
     tracker.print_virtual_grid();
 
@@ -211,6 +234,17 @@ fn start_server() -> Result<(), Box<dyn std::error::Error>> {
     // Give the server a moment to be ready
     thread::sleep(Duration::from_millis(500));
 
+    if distribute {
+        // Leave the console we were started from where it is
+        let console = WindowTracker::get_foreground_window().unwrap_or(0);
+        println!("\n🧮 Distributing windows across all monitors...");
+        ipc_server.set_distribution(Some(
+            e_grid::grid::DistributionConstraints::default()
+                .with_exclusion(console)
+                .with_animation(800, e_grid::EasingType::EaseInOut),
+        ));
+    }
+
     println!("\n✅ E-Grid Server fully operational!");
     println!("📡 Available IPC Services:");
     println!(
@@ -355,6 +389,9 @@ fn start_server() -> Result<(), Box<dyn std::error::Error>> {
         if SHUTDOWN_REQUESTED.load(std::sync::atomic::Ordering::SeqCst) {
             println!("🛑 Shutdown requested - exiting gracefully...");
 
+            // Put distributed windows back where they started
+            ipc_server.set_distribution(None);
+
             // Cleanup window events to stop the move/resize tracker
             window_events::cleanup_hooks();
 
@@ -376,6 +413,12 @@ fn start_server() -> Result<(), Box<dyn std::error::Error>> {
         // Poll move/resize events (required for move/resize start/stop detection)
         ipc_server.poll_move_resize_events();
 
+        // Keep distributed windows arranged as windows open and close
+        ipc_server.update_distribution();
+
+        // Retile tiled monitors as windows open, close, minimize and restore
+        ipc_server.process_lifecycle_events();
+
         // Process IPC commands from clients
         if let Err(e) = ipc_server.process_commands() {
             println!("⚠️ Error processing IPC commands: {}", e);
@@ -613,6 +656,7 @@ fn show_help() {
     println!("Commands:");
     println!("  (no args)     Auto-detect: start server if not running, or interactive client");
     println!("  server        Force start server mode");
+    println!("  distribute    Server mode that tiles every window across all monitors");
     println!("  client        Force start interactive client mode");
     println!("  help          Show this help message");
     println!();
@@ -627,10 +671,21 @@ fn show_help() {
     println!("  🎬 Window Animations - Smooth window transitions");
     println!("  💾 Layout Management - Save and restore window arrangements");
     println!("  📡 Multi-Client IPC - Up to 8 clients per service");
+    println!("  📏 Window Rules - Ignore, float or place windows by class, title or process");
+    println!("                    (rules file: $E_GRID_RULES or ./e_grid_rules.json)");
+    println!("  🔎 Window Filter - Include or exclude windows the built-in heuristics get wrong");
+    println!("                    (filter file: $E_GRID_FILTER or ./e_grid_filter.json)");
+    println!("  🗂️ Workspaces - Swap named sets of windows in and out per monitor (IPC)");
+    println!("  📋 Scratchpads - Hide a window and toggle it into view over the grid (IPC)");
+    println!("                    (kept in $E_GRID_SCRATCHPADS or ./e_grid_scratchpads.json)");
+    println!("  🧭 Directional Navigation - Focus, move, swap or resize windows left/right/up/down (IPC)");
+    println!("  🔄 Swap & Rotate - Swap two windows or rotate them around a monitor or across monitors (IPC)");
+    println!("  📑 Window Groups - Tab several windows in one cell span, cycling the visible one (IPC)");
     println!();
     println!("Examples:");
     println!("  e_grid                            # Auto-detect and start appropriate mode");
     println!("  e_grid server                     # Force server mode");
+    println!("  e_grid distribute                 # Tile windows, restore them on Ctrl+C");
     println!("  e_grid client                     # Force client mode");
     println!("  cargo run --example simple_focus_demo  # Test focus events");
     println!();
@@ -665,7 +720,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         Some("server") => {
             // Force server mode
-            return start_server();
+            return start_server(false);
+        }
+        Some("distribute") => {
+            // Server mode that also spreads every window across all monitors
+            return start_server(true);
         }
         Some("client") => {
             // Force client mode
@@ -696,7 +755,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         // Start server in background thread so we can also start a client
         let server_handle = thread::spawn(|| {
-            if let Err(e) = start_server() {
+            if let Err(e) = start_server(false) {
                 println!("❌ Server failed: {}", e);
             }
         });
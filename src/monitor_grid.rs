@@ -1,50 +1,40 @@
 use dashmap::DashMap;
-use winapi::shared::windef::RECT;
+use std::collections::BTreeMap;
 
 use crate::{
+    config::{CellPath, GridArea, ZoneLayout},
+    geometry::{dpi_to_scale, Point, Rect, DEFAULT_DPI},
     grid::{GridConfig, WindowInfo},
-    util::meets_coverage_threshold,
     CellState,
 };
 
-#[derive(Clone, Debug, Copy)]
-pub struct MonitorRect {
-    pub left: i32,
-    pub top: i32,
-    pub right: i32,
-    pub bottom: i32,
-}
-
-impl MonitorRect {
-    pub fn from_rect(rect: RECT) -> Self {
-        Self {
-            left: rect.left,
-            top: rect.top,
-            right: rect.right,
-            bottom: rect.bottom,
-        }
-    }
-
-    pub fn to_rect(&self) -> RECT {
-        RECT {
-            left: self.left,
-            top: self.top,
-            right: self.right,
-            bottom: self.bottom,
-        }
-    }
-}
+/// Bounds of a monitor in virtual screen coordinates.
+pub type MonitorRect = Rect;
 
 #[derive(Clone, Debug)]
 pub struct MonitorGrid {
     pub monitor_id: usize,
+    /// Full monitor bounds
     pub monitor_rect: MonitorRect,
+    /// Monitor bounds minus taskbars and docked app bars
+    pub work_area: MonitorRect,
+    /// Which of the two rectangles the grid is laid over
+    pub grid_area: GridArea,
+    /// Effective DPI of the monitor; 96 is 100% scaling
+    pub dpi: u32,
+    /// Grid layout in physical pixels for this monitor's DPI
     pub config: GridConfig,
     pub grid: Vec<Vec<CellState>>,
+    /// State of every cell inside a sub-grid (see `GridConfig::subgrids`), at every depth
+    pub subgrid_cells: BTreeMap<CellPath, CellState>,
+    /// Named zones laid over the same area as the grid, if the monitor uses them
+    pub zones: Option<ZoneLayout>,
+    /// Owner of each zone, by zone index
+    pub zone_owners: Vec<Option<u64>>,
 }
 
 impl MonitorGrid {
-    pub fn new(monitor_id: usize, monitor_rect: RECT) -> Self {
+    pub fn new(monitor_id: usize, monitor_rect: impl Into<Rect>) -> Self {
         Self::new_with_config(monitor_id, monitor_rect, GridConfig::default())
     }
     /// Updates the grid for this monitor based on the provided windows.
@@ -52,108 +42,147 @@ impl MonitorGrid {
         &mut self,
         windows: &dashmap::DashMap<u64, crate::window::info::WindowInfo>,
     ) {
-        // Clear the grid
-        for row in 0..self.config.rows {
-            for col in 0..self.config.cols {
-                self.grid[row][col] = crate::CellState::Empty;
-            }
+        let windows: Vec<(u64, Rect)> = windows
+            .iter()
+            .map(|entry| (entry.value().hwnd, entry.value().window_rect.0))
+            .collect();
+        self.place_windows(&windows);
+    }
+
+    /// Reset the grid and mark the cells each window occupies under `config.occupancy`,
+    /// and the zones under the zone layout's own rule. `windows` is in z-order, topmost last.
+    pub fn place_windows(&mut self, windows: &[(u64, Rect)]) {
+        if let Some(zones) = &self.zones {
+            self.zone_owners =
+                crate::grid::math::assign_zone_owners(&self.grid_rect(), zones, windows);
         }
-        // Place windows on the grid
-        for entry in windows.iter() {
-            let window_info = entry.value();
-            let rect = window_info.window_rect.0;
-            let cells = self.window_to_grid_cells(&rect);
-            for (row, col) in cells {
-                if row < self.config.rows && col < self.config.cols {
-                    self.grid[row][col] = crate::CellState::Occupied(window_info.hwnd);
-                }
+        self.subgrid_cells =
+            crate::grid::math::assign_subgrid_owners(&self.grid_rect(), &self.config, windows)
+                .into_iter()
+                .map(|(path, owner)| (path, owner.map_or(CellState::Empty, CellState::Occupied)))
+                .collect();
+        let owners =
+            crate::grid::math::assign_cell_owners(&self.grid_rect(), &self.config, windows);
+        for (row, row_owners) in owners.iter().enumerate() {
+            for (col, owner) in row_owners.iter().enumerate() {
+                self.grid[row][col] = match owner {
+                    Some(hwnd) => CellState::Occupied(*hwnd),
+                    None => CellState::Empty,
+                };
             }
         }
     }
-    pub fn new_with_config(monitor_id: usize, monitor_rect: RECT, config: GridConfig) -> Self {
+
+    pub fn new_with_config(
+        monitor_id: usize,
+        monitor_rect: impl Into<Rect>,
+        config: GridConfig,
+    ) -> Self {
         let grid = vec![vec![CellState::Empty; config.cols]; config.rows];
+        let monitor_rect = monitor_rect.into();
         Self {
             monitor_id,
-            monitor_rect: MonitorRect::from_rect(monitor_rect),
+            monitor_rect,
+            work_area: monitor_rect,
+            grid_area: GridArea::default(),
+            dpi: DEFAULT_DPI,
             config,
             grid,
+            subgrid_cells: BTreeMap::new(),
+            zones: None,
+            zone_owners: Vec::new(),
         }
     }
 
-    pub fn window_to_grid_cells(&self, rect: &RECT) -> Vec<(usize, usize)> {
-        let mut cells = Vec::new();
+    /// Set the work area; defaults to the full monitor bounds.
+    pub fn with_work_area(mut self, work_area: impl Into<Rect>) -> Self {
+        self.work_area = work_area.into();
+        self
+    }
 
-        // Skip windows with invalid coordinates
-        if rect.right < rect.left || rect.bottom < rect.top {
-            return cells;
-        }
+    /// Choose whether the grid covers the full bounds or only the work area.
+    pub fn with_grid_area(mut self, grid_area: GridArea) -> Self {
+        self.grid_area = grid_area;
+        self
+    }
 
-        let left = self.monitor_rect.left;
-        let top = self.monitor_rect.top;
-        let right = self.monitor_rect.right;
-        let bottom = self.monitor_rect.bottom;
+    /// Lay named zones over the grid area alongside the cells.
+    pub fn with_zone_layout(mut self, zones: Option<ZoneLayout>) -> Self {
+        self.zone_owners = vec![None; zones.as_ref().map_or(0, |z| z.len())];
+        self.zones = zones;
+        self
+    }
 
-        // Check if window intersects with this monitor
-        if rect.right <= left || rect.left >= right || rect.bottom <= top || rect.top >= bottom {
-            return cells; // Window is not on this monitor
-        }
+    /// Record the monitor's DPI. Logical-unit configs are converted to this
+    /// monitor's physical pixels.
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.dpi = dpi;
+        self.config = self.config.to_physical(self.scale_factor());
+        self
+    }
 
-        let cell_width = (right - left) / self.config.cols as i32;
-        let cell_height = (bottom - top) / self.config.rows as i32;
+    /// Physical pixels per logical pixel, e.g. 1.5 at 150% scaling.
+    pub fn scale_factor(&self) -> f64 {
+        dpi_to_scale(self.dpi)
+    }
 
-        if cell_width <= 0 || cell_height <= 0 {
-            return cells;
-        }
+    /// Convert a physical rectangle on this monitor to logical pixels. The monitor's
+    /// top-left corner is the same point in both spaces.
+    pub fn to_logical(&self, rect: &Rect) -> Rect {
+        rect.scale_about(self.origin(), 1.0 / self.scale_factor())
+    }
 
-        // Calculate potential range of cells that might be affected
-        let start_col = ((rect.left.max(left) - left) / cell_width).max(0) as usize;
-        let end_col =
-            ((rect.right.min(right) - left) / cell_width).min(self.config.cols as i32 - 1) as usize;
-        let start_row = ((rect.top.max(top) - top) / cell_height).max(0) as usize;
-        let end_row = ((rect.bottom.min(bottom) - top) / cell_height)
-            .min(self.config.rows as i32 - 1) as usize;
-
-        // Check coverage for each potentially affected cell
-        for row in start_row..=end_row {
-            for col in start_col..=end_col {
-                if row < self.config.rows && col < self.config.cols {
-                    // Calculate the exact bounds of this grid cell
-                    let cell_rect = RECT {
-                        left: left + (col as i32 * cell_width),
-                        top: top + (row as i32 * cell_height),
-                        right: left + ((col + 1) as i32 * cell_width),
-                        bottom: top + ((row + 1) as i32 * cell_height),
-                    };
-
-                    // Only include cell if window meets coverage threshold
-                    if meets_coverage_threshold(rect, &cell_rect) {
-                        cells.push((row, col));
-                    }
-                }
-            }
-        }
+    /// Convert a logical rectangle on this monitor to physical pixels.
+    pub fn to_physical(&self, rect: &Rect) -> Rect {
+        rect.scale_about(self.origin(), self.scale_factor())
+    }
 
-        cells
+    fn origin(&self) -> Point {
+        self.monitor_rect.origin()
     }
 
-    pub fn update_grid(&mut self, windows: &DashMap<u64, WindowInfo>) {
-        // Reset grid to empty
-        for row in 0..self.config.rows {
-            for col in 0..self.config.cols {
-                self.grid[row][col] = CellState::Empty;
-            }
+    /// Rectangle the grid cells are laid over.
+    pub fn grid_rect(&self) -> Rect {
+        match self.grid_area {
+            GridArea::Bounds => self.monitor_rect,
+            GridArea::WorkArea => self.work_area,
         }
+    }
 
-        // Place windows on the grid
-        for entry in windows {
-            let (hwnd, window_info) = entry.pair();
-            let grid_cells = self.window_to_grid_cells(&window_info.window_rect);
-            for (row, col) in grid_cells {
-                if row < self.config.rows && col < self.config.cols {
-                    self.grid[row][col] = CellState::Occupied(*hwnd);
-                }
-            }
-        }
+    /// Pixel rectangle of the cell at `path`, which may reach into sub-grids.
+    pub fn cell_path_to_rect(&self, path: &CellPath) -> Option<Rect> {
+        crate::grid::math::cell_path_to_rect(&self.grid_rect(), &self.config, path)
+    }
+
+    /// Cells `rect` occupies at every level, top-level cells first.
+    pub fn window_to_cell_paths(&self, rect: &Rect) -> Vec<CellPath> {
+        crate::grid::math::window_to_cell_paths(&self.grid_rect(), &self.config, rect)
+    }
+
+    /// Pixel rectangle of the zone called `name`.
+    pub fn zone_rect(&self, name: &str) -> Option<Rect> {
+        let zones = self.zones.as_ref()?;
+        let zone = &zones.zones[zones.zone_index(name)?];
+        Some(crate::grid::math::zone_to_rect(&self.grid_rect(), zone))
+    }
+
+    /// Indices of the zones `rect` occupies; empty without a zone layout.
+    pub fn window_to_zones(&self, rect: &Rect) -> Vec<usize> {
+        self.zones.as_ref().map_or_else(Vec::new, |zones| {
+            crate::grid::math::window_to_zones(&self.grid_rect(), zones, rect)
+        })
+    }
+
+    pub fn window_to_grid_cells(&self, rect: &Rect) -> Vec<(usize, usize)> {
+        crate::grid::math::window_to_grid_cells(&self.grid_rect(), &self.config, rect)
+    }
+
+    pub fn update_grid(&mut self, windows: &DashMap<u64, WindowInfo>) {
+        let windows: Vec<(u64, Rect)> = windows
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().window_rect.0))
+            .collect();
+        self.place_windows(&windows);
     }
 
     pub fn print_grid(&self) {
@@ -179,5 +208,46 @@ impl MonitorGrid {
             }
             println!();
         }
+        self.print_subgrids();
+        self.print_zones();
+    }
+
+    /// List the cells of every sub-grid with their owners.
+    pub fn print_subgrids(&self) {
+        if self.subgrid_cells.is_empty() {
+            return;
+        }
+        println!("Sub-grid cells:");
+        for (path, state) in &self.subgrid_cells {
+            match state {
+                CellState::Occupied(hwnd) => println!("  {:<20} 0x{:X}", path.to_string(), hwnd),
+                _ => println!("  {:<20} .", path.to_string()),
+            }
+        }
+    }
+
+    /// List the zones, if any, with their owners.
+    pub fn print_zones(&self) {
+        let Some(zones) = &self.zones else {
+            return;
+        };
+        println!("Zones ({}):", zones.name);
+        for (i, zone) in zones.zones.iter().enumerate() {
+            let rect = crate::grid::math::zone_to_rect(&self.grid_rect(), zone);
+            let owner = match self.zone_owners.get(i).copied().flatten() {
+                Some(hwnd) => format!("0x{:X}", hwnd),
+                None => ".".to_string(),
+            };
+            println!(
+                "  {:>2} {:<16} {}x{} at ({}, {})  {}",
+                i,
+                zone.name,
+                rect.width(),
+                rect.height(),
+                rect.left,
+                rect.top,
+                owner
+            );
+        }
     }
 }
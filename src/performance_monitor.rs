@@ -52,6 +52,12 @@ pub struct PerformanceMonitor {
     start_time: Instant,
 }
 
+impl Default for PerformanceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PerformanceMonitor {
     pub fn new() -> Self {
         Self {
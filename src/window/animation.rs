@@ -1,16 +1,49 @@
 // Window animation support
 // Moved from lib.rs to maintain modular structure
 
-use crate::grid::animation::EasingType;
+use crate::geometry::Rect;
+#[cfg(feature = "ipc")]
+use iceoryx2::prelude::ZeroCopySend;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::time::{Duration, Instant};
-use winapi::shared::windef::RECT;
+
+#[repr(C)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ipc", derive(ZeroCopySend))]
+pub enum EasingType {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    Bounce,
+    Elastic,
+    Back,
+}
+
+// Automatically build a static map from enum discriminant to name string
+pub static EASING_TYPE_MAP: Lazy<HashMap<EasingType, &'static str>> = Lazy::new(|| {
+    use EasingType::*;
+    [
+        (Linear, "Linear"),
+        (EaseIn, "EaseIn"),
+        (EaseOut, "EaseOut"),
+        (EaseInOut, "EaseInOut"),
+        (Bounce, "Bounce"),
+        (Elastic, "Elastic"),
+        (Back, "Back"),
+    ]
+    .iter()
+    .copied()
+    .collect()
+});
 
 #[derive(Clone)]
 pub struct WindowAnimation {
     pub hwnd: u64,
-    pub start_rect: RECT,
-    pub target_rect: RECT,
+    pub start_rect: Rect,
+    pub target_rect: Rect,
     pub start_time: Instant,
     pub duration: Duration,
     pub easing: EasingType,
@@ -83,7 +116,7 @@ impl WindowAnimation {
     }
 
     /// Get the current interpolated rectangle
-    pub fn get_current_rect(&mut self) -> RECT {
+    pub fn get_current_rect(&mut self) -> Rect {
         let progress = self.get_progress();
 
         if progress >= 1.0 {
@@ -95,7 +128,7 @@ impl WindowAnimation {
         let eased_progress = Self::apply_easing(progress, &self.easing);
 
         // Interpolate rectangle
-        RECT {
+        Rect {
             left: Self::lerp(self.start_rect.left, self.target_rect.left, eased_progress),
             top: Self::lerp(self.start_rect.top, self.target_rect.top, eased_progress),
             right: Self::lerp(
@@ -111,9 +144,10 @@ impl WindowAnimation {
         }
     }
 
-    /// Check if the animation is completed
+    /// Check if the animation is completed: its final frame, the target rect, has been
+    /// taken from [`Self::get_current_rect`], even if time ran out between frames
     pub fn is_completed(&self) -> bool {
-        self.completed || self.start_time.elapsed() >= self.duration
+        self.completed
     }
 
     /// Linear interpolation between two i32 values
@@ -170,3 +204,14 @@ impl WindowAnimation {
         }
     }
 }
+
+/// Windows started animating together by one operation, such as a swap or a rotation,
+/// so the operation can be reported finished once all of them have stopped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnimationBatch {
+    pub id: u64,
+    /// Windows that started animating
+    pub window_count: usize,
+    /// Windows still animating
+    pub pending: HashSet<u64>,
+}
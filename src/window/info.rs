@@ -1,7 +1,9 @@
 // Window information and related structures
 
 use std::fmt;
-use winapi::shared::windef::{HWND, RECT};
+
+use crate::geometry::Rect;
+pub use crate::geometry::UsizeRect;
 /// Converts a window rectangle to a grid rectangle based on the grid configuration.
 pub fn rect_to_grid_rect(rect: &RectWrapper, config: &crate::grid::GridConfig) -> UsizeRect {
     // Example implementation: map window rect to grid cell indices
@@ -49,12 +51,7 @@ impl Default for WindowInfo {
             hwnd: 0,
             title: [0u16; 256],
             title_len: 0,
-            window_rect: RectWrapper(RECT {
-                left: 0,
-                top: 0,
-                right: 0,
-                bottom: 0,
-            }),
+            window_rect: RectWrapper::default(),
             // grid_rect: UsizeRect {
             //     left: 0,
             //     top: 0,
@@ -116,7 +113,7 @@ impl fmt::Debug for WindowInfo {
 }
 
 impl WindowInfo {
-    pub fn new(hwnd: HWND, title: &str, rect: RECT) -> Self {
+    pub fn new(hwnd: u64, title: &str, rect: Rect) -> Self {
         let mut title_buf = [0u16; 256];
         let mut title_len = 0u32;
         for (i, c) in title.encode_utf16().take(256).enumerate() {
@@ -124,7 +121,7 @@ impl WindowInfo {
             title_len += 1;
         }
         Self {
-            hwnd: hwnd as u64,
+            hwnd,
             title: title_buf,
             title_len,
             window_rect: RectWrapper(rect),
@@ -150,7 +147,7 @@ impl WindowInfo {
         }
     }
 
-    pub fn update_rect(&mut self, new_rect: RECT) {
+    pub fn update_rect(&mut self, new_rect: Rect) {
         self.window_rect = RectWrapper(new_rect);
     }
 
@@ -174,29 +171,13 @@ impl WindowInfo {
     }
 }
 
-// Wrapper for RECT to allow trait implementations
-#[derive(Copy, Clone)]
-pub struct RectWrapper(pub RECT);
+// Wrapper for Rect to allow trait implementations
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct RectWrapper(pub Rect);
 
 impl RectWrapper {
     pub fn from_bounds(left: i32, top: i32, right: i32, bottom: i32) -> Self {
-        RectWrapper(RECT {
-            left,
-            top,
-            right,
-            bottom,
-        })
-    }
-}
-
-impl Default for RectWrapper {
-    fn default() -> Self {
-        RectWrapper(RECT {
-            left: 0,
-            top: 0,
-            right: 0,
-            bottom: 0,
-        })
+        RectWrapper(Rect::new(left, top, right, bottom))
     }
 }
 
@@ -212,68 +193,18 @@ impl fmt::Debug for RectWrapper {
     }
 }
 impl RectWrapper {
-    pub fn from_rect(rect: RECT) -> Self {
-        RectWrapper(rect)
+    pub fn from_rect(rect: impl Into<Rect>) -> Self {
+        RectWrapper(rect.into())
     }
-    pub fn to_rect(&self) -> RECT {
+    pub fn to_rect(&self) -> Rect {
         self.0
     }
 }
-// SAFETY: RECT is a plain-old-data struct (all i32 fields), so it's safe to implement Send/Sync for the wrapper
-unsafe impl Send for RectWrapper {}
-unsafe impl Sync for RectWrapper {}
 use std::ops::Deref;
 
 impl Deref for RectWrapper {
-    type Target = RECT;
+    type Target = Rect;
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
-
-// Wrapper for a RECT-like struct using usize fields
-#[derive(Copy, Clone, Default)]
-pub struct UsizeRect {
-    pub left: usize,
-    pub top: usize,
-    pub right: usize,
-    pub bottom: usize,
-}
-
-impl UsizeRect {
-    pub fn from_bounds(left: usize, top: usize, right: usize, bottom: usize) -> Self {
-        UsizeRect {
-            left,
-            top,
-            right,
-            bottom,
-        }
-    }
-}
-
-impl fmt::Debug for UsizeRect {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("UsizeRect")
-            .field("left", &self.left)
-            .field("top", &self.top)
-            .field("right", &self.right)
-            .field("bottom", &self.bottom)
-            .finish()
-    }
-}
-
-impl Deref for UsizeRect {
-    type Target = (usize, usize, usize, usize);
-    fn deref(&self) -> &Self::Target {
-        // This is a workaround to allow deref, but it creates a tuple on the fly.
-        // For most use-cases, direct field access is preferred.
-        // If you want to deref to the struct itself, you can omit Deref.
-        // Here, we use a static tuple for demonstration, but this is not idiomatic.
-        // Instead, you can implement methods for conversion.
-        panic!("Deref to tuple is not supported for UsizeRect; use fields directly");
-    }
-}
-
-// SAFETY: UsizeRect only contains usize fields, so it's safe to implement Send/Sync
-unsafe impl Send for UsizeRect {}
-unsafe impl Sync for UsizeRect {}
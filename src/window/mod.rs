@@ -1,9 +1,11 @@
 // Window management module
+#[cfg(feature = "animation")]
 pub mod animation;
 pub mod info;
 pub mod tracker;
 
 // Re-export main types
-pub use animation::WindowAnimation;
+#[cfg(feature = "animation")]
+pub use animation::{AnimationBatch, WindowAnimation};
 pub use info::WindowInfo;
 // pub use tracker::WindowTracker;
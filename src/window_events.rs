@@ -1,3 +1,4 @@
+use crate::backend::WindowBackend;
 use crate::{WindowEventCallback, WindowInfo, WindowTracker};
 use std::ptr;
 use std::sync::{Arc, Mutex};
@@ -17,6 +18,8 @@ type LONG = winapi::shared::ntdef::LONG;
 /// Configuration for window events with optional callbacks
 pub struct WindowEventConfig {
     pub tracker: Arc<Mutex<WindowTracker>>,
+    /// The tracker's backend, whose window filter the hook applies without locking the tracker
+    pub backend: Arc<dyn WindowBackend>,
     pub focus_callback: Option<Box<dyn Fn(u64, bool) + Send + Sync>>, // hwnd, is_focused
     pub heartbeat_reset: Option<Box<dyn Fn() + Send + Sync>>,
     pub event_callback: Option<Box<dyn Fn(crate::ipc_protocol::GridEvent) + Send + Sync>>, // NEW: event publishing callback
@@ -35,8 +38,14 @@ impl WindowEventConfig {
         tracker: Arc<Mutex<WindowTracker>>,
         event_dispatch_mode: crate::EventDispatchMode,
     ) -> Self {
+        let backend = tracker
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .backend
+            .clone();
         Self {
             tracker,
+            backend,
             focus_callback: None,
             heartbeat_reset: None,
             event_callback: None,        // NEW
@@ -234,7 +243,7 @@ pub unsafe extern "system" fn win_event_proc(
     // Handle focus events specially
     if event == EVENT_SYSTEM_FOREGROUND {
         let hwnd_u64 = hwnd as u64;
-        if !WindowTracker::is_manageable_window(hwnd_u64) {
+        if !config.backend.is_manageable_window(hwnd_u64) {
             return;
         }
         if let Some(ref focus_callback) = config.focus_callback {
@@ -293,7 +302,7 @@ pub unsafe extern "system" fn win_event_proc(
             let hwnd_u64 = hwnd as u64;
             let should_dispatch = match dispatch_mode {
                 crate::EventDispatchMode::TrackedOnly => {
-                    WindowTracker::is_manageable_window(hwnd_u64)
+                    config.backend.is_manageable_window(hwnd_u64)
                 }
                 crate::EventDispatchMode::Open | crate::EventDispatchMode::AutoTrack => true,
             };
@@ -363,7 +372,7 @@ pub unsafe extern "system" fn win_event_proc(
         }
         EVENT_OBJECT_SHOW => {
             let hwnd_u64 = hwnd as u64;
-            if WindowTracker::is_manageable_window(hwnd_u64) {
+            if config.backend.is_manageable_window(hwnd_u64) {
                 let class = WindowTracker::get_window_class(hwnd_u64);
                 if class == "Windows.UI.Composition.DesktopWindowContentBridge" {
                     return;
@@ -393,7 +402,7 @@ pub unsafe extern "system" fn win_event_proc(
         EVENT_OBJECT_HIDE => {
             let hwnd_u64 = hwnd as u64;
             if let Some(ref callback) = config.event_callback {
-                if WindowTracker::is_manageable_window(hwnd_u64) {
+                if config.backend.is_manageable_window(hwnd_u64) {
                     println!("🙈 [WINEVENT] Window HIDDEN: HWND 0x{:X}", hwnd_u64);
                     let title = WindowTracker::get_window_title(hwnd_u64);
                     let event = crate::ipc_protocol::GridEvent::WindowDestroyed {
@@ -406,7 +415,7 @@ pub unsafe extern "system" fn win_event_proc(
         }
         EVENT_OBJECT_LOCATIONCHANGE => {
             let hwnd_u64 = hwnd as u64;
-            if WindowTracker::is_manageable_window(hwnd_u64) {
+            if config.backend.is_manageable_window(hwnd_u64) {
                 println!("📍 [WINEVENT] Window LOCATIONCHANGE: HWND 0x{:X}", hwnd_u64);
                 if let Some(ref callback) = config.event_callback {
                     let title = WindowTracker::get_window_title(hwnd_u64);
@@ -476,7 +485,7 @@ pub unsafe extern "system" fn win_event_proc(
     if let Ok(mut tracker) = config.tracker.try_lock() {
         match event {
             EVENT_OBJECT_CREATE => {
-                if WindowTracker::is_manageable_window(hwnd as u64) {
+                if config.backend.is_manageable_window(hwnd as u64) {
                     tracker.add_window(hwnd as u64);
                 }
             }
@@ -484,7 +493,7 @@ pub unsafe extern "system" fn win_event_proc(
                 tracker.remove_window(hwnd as u64);
             }
             EVENT_OBJECT_LOCATIONCHANGE => {
-                if WindowTracker::is_manageable_window(hwnd as u64) {
+                if config.backend.is_manageable_window(hwnd as u64) {
                     // Quick validation: check if we can get a valid rect before processing
                     if let Some(rect) = WindowTracker::get_window_rect(hwnd as u64) {
                         // Ensure rect is reasonable (not zero-sized or negative)
@@ -667,7 +676,7 @@ pub unsafe extern "system" fn win_event_proc(
                 tracker.remove_window(hwnd as u64);
             }
             EVENT_SYSTEM_MINIMIZEEND => {
-                if WindowTracker::is_manageable_window(hwnd as u64) {
+                if config.backend.is_manageable_window(hwnd as u64) {
                     tracker.add_window(hwnd as u64);
                 }
             }
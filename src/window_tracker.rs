@@ -1,49 +1,80 @@
 use dashmap::DashMap;
-use std::collections::HashMap;
-use std::ptr;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "animation")]
 use std::time::Duration;
-use winapi::shared::minwindef::LPARAM;
+#[cfg(feature = "win32")]
 use winapi::shared::windef::{HWND, RECT};
-use winapi::um::errhandlingapi::GetLastError;
+#[cfg(feature = "win32")]
 use winapi::um::winuser::*;
 
+#[cfg(feature = "win32")]
+use crate::backend::Win32Backend;
+use crate::backend::{MonitorAreas, WindowBackend, WindowShowState};
+use crate::config::{
+    CellOccupancyStrategy, CellPath, FilterDecision, GridArea, RulePlacement, Scratchpads,
+    SnapConfig, SnapPolicy, SubGrid, WindowFilter, WindowProperties, WindowRule, WindowRules,
+    ZoneLayout,
+};
+use crate::geometry::{dpi_to_scale, Point, Rect, UsizeRect};
+use crate::grid::distribution::{DistributionPlan, Distributor};
+use crate::grid::group::{GroupMembership, WindowGroup, MAX_GROUP_MEMBERS};
 use crate::grid::layout::GridLayout;
+use crate::grid::navigation::{self, Direction};
+#[cfg(feature = "animation")]
+use crate::grid::rotation::{self, RotationDirection};
+use crate::grid::tiling::LayoutAlgorithm;
+use crate::grid::workspace::{Workspace, WorkspaceHideMode, WorkspaceSummary, WorkspaceWindow};
 use crate::grid::GridConfig;
 use crate::monitor_grid::MonitorGrid;
 use crate::window::info::{RectWrapper, WindowInfo};
-use crate::window::{self, WindowAnimation};
-use crate::{CellState, EasingType, WindowEventCallbackBox};
-
-// Window enumeration callback function
-unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> i32 {
-    let tracker = &mut *(lparam as *mut WindowTracker);
-    let counter = tracker.enum_counter.fetch_add(1, Ordering::SeqCst) + 1;
-
-    if WindowTracker::is_manageable_window(hwnd as u64) {
-        if tracker.add_window(hwnd as u64) { // store as u64
-             // println!("  -> Added successfully");
-        } else {
-            // println!("  -> Failed to add window");
-        }
-    }
-
-    1 // Continue enumeration
-}
+#[cfg(feature = "animation")]
+use crate::window::{self, AnimationBatch, WindowAnimation};
+#[cfg(feature = "animation")]
+use crate::EasingType;
+use crate::{CellState, WindowEventCallbackBox};
 
 pub struct WindowTracker {
     pub windows: DashMap<u64, WindowInfo>, // Lock-free concurrent HashMap, now u64
-    pub monitor_rect: RECT,                // Virtual screen rect
+    pub monitor_rect: Rect,                // Virtual screen rect
     pub config: crate::grid::GridConfig,   // Dynamic grid configuration
+    pub virtual_config: GridConfig,        // `config` in physical pixels, for the virtual grid
     pub event_dispatch_mode: crate::EventDispatchMode, // Event dispatch mode
     pub grid: Vec<Vec<CellState>>,         // Virtual grid (dynamic)
     pub monitor_grids: Vec<MonitorGrid>,   // Individual monitor grids
+    pub monitor_grid_areas: HashMap<usize, GridArea>, // Per-monitor override of the grid area
+    pub monitor_occupancy: HashMap<usize, CellOccupancyStrategy>, // Per-monitor occupancy rule
+    pub monitor_zone_layouts: HashMap<usize, ZoneLayout>, // Per-monitor named zones
+    pub monitor_subgrids: HashMap<usize, Vec<SubGrid>>, // Per-monitor cells split into sub-grids
+    pub snap_config: SnapConfig,           // Snap-to-grid when a drag or resize ends (opt-in)
+    pub monitor_tilings: HashMap<usize, Arc<dyn LayoutAlgorithm>>, // Per-monitor tiling layout
+    pub tiled_arrangements: HashMap<usize, Vec<(u64, Rect)>>, // Last tiling of each monitor
+    pub floating_windows: HashSet<u64>,    // Windows left out of tiling layouts
+    pub window_rules: WindowRules,         // Rules applied to new and rescanned windows
+    pub ignored_windows: HashSet<u64>,     // Windows a rule keeps out of tracking
+    pub workspaces: BTreeMap<String, Workspace>, // Named window sets, by name
+    pub active_workspaces: HashMap<usize, String>, // Workspace each monitor shows
+    pub workspace_hide_mode: WorkspaceHideMode, // How switched-away windows are put away
+    pub scratchpads: Scratchpads,          // Scratchpads and their windows' identities
+    pub scratchpad_windows: HashMap<String, u64>, // Window each scratchpad holds
+    pub shown_scratchpads: HashSet<String>, // Scratchpads toggled into view
+    pub window_groups: BTreeMap<u32, WindowGroup>, // Tabbed windows sharing a span, by id
+    pub window_group_counter: u32,         // Id of the last group created
+    #[cfg(feature = "animation")]
+    pub window_easings: HashMap<u64, EasingType>, // Per-window easing set by a rule
     pub enum_counter: AtomicUsize,         // Atomic counter for lock-free access
+    #[cfg(feature = "animation")]
     pub active_animations: DashMap<u64, WindowAnimation>, // Lock-free animations, now u64
+    #[cfg(feature = "animation")]
+    pub animation_batches: Vec<AnimationBatch>, // Swaps and rotations still animating
+    #[cfg(feature = "animation")]
+    pub animation_batch_counter: u64, // Id of the last batch started
     pub saved_layouts: DashMap<String, GridLayout>, // Lock-free layouts
     pub event_callbacks: Vec<WindowEventCallbackBox>, // Event callbacks
     pub desktop_hwnds: Vec<u64>,           // Track all desktop (Progman/WorkerW) HWNDs
     pub last_scan_time: std::sync::Mutex<std::time::Instant>,
+    pub backend: Arc<dyn WindowBackend>, // Window system access (Win32 or simulated)
 }
 
 impl WindowTracker {
@@ -58,37 +89,16 @@ impl WindowTracker {
     }
 
     /// Returns the HWND (u64) of the current foreground window, or None if not available.
+    #[cfg(feature = "win32")]
     pub fn get_foreground_window() -> Option<u64> {
-        unsafe {
-            let hwnd = GetForegroundWindow();
-            if hwnd.is_null() {
-                None
-            } else {
-                Some(hwnd as u64)
-            }
-        }
+        Win32Backend::new().get_foreground_window()
     }
 
-    pub fn move_window_to_rect(&self, hwnd: u64, rect: RECT) -> Result<(), String> {
-        unsafe {
-            use winapi::um::winuser::{SetWindowPos, SWP_NOACTIVATE, SWP_NOZORDER};
-            let result = SetWindowPos(
-                hwnd as winapi::shared::windef::HWND,
-                std::ptr::null_mut(),
-                rect.left,
-                rect.top,
-                rect.right - rect.left,
-                rect.bottom - rect.top,
-                SWP_NOZORDER | SWP_NOACTIVATE,
-            );
-            if result == 0 {
-                Err(format!("SetWindowPos failed for 0x{:X}", hwnd))
-            } else {
-                Ok(())
-            }
-        }
+    pub fn move_window_to_rect(&self, hwnd: u64, rect: impl Into<Rect>) -> Result<(), String> {
+        self.backend.move_window_to_rect(hwnd, rect.into())
     }
 
+    #[cfg(feature = "win32")]
     pub fn new() -> Self {
         let mut ret = Self::new_with_config(GridConfig::default());
         ret.find_desktop_hwnds();
@@ -97,16 +107,18 @@ impl WindowTracker {
         ret.event_dispatch_mode = crate::EventDispatchMode::AutoTrack; // default
         ret
     }
-    /// Returns the current grid state in IPC protocol format (GridState)
+    /// Returns the current grid state in IPC protocol format (GridState). Only the
+    /// top-left 32x32 cells fit; see `get_ipc_cell_runs` for the full grid.
+    #[cfg(feature = "ipc")]
     pub fn get_ipc_grid_state(&self) -> crate::ipc_protocol::GridState {
         crate::ipc_protocol::GridState {
             rows: self.config.rows as u32,
             cols: self.config.cols as u32,
             grid: {
                 let mut arr = [[0u64; 32]; 32];
-                for (i, row) in self.grid.iter().enumerate().take(self.config.rows) {
-                    for (j, cell) in row.iter().enumerate().take(self.config.cols) {
-                        arr[i][j] = match cell {
+                for (arr_row, row) in arr.iter_mut().zip(&self.grid) {
+                    for (value, cell) in arr_row.iter_mut().zip(row) {
+                        *value = match cell {
                             CellState::Occupied(hwnd) => *hwnd,
                             _ => 0,
                         };
@@ -114,37 +126,106 @@ impl WindowTracker {
                 }
                 arr
             },
+            groups: {
+                let memberships = self.group_memberships();
+                let mut arr = [[0u32; 32]; 32];
+                for (arr_row, row) in arr.iter_mut().zip(&self.grid) {
+                    for (value, cell) in arr_row.iter_mut().zip(row) {
+                        if let CellState::Occupied(hwnd) = cell {
+                            *value = memberships.get(hwnd).map_or(0, |m| m.group_id);
+                        }
+                    }
+                }
+                arr
+            },
+        }
+    }
+
+    /// The virtual grid (monitor id 999) and every monitor grid, in full, as
+    /// chunked cell runs for IPC.
+    #[cfg(feature = "ipc")]
+    pub fn get_ipc_cell_runs(
+        &self,
+        sequence: u64,
+    ) -> Vec<crate::ipc_protocol::GridCellRunsMessage> {
+        use crate::ipc_protocol::GridCellRunsMessage;
+        let mut messages = GridCellRunsMessage::encode(999, sequence, &Self::ipc_cells(&self.grid));
+        for monitor_grid in &self.monitor_grids {
+            messages.extend(GridCellRunsMessage::encode(
+                monitor_grid.monitor_id as u32,
+                sequence,
+                &Self::ipc_cells(&monitor_grid.grid),
+            ));
         }
+        messages
+    }
+
+    /// Cell states as IPC cell values: the window handle, 0 if empty, `u64::MAX` if off-screen.
+    #[cfg(feature = "ipc")]
+    pub fn ipc_cells(grid: &[Vec<CellState>]) -> Vec<Vec<u64>> {
+        grid.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        CellState::Occupied(hwnd) => *hwnd,
+                        CellState::Empty => 0,
+                        CellState::OffScreen => u64::MAX,
+                    })
+                    .collect()
+            })
+            .collect()
     }
 
     /// Returns the current window list in IPC protocol format (Vec<crate::grid::WindowInfo>)
     pub fn get_ipc_window_list(&self) -> Vec<crate::grid::WindowInfo> {
-        self.windows
-            .iter()
-            .map(|entry| entry.value().clone())
-            .collect()
+        self.windows.iter().map(|entry| *entry.value()).collect()
     }
+    #[cfg(feature = "win32")]
     pub fn new_with_config(config: GridConfig) -> Self {
+        Self::new_with_backend(config, Arc::new(Win32Backend::new()))
+    }
+
+    /// Create a tracker on top of any window backend (e.g. a `SimulatedDesktop`).
+    pub fn new_with_backend(config: GridConfig, backend: Arc<dyn WindowBackend>) -> Self {
         // Get the virtual screen dimensions (all monitors combined)
-        let rect = unsafe {
-            RECT {
-                left: GetSystemMetrics(SM_XVIRTUALSCREEN),
-                top: GetSystemMetrics(SM_YVIRTUALSCREEN),
-                right: GetSystemMetrics(SM_XVIRTUALSCREEN) + GetSystemMetrics(SM_CXVIRTUALSCREEN),
-                bottom: GetSystemMetrics(SM_YVIRTUALSCREEN) + GetSystemMetrics(SM_CYVIRTUALSCREEN),
-            }
-        };
+        let rect = backend.get_virtual_screen_rect();
 
         let grid = vec![vec![CellState::Empty; config.cols]; config.rows];
         let mut tracker = Self {
             windows: DashMap::new(),
             monitor_rect: rect,
+            virtual_config: config.to_physical(1.0),
             config: config.clone(),
             event_dispatch_mode: crate::EventDispatchMode::AutoTrack, // default
             grid,
             monitor_grids: Vec::new(),
+            monitor_grid_areas: HashMap::new(),
+            monitor_occupancy: HashMap::new(),
+            monitor_zone_layouts: HashMap::new(),
+            monitor_subgrids: HashMap::new(),
+            snap_config: SnapConfig::default(),
+            monitor_tilings: HashMap::new(),
+            tiled_arrangements: HashMap::new(),
+            floating_windows: HashSet::new(),
+            window_rules: WindowRules::default(),
+            ignored_windows: HashSet::new(),
+            workspaces: BTreeMap::new(),
+            active_workspaces: HashMap::new(),
+            workspace_hide_mode: WorkspaceHideMode::default(),
+            scratchpads: Scratchpads::default(),
+            scratchpad_windows: HashMap::new(),
+            shown_scratchpads: HashSet::new(),
+            window_groups: BTreeMap::new(),
+            window_group_counter: 0,
+            #[cfg(feature = "animation")]
+            window_easings: HashMap::new(),
             enum_counter: AtomicUsize::new(0),
+            #[cfg(feature = "animation")]
             active_animations: DashMap::new(),
+            #[cfg(feature = "animation")]
+            animation_batches: Vec::new(),
+            #[cfg(feature = "animation")]
+            animation_batch_counter: 0,
             saved_layouts: DashMap::new(),
             event_callbacks: Vec::new(),
             desktop_hwnds: Vec::new(),
@@ -152,39 +233,17 @@ impl WindowTracker {
             last_scan_time: std::sync::Mutex::new(
                 std::time::Instant::now() - std::time::Duration::from_secs(2),
             ),
+            backend,
         };
 
-        // Initialize individual monitor grids
+        // Mark dead space between monitors, then initialize individual monitor grids
+        tracker.initialize_grid();
         tracker.initialize_monitor_grids();
         tracker
     }
 
     pub fn find_desktop_hwnds(&mut self) {
-        use std::ffi::OsString;
-        use std::os::windows::ffi::OsStringExt;
-        use winapi::um::winuser::{EnumWindows, GetClassNameW};
-        self.desktop_hwnds.clear();
-        unsafe {
-            extern "system" fn enum_proc(hwnd: HWND, lparam: LPARAM) -> i32 {
-                let desktop_hwnds = unsafe { &mut *(lparam as *mut Vec<u64>) };
-                let mut class_buf = [0u16; 256];
-                let len = unsafe { GetClassNameW(hwnd, class_buf.as_mut_ptr(), 256) };
-                if len > 0 {
-                    let class = OsString::from_wide(&class_buf[..len as usize])
-                        .to_string_lossy()
-                        .to_string();
-                    if class == "Progman"
-                        || class == "WorkerW"
-                        || class == "ApplicationFrameWindow"
-                        || class == "Windows.UI.Core.CoreWindow"
-                    {
-                        desktop_hwnds.push(hwnd as u64);
-                    }
-                }
-                1
-            }
-            EnumWindows(Some(enum_proc), &mut self.desktop_hwnds as *mut _ as LPARAM);
-        }
+        self.desktop_hwnds = self.backend.find_desktop_hwnds();
     }
 
     pub fn is_desktop_hwnd(&self, hwnd: u64) -> bool {
@@ -192,11 +251,10 @@ impl WindowTracker {
     }
     pub fn initialize_monitor_grids(&mut self) {
         self.monitor_grids.clear();
-        let monitors = self.get_actual_monitor_bounds();
+        let monitors = self.backend.get_monitors();
 
-        for (index, monitor_rect) in monitors.iter().enumerate() {
-            let monitor_grid =
-                MonitorGrid::new_with_config(index, *monitor_rect, self.config.clone());
+        for (index, monitor) in monitors.iter().enumerate() {
+            let monitor_grid = self.build_monitor_grid(index, monitor);
             self.monitor_grids.push(monitor_grid);
         }
 
@@ -206,6 +264,176 @@ impl WindowTracker {
         );
     }
 
+    fn build_monitor_grid(&self, index: usize, monitor: &MonitorAreas) -> MonitorGrid {
+        let grid_area = self
+            .monitor_grid_areas
+            .get(&index)
+            .copied()
+            .unwrap_or_default();
+        let mut config = self.config.clone();
+        if let Some(occupancy) = self.monitor_occupancy.get(&index) {
+            config.occupancy = *occupancy;
+        }
+        if let Some(subgrids) = self.monitor_subgrids.get(&index) {
+            config.subgrids = subgrids.clone();
+        }
+        MonitorGrid::new_with_config(index, monitor.bounds, config)
+            .with_work_area(monitor.work_area)
+            .with_grid_area(grid_area)
+            .with_dpi(monitor.dpi)
+            .with_zone_layout(self.monitor_zone_layouts.get(&index).cloned())
+    }
+
+    /// Choose whether monitor `monitor_id`'s grid covers its full bounds or only its work area.
+    pub fn set_monitor_grid_area(
+        &mut self,
+        monitor_id: usize,
+        grid_area: GridArea,
+    ) -> Result<(), String> {
+        let Some(monitor_grid) = self.monitor_grids.get_mut(monitor_id) else {
+            return Err(format!("Monitor {} does not exist", monitor_id));
+        };
+        self.monitor_grid_areas.insert(monitor_id, grid_area);
+        monitor_grid.grid_area = grid_area;
+        monitor_grid.update_grid_for_monitor(&self.windows);
+        Ok(())
+    }
+
+    /// Give monitor `monitor_id` a set of named zones, tracked alongside its grid cells,
+    /// or remove them with `None`.
+    pub fn set_monitor_zone_layout(
+        &mut self,
+        monitor_id: usize,
+        zones: Option<ZoneLayout>,
+    ) -> Result<(), String> {
+        let Some(monitor_grid) = self.monitor_grids.get_mut(monitor_id) else {
+            return Err(format!("Monitor {} does not exist", monitor_id));
+        };
+        match &zones {
+            Some(layout) => {
+                layout.validate()?;
+                self.monitor_zone_layouts.insert(monitor_id, layout.clone());
+            }
+            None => {
+                self.monitor_zone_layouts.remove(&monitor_id);
+            }
+        }
+        *monitor_grid = monitor_grid.clone().with_zone_layout(zones);
+        monitor_grid.update_grid_for_monitor(&self.windows);
+        Ok(())
+    }
+
+    /// Split the cell at `path` on monitor `monitor_id` into `grid`, or with `None` merge
+    /// it back into a single cell. Paths may reach into existing sub-grids, e.g. to split
+    /// a sub-cell again. Splits that `validate_grid_config` rejects leave the grid
+    /// unchanged.
+    pub fn subdivide_cell(
+        &mut self,
+        monitor_id: usize,
+        path: &CellPath,
+        grid: Option<GridConfig>,
+    ) -> Result<(), String> {
+        let Some(monitor_grid) = self.monitor_grids.get(monitor_id) else {
+            return Err(format!("Monitor {} does not exist", monitor_id));
+        };
+        // Edit the unscaled config; the monitor grid is rebuilt for its DPI below
+        let mut config = self.config.clone();
+        config.subgrids = self
+            .monitor_subgrids
+            .get(&monitor_id)
+            .cloned()
+            .unwrap_or_default();
+        config.subdivide(path, grid)?;
+        // Refuse grids past the limits before committing them
+        crate::config::validate_grid_config(&config)?;
+        self.monitor_subgrids.insert(monitor_id, config.subgrids);
+
+        let monitor = MonitorAreas {
+            bounds: monitor_grid.monitor_rect,
+            work_area: monitor_grid.work_area,
+            dpi: monitor_grid.dpi,
+        };
+        let mut monitor_grid = self.build_monitor_grid(monitor_id, &monitor);
+        monitor_grid.update_grid_for_monitor(&self.windows);
+        self.monitor_grids[monitor_id] = monitor_grid;
+        Ok(())
+    }
+
+    /// Change the rule deciding which cells a window occupies, for monitor `monitor_id`
+    /// or, with `None`, for the virtual grid and every monitor without an override.
+    pub fn set_occupancy_strategy(
+        &mut self,
+        monitor_id: Option<usize>,
+        strategy: CellOccupancyStrategy,
+    ) -> Result<(), String> {
+        match monitor_id {
+            Some(monitor_id) => {
+                let Some(monitor_grid) = self.monitor_grids.get_mut(monitor_id) else {
+                    return Err(format!("Monitor {} does not exist", monitor_id));
+                };
+                self.monitor_occupancy.insert(monitor_id, strategy);
+                monitor_grid.config.occupancy = strategy;
+                monitor_grid.update_grid_for_monitor(&self.windows);
+            }
+            None => {
+                self.config.occupancy = strategy;
+                self.virtual_config.occupancy = strategy;
+                for monitor_grid in &mut self.monitor_grids {
+                    if !self
+                        .monitor_occupancy
+                        .contains_key(&monitor_grid.monitor_id)
+                    {
+                        monitor_grid.config.occupancy = strategy;
+                        monitor_grid.update_grid_for_monitor(&self.windows);
+                    }
+                }
+                // Bypass the update throttle so the new rule shows up immediately
+                if let Ok(mut last) = self.last_scan_time.lock() {
+                    *last = std::time::Instant::now() - std::time::Duration::from_secs(2);
+                }
+                self.update_grid();
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-read monitor bounds, work areas and DPI from the backend and rebuild the grid
+    /// of every monitor that changed, e.g. after a taskbar or app bar appeared, moved or
+    /// resized. Returns the ids of the rebuilt monitor grids.
+    pub fn refresh_monitor_areas(&mut self) -> Vec<usize> {
+        let monitors = self.backend.get_monitors();
+        if monitors.len() != self.monitor_grids.len() {
+            // Monitors were added or removed; every grid may have moved
+            self.monitor_rect = self.backend.get_virtual_screen_rect();
+            self.initialize_grid();
+            self.update_grid();
+            self.initialize_monitor_grids();
+            self.update_monitor_grids();
+            return (0..self.monitor_grids.len()).collect();
+        }
+
+        let mut changed = Vec::new();
+        for (index, monitor) in monitors.iter().enumerate() {
+            let current = &self.monitor_grids[index];
+            if current.monitor_rect == monitor.bounds
+                && current.work_area == monitor.work_area
+                && current.dpi == monitor.dpi
+            {
+                continue;
+            }
+            let mut monitor_grid = self.build_monitor_grid(index, monitor);
+            monitor_grid.update_grid_for_monitor(&self.windows);
+            self.monitor_grids[index] = monitor_grid;
+            changed.push(index);
+        }
+        if !changed.is_empty() {
+            self.monitor_rect = self.backend.get_virtual_screen_rect();
+            self.initialize_grid();
+            self.update_grid();
+        }
+        changed
+    }
+
     // Event callback management
     pub fn register_event_callback(&mut self, callback: WindowEventCallbackBox) {
         self.event_callbacks.push(callback);
@@ -234,225 +462,64 @@ impl WindowTracker {
         }
     }
 
+    #[allow(dead_code)]
     fn trigger_window_activated(&self, hwnd: u64, window_info: &WindowInfo) {
         for callback in &self.event_callbacks {
             callback.on_window_activated(hwnd, window_info);
         }
     }
 
+    #[allow(dead_code)]
     fn trigger_window_minimized(&self, hwnd: u64) {
         for callback in &self.event_callbacks {
             callback.on_window_minimized(hwnd);
         }
     }
 
+    #[allow(dead_code)]
     fn trigger_window_restored(&self, hwnd: u64, window_info: &WindowInfo) {
         for callback in &self.event_callbacks {
             callback.on_window_restored(hwnd, window_info);
         }
     }
 
+    #[cfg(feature = "win32")]
     pub fn get_window_class(hwnd: u64) -> String {
-        unsafe {
-            let mut buffer = [0u16; 256];
-            let len = GetClassNameW(hwnd as HWND, buffer.as_mut_ptr(), buffer.len() as i32);
-            if len > 0 {
-                String::from_utf16_lossy(&buffer[..len as usize])
-                    .chars()
-                    .take(50)
-                    .collect()
-            } else {
-                String::new()
-            }
-        }
+        Win32Backend::new()
+            .get_window_class_name(hwnd)
+            .chars()
+            .take(50)
+            .collect()
     }
 
+    #[cfg(feature = "win32")]
     pub fn get_window_title(hwnd: u64) -> String {
-        unsafe {
-            let mut buffer = [0u16; 256];
-            let len = GetWindowTextW(hwnd as HWND, buffer.as_mut_ptr(), buffer.len() as i32);
-            if len > 0 {
-                String::from_utf16_lossy(&buffer[..len as usize])
-                    .chars()
-                    .take(50)
-                    .collect()
-            } else {
-                String::new()
-            }
-        }
+        Win32Backend::new()
+            .get_window_title(hwnd)
+            .chars()
+            .take(50)
+            .collect()
     }
 
+    #[cfg(feature = "win32")]
     pub fn get_window_rect(hwnd: u64) -> Option<RECT> {
-        unsafe {
-            let mut rect = RECT {
-                left: 0,
-                top: 0,
-                right: 0,
-                bottom: 0,
-            };
-            if GetWindowRect(hwnd as HWND, &mut rect) != 0 {
-                Some(rect)
-            } else {
-                None
-            }
-        }
+        Win32Backend::new().get_window_rect(hwnd).map(RECT::from)
     }
 
+    /// Whether the default window filter accepts `hwnd`; a tracker's own filter lives on
+    /// its backend.
+    #[cfg(feature = "win32")]
     pub fn is_manageable_window(hwnd: u64) -> bool {
-        unsafe {
-            // let title = Self::get_window_title(hwnd);
-            if IsWindow(hwnd as HWND) == 0 {
-                //println!("[DEBUG] Skipping hwnd=0x{:X}: not a valid window", hwnd);
-                return false;
-            }
-            if IsWindowVisible(hwnd as HWND) == 0 {
-                //println!("[DEBUG] Skipping hwnd=0x{:X}: not visible", hwnd);
-                return false;
-            }
-            // Skip minimized windows
-            if IsIconic(hwnd as HWND) != 0 {
-                //println!("[DEBUG] Skipping hwnd=0x{:X}: minimized", hwnd);
-                return false;
-            }
-
-            let ex_style = GetWindowLongW(hwnd as HWND, GWL_EXSTYLE) as u32;
-            if (ex_style & WS_EX_TOOLWINDOW) != 0 {
-                //println!("[DEBUG] Skipping hwnd=0x{:X}: toolwindow", hwnd);
-                return false;
-            }
-            let style = GetWindowLongW(hwnd as HWND, GWL_STYLE) as u32;
-            if (style & WS_CHILD) != 0 {
-                //println!("[DEBUG] Skipping hwnd=0x{:X}: is a child window", hwnd);
-                return false;
-            }
-            // Exclude "Windows.UI.Composition.DesktopWindowContentBridge" windows
-            let class_name = Self::get_window_class_name(hwnd);
-            if class_name.is_empty()
-                || class_name == "Windows.UI.Composition.DesktopWindowContentBridge"
-                || class_name == "Windows.UI.Core.CoreWindow"
-                || class_name == "XamlExplorerHostIslandWindow"
-            {
-                return false;
-            }
-            // Exclude Chrome_WidgetWin_1 windows with no border (WS_BORDER not set)
-            if class_name == "Chrome_WidgetWin_1" {
-                let style = GetWindowLongW(hwnd as HWND, GWL_STYLE) as u32;
-                if (style & WS_BORDER) == 0 {
-                    return false; //tooltip!
-                }
-            }
-            //println!("[DEBUG] Accepting hwnd=0x{:X}: '{}'", hwnd, title);
-            true
-        }
+        Win32Backend::new().is_manageable_window(hwnd)
     }
 
-    /// Given a window RECT, return the bounding grid rectangle as UsizeRect (start_row, start_col, end_row, end_col)
-    pub fn window_to_grid_rect(&self, rect: &RECT) -> crate::window::info::UsizeRect {
-        // Skip invalid rectangles
-        if rect.right <= rect.left || rect.bottom <= rect.top {
-            return crate::window::info::UsizeRect {
-                left: 0,
-                top: 0,
-                right: 0,
-                bottom: 0,
-            };
-        }
-
-        let cell_width =
-            (self.monitor_rect.right - self.monitor_rect.left) / self.config.cols as i32;
-        let cell_height =
-            (self.monitor_rect.bottom - self.monitor_rect.top) / self.config.rows as i32;
-
-        if cell_width <= 0 || cell_height <= 0 {
-            return crate::window::info::UsizeRect {
-                left: 0,
-                top: 0,
-                right: 0,
-                bottom: 0,
-            };
-        }
-
-        let start_col = ((rect.left - self.monitor_rect.left) / cell_width).max(0) as usize;
-        let end_col = (((rect.right - self.monitor_rect.left - 1) / cell_width)
-            .min(self.config.cols as i32 - 1)
-            .max(0)) as usize;
-        let start_row = ((rect.top - self.monitor_rect.top) / cell_height).max(0) as usize;
-        let end_row = ((rect.bottom - self.monitor_rect.top - 1) / cell_height)
-            .min(self.config.rows as i32 - 1)
-            .max(0) as usize;
-
-        if start_row > end_row
-            || start_col > end_col
-            || start_row >= self.config.rows
-            || end_row >= self.config.rows
-        {
-            return crate::window::info::UsizeRect {
-                left: 0,
-                top: 0,
-                right: 0,
-                bottom: 0,
-            };
-        }
-
-        return crate::window::info::UsizeRect {
-            left: start_col,
-            top: start_row,
-            right: end_col,
-            bottom: end_row,
-        };
+    /// Given a window rect, return the bounding grid rectangle as UsizeRect (start_row, start_col, end_row, end_col)
+    pub fn window_to_grid_rect(&self, rect: &Rect) -> crate::window::info::UsizeRect {
+        crate::grid::math::window_to_grid_rect(&self.monitor_rect, &self.virtual_config, rect)
     }
 
-    pub fn window_to_grid_cells(&self, rect: &RECT) -> Vec<(usize, usize)> {
-        let mut cells = Vec::new();
-
-        // Skip windows with invalid rectangle (right must be > left, bottom > top)
-        if rect.right < rect.left || rect.bottom < rect.top {
-            return cells;
-        }
-
-        let cell_width =
-            (self.monitor_rect.right - self.monitor_rect.left) / self.config.cols as i32;
-        let cell_height =
-            (self.monitor_rect.bottom - self.monitor_rect.top) / self.config.rows as i32;
-
-        if cell_width <= 0 || cell_height <= 0 {
-            return cells;
-        }
-
-        // Calculate potential range of cells that might be affected
-        let start_col = ((rect.left - self.monitor_rect.left) / cell_width).max(0) as usize;
-        let end_col = ((rect.right - self.monitor_rect.left) / cell_width)
-            .min(self.config.cols as i32 - 1) as usize;
-        let start_row = ((rect.top - self.monitor_rect.top) / cell_height).max(0) as usize;
-        let end_row = ((rect.bottom - self.monitor_rect.top) / cell_height)
-            .min(self.config.rows as i32 - 1) as usize;
-
-        // Additional bounds checking
-        if start_col >= self.config.cols || start_row >= self.config.rows {
-            return cells;
-        }
-
-        // Check coverage for each potentially affected cell
-        for row in start_row..=end_row {
-            for col in start_col..=end_col {
-                if row < self.config.rows && col < self.config.cols {
-                    // Calculate the exact bounds of this grid cell
-                    let cell_rect = RECT {
-                        left: self.monitor_rect.left + (col as i32 * cell_width),
-                        top: self.monitor_rect.top + (row as i32 * cell_height),
-                        right: self.monitor_rect.left + ((col + 1) as i32 * cell_width),
-                        bottom: self.monitor_rect.top + ((row + 1) as i32 * cell_height),
-                    };
-                    // Only include cell if window meets coverage threshold
-                    if crate::util::meets_coverage_threshold(rect, &cell_rect) {
-                        // Push all cells from start to end (full rectangle coverage)
-                        cells.push((row, col));
-                    }
-                }
-            }
-        }
-
-        cells
+    pub fn window_to_grid_cells(&self, rect: &Rect) -> Vec<(usize, usize)> {
+        crate::grid::math::window_to_grid_cells(&self.monitor_rect, &self.virtual_config, rect)
     }
 
     pub fn update_grid(&mut self) {
@@ -482,55 +549,44 @@ impl WindowTracker {
                 }
             }
         }
-        // Place windows on the grid (only once)
-        for entry in &self.windows {
-            let (hwnd, window_info) = entry.pair();
-            // if (*hwnd & 0xFF) == 0x2E {
-            //     let title =
-            //         String::from_utf16_lossy(&window_info.title[..window_info.title_len as usize]);
-            //     let class_name = String::from_utf16_lossy(
-            //         &window_info.class_name[..window_info.class_name_len as usize],
-            //     );
-            //     println!(
-            //         "[DEBUG] HWND ending in 2E: hwnd=0x{:X}, title='{}', class='{}'",
-            //         hwnd, title, class_name
-            //     );
-            // }
+        // Place windows on the grid, bottommost first so the topmost window wins shared cells
+        let z_order = self.backend.get_hwnd_z_order_map();
+        let mut windows: Vec<(u64, Rect)> = self
+            .windows
+            .iter()
+            .map(|entry| *entry.key())
             // Skip desktop windows for occupancy
-            if self.is_desktop_hwnd(*hwnd) {
-                continue;
-            }
-            // Compute the grid cells this window occupies based on its geometry
-            if let Some(rect) = Self::get_window_rect(*hwnd) {
-                let cells = self.window_to_grid_cells(&rect);
-                for (row, col) in cells {
-                    if row < self.config.rows && col < self.config.cols {
-                        match self.grid[row][col] {
-                            CellState::Occupied(existing_hwnd) if existing_hwnd == *hwnd => {
-                                // Already occupied by this hwnd, skip
-                            }
-                            _ => {
-                                self.grid[row][col] = CellState::Occupied(*hwnd);
-                            }
-                        }
+            .filter(|hwnd| !self.is_desktop_hwnd(*hwnd))
+            .filter_map(|hwnd| Some((hwnd, self.backend.get_window_rect(hwnd)?)))
+            .collect();
+        windows.sort_by_key(|(hwnd, _)| {
+            std::cmp::Reverse(z_order.get(hwnd).copied().unwrap_or(usize::MAX))
+        });
+
+        let owners = crate::grid::math::assign_cell_owners(
+            &self.monitor_rect,
+            &self.virtual_config,
+            &windows,
+        );
+        for (row, row_owners) in owners.iter().enumerate() {
+            for (col, owner) in row_owners.iter().enumerate() {
+                if let Some(hwnd) = owner {
+                    // A window hanging into dead space doesn't make it usable
+                    if self.grid[row][col] != CellState::OffScreen {
+                        self.grid[row][col] = CellState::Occupied(*hwnd);
                     }
                 }
             }
         }
     }
 
-    /// Get the primary monitor rectangle for window positioning
-    fn get_primary_monitor_rect(&self) -> RECT {
+    /// Get the primary monitor's grid rectangle (bounds or work area) for window positioning
+    fn get_primary_monitor_rect(&self) -> Rect {
         if !self.monitor_grids.is_empty() {
             // Find the monitor at (0,0) - this is the true primary monitor
             for monitor_grid in &self.monitor_grids {
                 if monitor_grid.monitor_rect.left == 0 && monitor_grid.monitor_rect.top == 0 {
-                    let rect = RECT {
-                        left: monitor_grid.monitor_rect.left,
-                        top: monitor_grid.monitor_rect.top,
-                        right: monitor_grid.monitor_rect.right,
-                        bottom: monitor_grid.monitor_rect.bottom,
-                    };
+                    let rect = monitor_grid.grid_rect();
                     println!(
                         "🖥️  Using true primary monitor at (0,0): ({}, {}) to ({}, {})",
                         rect.left, rect.top, rect.right, rect.bottom
@@ -541,12 +597,7 @@ impl WindowTracker {
 
             // Fallback to first monitor if no monitor at (0,0) found
             let primary_monitor = &self.monitor_grids[0];
-            let rect = RECT {
-                left: primary_monitor.monitor_rect.left,
-                top: primary_monitor.monitor_rect.top,
-                right: primary_monitor.monitor_rect.right,
-                bottom: primary_monitor.monitor_rect.bottom,
-            };
+            let rect = primary_monitor.grid_rect();
             println!(
                 "🖥️  Fallback to first monitor: ({}, {}) to ({}, {})",
                 rect.left, rect.top, rect.right, rect.bottom
@@ -565,8 +616,17 @@ impl WindowTracker {
         }
     }
 
+    fn primary_monitor_config(&self) -> GridConfig {
+        self.monitor_grids
+            .iter()
+            .find(|m| m.monitor_rect.left == 0 && m.monitor_rect.top == 0)
+            .or(self.monitor_grids.first())
+            .map(|m| m.config.clone())
+            .unwrap_or_else(|| self.virtual_config.clone())
+    }
+
     /// Convert grid cell to window rectangle on primary monitor
-    pub fn primary_monitor_cell_to_rect(&self, row: usize, col: usize) -> Option<RECT> {
+    pub fn primary_monitor_cell_to_rect(&self, row: usize, col: usize) -> Option<Rect> {
         if row >= self.config.rows || col >= self.config.cols {
             println!(
                 "❌ Invalid cell coordinates: ({}, {}) for grid ({}x{})",
@@ -576,11 +636,12 @@ impl WindowTracker {
         }
 
         let monitor_rect = self.get_primary_monitor_rect();
-        let grid_width = monitor_rect.right - monitor_rect.left;
-        let grid_height = monitor_rect.bottom - monitor_rect.top;
+        let grid_width = monitor_rect.width();
+        let grid_height = monitor_rect.height();
+        // The primary monitor's grid has logical units already scaled to its DPI
+        let config = self.primary_monitor_config();
 
-        let cell_width = grid_width / self.config.cols as i32;
-        let cell_height = grid_height / self.config.rows as i32;
+        let (cell_width, cell_height) = crate::grid::math::cell_size(&monitor_rect, &config);
 
         println!("🧮 Cell calculation for ({}, {}):", row, col);
         println!(
@@ -593,22 +654,14 @@ impl WindowTracker {
         );
         println!("   Cell size: {}x{}", cell_width, cell_height);
 
-        let left = monitor_rect.left + (col as i32 * cell_width);
-        let top = monitor_rect.top + (row as i32 * cell_height);
-        let right = left + cell_width;
-        let bottom = top + cell_height;
+        let rect = crate::grid::math::cell_to_rect(&monitor_rect, &config, row, col)?;
 
         println!(
             "   Calculated cell rect: ({}, {}) to ({}, {})",
-            left, top, right, bottom
+            rect.left, rect.top, rect.right, rect.bottom
         );
 
-        Some(RECT {
-            left,
-            top,
-            right,
-            bottom,
-        })
+        Some(rect)
     }
 
     pub fn add_window(&mut self, hwnd: u64) -> bool {
@@ -616,104 +669,28 @@ impl WindowTracker {
             println!("[DEBUG] Skipping hwnd=0x{:X}: is desktop window", hwnd);
             return false;
         }
-        if let Some(rect) = Self::get_window_rect(hwnd) {
-            let mut title_buf = [0u16; 256];
-            let title_len = unsafe {
-                GetWindowTextW(hwnd as HWND, title_buf.as_mut_ptr(), title_buf.len() as i32)
-            };
-            let title = if title_len > 0 {
-                String::from_utf16_lossy(&title_buf[..title_len as usize])
-            } else {
-                String::new()
-            };
-            let grid_cells = self.window_to_grid_cells(&rect);
+        if self.ignored_windows.contains(&hwnd) {
+            return false;
+        }
+        if let Some(mut window_info) = self.backend.get_window_info(hwnd) {
+            let rect = window_info.window_rect.0;
             let monitor_cells = self.calculate_monitor_cells(&rect);
 
-            // Get additional required fields for WindowInfo
-            let is_maximized = WindowTracker::is_window_maximized(hwnd);
-            let is_visible = unsafe { IsWindowVisible(hwnd as HWND) != 0 };
-            let is_minimized = unsafe { IsIconic(hwnd as HWND) != 0 };
-            let mut process_id: u32 = 0;
-            unsafe {
-                GetWindowThreadProcessId(hwnd as HWND, &mut process_id);
-            }
-            let mut class_name_buf = [0u16; 256];
-            let class_name_len = unsafe {
-                GetClassNameW(
-                    hwnd as HWND,
-                    class_name_buf.as_mut_ptr(),
-                    class_name_buf.len() as i32,
-                )
-            };
-            let class_name = if class_name_len > 0 {
-                String::from_utf16_lossy(&class_name_buf[..class_name_len as usize])
-            } else {
-                String::new()
-            };
-            // println!(
-            //     "[DEBUG] Adding window: hwnd=0x{:X}, title='{}', class='{}', rect=({}, {}, {}, {})",
-            //     hwnd, title, class_name, rect.left, rect.top, rect.right, rect.bottom
-            // );
-
-            let window_info = WindowInfo {
-                hwnd,
-                title: title_buf,
-                title_len: title_len as u32,
-                // grid_cells: {
-                //     let mut arr = [(0, 0); crate::MAX_WINDOW_GRID_CELLS];
-                //     for (i, cell) in grid_cells
-                //         .iter()
-                //         .enumerate()
-                //         .take(crate::MAX_WINDOW_GRID_CELLS)
-                //     {
-                //         arr[i] = *cell;
-                //     }
-                //     arr
-                // },
-                // grid_cells_len: grid_cells.len() as u32,
-                monitor_ids: {
-                    let mut arr = [0usize; 8];
-                    for (i, id) in monitor_cells.keys().cloned().enumerate().take(8) {
-                        arr[i] = id;
-                    }
-                    arr
-                },
-                // monitor_cells: {
-                //     let mut arr = [[(0, 0); 8]; 8];
-                //     for (monitor_id, cells) in monitor_cells.iter() {
-                //         for (i, cell) in cells.iter().enumerate().take(8) {
-                //             if *monitor_id < 8 {
-                //                 arr[*monitor_id][i] = *cell;
-                //             }
-                //         }
-                //     }
-                //     arr
-                // },
-                // monitor_cells_lens: {
-                //     let mut arr = [0u32; 8];
-                //     for (monitor_id, cells) in monitor_cells.iter() {
-                //         if *monitor_id < 8 {
-                //             arr[*monitor_id] = cells.len() as u32;
-                //         }
-                //     }
-                //     arr
-                // },
-                // monitor_cells_len: monitor_cells.len() as u32,
-                z_order: crate::util::get_hwnd_z_order_map()
-                    .get(&hwnd)
-                    .copied()
-                    .unwrap_or(0) as u32,
-                window_rect: RectWrapper::from_rect(rect),
-                is_visible,
-                is_minimized,
-                is_maximized,
-                process_id,
-                class_name: class_name_buf,
-                class_name_len: class_name.len() as u32,
-                // grid_rect: self.window_to_grid_rect(&rect),
+            window_info.monitor_ids = {
+                let mut arr = [0usize; 8];
+                for (i, id) in monitor_cells.keys().cloned().enumerate().take(8) {
+                    arr[i] = id;
+                }
+                arr
             };
-
-            self.windows.insert(hwnd, window_info.clone());
+            window_info.z_order = self
+                .backend
+                .get_hwnd_z_order_map()
+                .get(&hwnd)
+                .copied()
+                .unwrap_or(0) as u32;
+
+            self.windows.insert(hwnd, window_info);
             self.update_grid();
             self.update_monitor_grids();
 
@@ -728,6 +705,9 @@ impl WindowTracker {
 
     pub fn remove_window(&mut self, hwnd: u64) -> bool {
         if self.windows.remove(&hwnd).is_some() {
+            self.floating_windows.remove(&hwnd);
+            #[cfg(feature = "animation")]
+            self.window_easings.remove(&hwnd);
             self.update_grid();
             self.update_monitor_grids();
 
@@ -740,15 +720,12 @@ impl WindowTracker {
     }
 
     pub fn update_window(&mut self, hwnd: u64) -> bool {
-        if let Some(rect) = Self::get_window_rect(hwnd) {
-            let grid_cells = self.window_to_grid_cells(&rect);
-            let monitor_cells = self.calculate_monitor_cells(&rect);
-
+        if let Some(rect) = self.backend.get_window_rect(hwnd) {
             // Update the window info
             let updated = if let Some(mut window_entry) = self.windows.get_mut(&hwnd) {
                 window_entry.window_rect = RectWrapper::from_rect(rect);
                 // window_entry.grid_rect = self.window_to_grid_rect(&rect);
-                window_entry.is_visible = unsafe { IsWindowVisible(hwnd as HWND) != 0 };
+                window_entry.is_visible = self.backend.is_window_visible(hwnd);
                 // let mut arr = [(0, 0); crate::MAX_WINDOW_GRID_CELLS];
                 // for (i, cell) in grid_cells
                 //     .iter()
@@ -778,7 +755,7 @@ impl WindowTracker {
 
                 // Get the updated window info for callback
                 if let Some(window_info) = self.windows.get(&hwnd) {
-                    self.trigger_window_moved(hwnd, &*window_info);
+                    self.trigger_window_moved(hwnd, &window_info);
                 }
 
                 return true;
@@ -822,14 +799,14 @@ impl WindowTracker {
                         let mut topmost_hwnd: Option<u64> = None;
                         let mut topmost_z: Option<usize> = None;
                         for entry in &self.windows {
-                            let (hwnd, window_info) = entry.pair();
+                            let (hwnd, _window_info) = entry.pair();
                             // For the virtual grid, use window_info.grid_cells
                             // Check if any monitor_cells contain (row, col)
                             if let CellState::Occupied(existing_hwnd) = self.grid[row][col] {
-                                let z_map = crate::util::get_hwnd_z_order_map();
+                                let z_map = self.backend.get_hwnd_z_order_map();
                                 if *hwnd == existing_hwnd {
                                     if let Some(&z) = z_map.get(hwnd) {
-                                        if topmost_z.map_or(true, |tz| z < tz) {
+                                        if topmost_z.is_none_or(|tz| z < tz) {
                                             topmost_hwnd = Some(*hwnd);
                                             topmost_z = Some(z);
                                         }
@@ -883,16 +860,15 @@ impl WindowTracker {
         println!();
         println!("=== VIRTUAL GRID (All Monitors Combined) ===");
         self.print_virtual_grid();
-        let z_map = crate::util::get_hwnd_z_order_map();
+        let z_map = self.backend.get_hwnd_z_order_map();
 
         // Find the foremost (topmost) window across all windows
         let mut foremost_hwnd: Option<u64> = None;
         let mut foremost_z: Option<usize> = None;
-        let mut fg_hwnd = None;
         for entry in &self.windows {
             let (hwnd, _) = entry.pair();
             if let Some(&z) = z_map.get(hwnd) {
-                if foremost_z.map_or(true, |fz| z < fz) {
+                if foremost_z.is_none_or(|fz| z < fz) {
                     foremost_hwnd = Some(*hwnd);
                     foremost_z = Some(z);
                 }
@@ -900,7 +876,7 @@ impl WindowTracker {
         }
 
         // Get the current foreground window and store its HWND
-        fg_hwnd = unsafe { GetForegroundWindow().as_ref() }.map(|hwnd| hwnd as *const _ as u64);
+        let fg_hwnd = self.backend.get_foreground_window();
 
         if let Some(fg_hwnd) = fg_hwnd {
             println!("Foreground window: \x1b[34m0x{:X}\x1b[0m", fg_hwnd);
@@ -920,17 +896,12 @@ impl WindowTracker {
             // Count windows on this monitor
             let mut windows_on_monitor = 0;
             for entry in &self.windows {
-                let (hwnd, window_info) = entry.pair();
+                let (_hwnd, window_info) = entry.pair();
 
                 // For this monitor, print grid cell info for each window
                 // We no longer use monitor_cells, so just print window info if it overlaps this monitor
                 let window_rect = window_info.window_rect.0;
-                let monitor_rect = RECT {
-                    left: monitor_grid.monitor_rect.left,
-                    top: monitor_grid.monitor_rect.top,
-                    right: monitor_grid.monitor_rect.right,
-                    bottom: monitor_grid.monitor_rect.bottom,
-                };
+                let monitor_rect = monitor_grid.monitor_rect;
                 // Check if window overlaps this monitor
                 let overlaps = window_rect.left < monitor_rect.right
                     && window_rect.right > monitor_rect.left
@@ -968,19 +939,14 @@ impl WindowTracker {
             // Print grid rows
             for row in 0..max_display_row {
                 print!("{:2} ", row);
-                for col in 0..monitor_grid.config.cols.min(32) {
+                for col in 0..monitor_grid.config.cols {
                     if row == 89 {
                         // Row 89: Display cell count for each window (number of cells it occupies)
                         let mut max_cell_count = 0;
                         for entry in &self.windows {
-                            let (hwnd, window_info) = entry.pair();
+                            let (_hwnd, window_info) = entry.pair();
                             let window_rect = window_info.window_rect.0;
-                            let monitor_rect = RECT {
-                                left: monitor_grid.monitor_rect.left,
-                                top: monitor_grid.monitor_rect.top,
-                                right: monitor_grid.monitor_rect.right,
-                                bottom: monitor_grid.monitor_rect.bottom,
-                            };
+                            let monitor_rect = monitor_grid.monitor_rect;
                             let overlaps = window_rect.left < monitor_rect.right
                                 && window_rect.right > monitor_rect.left
                                 && window_rect.top < monitor_rect.bottom
@@ -1004,7 +970,7 @@ impl WindowTracker {
                         // Row 90: Display fully spanning virtual grid
                         let mut has_spanning_window = false;
                         for entry in &self.windows {
-                            let (hwnd, window_info) = entry.pair();
+                            let (_hwnd, window_info) = entry.pair();
                             let window_rect = window_info.window_rect.0;
 
                             // Check if window spans multiple monitors or is maximized
@@ -1041,12 +1007,7 @@ impl WindowTracker {
                         for entry in &self.windows {
                             let (hwnd, window_info) = entry.pair();
                             let window_rect = window_info.window_rect.0;
-                            let monitor_rect = RECT {
-                                left: monitor_grid.monitor_rect.left,
-                                top: monitor_grid.monitor_rect.top,
-                                right: monitor_grid.monitor_rect.right,
-                                bottom: monitor_grid.monitor_rect.bottom,
-                            };
+                            let monitor_rect = monitor_grid.monitor_rect;
                             let overlaps = window_rect.left < monitor_rect.right
                                 && window_rect.right > monitor_rect.left
                                 && window_rect.top < monitor_rect.bottom
@@ -1087,12 +1048,7 @@ impl WindowTracker {
                             let window_rect = window_info.window_rect.0;
 
                             // Check if window overlaps this monitor
-                            let monitor_rect = RECT {
-                                left: monitor_grid.monitor_rect.left,
-                                top: monitor_grid.monitor_rect.top,
-                                right: monitor_grid.monitor_rect.right,
-                                bottom: monitor_grid.monitor_rect.bottom,
-                            };
+                            let monitor_rect = monitor_grid.monitor_rect;
                             let overlaps = window_rect.left < monitor_rect.right
                                 && window_rect.right > monitor_rect.left
                                 && window_rect.top < monitor_rect.bottom
@@ -1103,7 +1059,7 @@ impl WindowTracker {
                                 let cells = monitor_grid.window_to_grid_cells(&window_rect);
                                 if cells.contains(&(row, col)) {
                                     if let Some(&z) = z_map.get(hwnd) {
-                                        if topmost_z.map_or(true, |tz| z < tz) {
+                                        if topmost_z.is_none_or(|tz| z < tz) {
                                             topmost_hwnd = Some(*hwnd);
                                             topmost_z = Some(z);
                                         }
@@ -1178,9 +1134,12 @@ impl WindowTracker {
         //     *last = now;
         // }
         self.enum_counter.store(0, Ordering::SeqCst); // Reset counter
-        unsafe {
-            let result = EnumWindows(Some(enum_windows_proc), self as *mut _ as LPARAM);
-            println!("EnumWindows completed with result: {}", result);
+        let found_windows = self.backend.enumerate_windows();
+        let mut handles: Vec<u64> = found_windows.iter().map(|entry| *entry.key()).collect();
+        handles.sort_unstable();
+        for hwnd in handles {
+            self.enum_counter.fetch_add(1, Ordering::SeqCst);
+            self.add_window(hwnd);
         }
         println!(
             "Window enumeration finished. Found {} windows.",
@@ -1188,6 +1147,7 @@ impl WindowTracker {
         );
         self.update_grid();
         self.update_monitor_grids();
+        self.apply_window_rules_to_all();
     }
 
     pub fn get_monitor_info(&self) -> (i32, i32, i32, i32) {
@@ -1199,75 +1159,72 @@ impl WindowTracker {
         )
     }
 
+    /// Convert `config` to physical pixels for the virtual grid, at the primary
+    /// monitor's scale. Call after changing `config` directly; the tracker does so
+    /// itself when monitors or their DPI change.
+    pub fn update_virtual_config(&mut self) {
+        let monitors = self.backend.get_monitors();
+        let scale = monitors
+            .iter()
+            .find(|m| m.bounds.left == 0 && m.bounds.top == 0)
+            .or(monitors.first())
+            .map_or(1.0, |m| dpi_to_scale(m.dpi));
+        self.virtual_config = self.config.to_physical(scale);
+    }
+
     pub fn initialize_grid(&mut self) {
+        self.update_virtual_config();
         // Get actual monitor bounds (not virtual screen)
         let actual_monitors = self.get_actual_monitor_bounds();
-
-        let cell_width =
-            (self.monitor_rect.right - self.monitor_rect.left) / self.config.cols as i32;
-        let cell_height =
-            (self.monitor_rect.bottom - self.monitor_rect.top) / self.config.rows as i32;
+        let offscreen = crate::grid::math::offscreen_cells(
+            &self.monitor_rect,
+            &self.virtual_config,
+            &actual_monitors,
+        );
 
         // Initialize all cells based on whether they're on an actual monitor
-        for row in 0..self.config.rows {
-            for col in 0..self.config.cols {
-                let cell_left = self.monitor_rect.left + (col as i32 * cell_width);
-                let cell_top = self.monitor_rect.top + (row as i32 * cell_height);
-                let cell_right = cell_left + cell_width;
-                let cell_bottom = cell_top + cell_height;
-
-                // Check if this cell overlaps with any actual monitor
-                let mut is_on_screen = false;
-                for monitor_rect in &actual_monitors {
-                    if cell_left < monitor_rect.right
-                        && cell_right > monitor_rect.left
-                        && cell_top < monitor_rect.bottom
-                        && cell_bottom > monitor_rect.top
-                    {
-                        is_on_screen = true;
-                        break;
-                    }
-                }
-
-                self.grid[row][col] = if is_on_screen {
-                    CellState::Empty
-                } else {
+        for (row, cells) in offscreen.iter().enumerate() {
+            for (col, is_offscreen) in cells.iter().enumerate() {
+                self.grid[row][col] = if *is_offscreen {
                     CellState::OffScreen
+                } else {
+                    CellState::Empty
                 };
             }
         }
     }
 
-    fn get_actual_monitor_bounds(&self) -> Vec<RECT> {
-        let mut monitors = Vec::new();
-
-        unsafe {
-            // Enumerate all monitors
-            extern "system" fn monitor_enum_proc(
-                _hmonitor: winapi::shared::windef::HMONITOR,
-                _hdc: winapi::shared::windef::HDC,
-                rect: *mut RECT,
-                data: LPARAM,
-            ) -> i32 {
-                unsafe {
-                    let monitors = &mut *(data as *mut Vec<RECT>);
-                    monitors.push(*rect);
-                }
-                1 // Continue enumeration
-            }
-
-            EnumDisplayMonitors(
-                ptr::null_mut(),
-                ptr::null(),
-                Some(monitor_enum_proc),
-                &mut monitors as *mut Vec<RECT> as LPARAM,
+    /// Virtual cell a move to `(row, col)` should land in: the cell itself, or the
+    /// nearest cell on a monitor when it lies in the dead space between monitors.
+    /// Coordinates outside the grid come back unchanged for the caller to reject.
+    pub fn onscreen_virtual_cell(&self, row: usize, col: usize) -> Result<(usize, usize), String> {
+        let offscreen: Vec<Vec<bool>> = self
+            .grid
+            .iter()
+            .map(|cells| cells.iter().map(|c| *c == CellState::OffScreen).collect())
+            .collect();
+        let target = crate::grid::math::nearest_onscreen_cell(
+            &self.monitor_rect,
+            &self.virtual_config,
+            &offscreen,
+            row,
+            col,
+        )
+        .ok_or_else(|| "No virtual grid cell lies on a monitor".to_string())?;
+        if target != (row, col) {
+            println!(
+                "🖥️  Virtual cell ({}, {}) is off screen; using ({}, {}) instead",
+                row, col, target.0, target.1
             );
         }
+        Ok(target)
+    }
 
-        monitors
+    fn get_actual_monitor_bounds(&self) -> Vec<Rect> {
+        self.backend.get_monitor_bounds()
     }
 
-    pub fn calculate_monitor_cells(&self, rect: &RECT) -> HashMap<usize, Vec<(usize, usize)>> {
+    pub fn calculate_monitor_cells(&self, rect: &Rect) -> HashMap<usize, Vec<(usize, usize)>> {
         let mut monitor_cells = HashMap::new();
 
         for monitor_grid in &self.monitor_grids {
@@ -1295,14 +1252,17 @@ impl WindowTracker {
 
     // Animation Management Methods
 
+    #[cfg(feature = "animation")]
     pub fn start_window_animation(
         &mut self,
         hwnd: u64,
-        target_rect: RECT,
+        target_rect: impl Into<Rect>,
         duration: Duration,
         easing: EasingType,
     ) -> Result<(), String> {
-        if WindowTracker::is_window_maximized(hwnd) {
+        let target_rect: Rect = target_rect.into();
+        let easing = self.window_easings.get(&hwnd).copied().unwrap_or(easing);
+        if self.backend.is_window_maximized(hwnd) {
             println!(
                 "HWND 0x{:X} is maximized (WindowTracker::is_maximized), skipping animation.",
                 hwnd
@@ -1310,7 +1270,7 @@ impl WindowTracker {
             return Ok(());
         }
 
-        if let Some(current_rect) = Self::get_window_rect(hwnd) {
+        if let Some(current_rect) = self.backend.get_window_rect(hwnd) {
             if current_rect.left == target_rect.left
                 && current_rect.top == target_rect.top
                 && current_rect.right == target_rect.right
@@ -1332,24 +1292,34 @@ impl WindowTracker {
                 window::info::RectWrapper(current_rect),
                 window::info::RectWrapper(target_rect),
                 duration,
-                easing.clone(),
+                easing,
             );
             self.active_animations.insert(hwnd, animation);
-            let title = Self::get_window_title(hwnd);
-            let class = Self::get_window_class(hwnd);
+            let title: String = self
+                .backend
+                .get_window_title(hwnd)
+                .chars()
+                .take(50)
+                .collect();
+            let class: String = self
+                .backend
+                .get_window_class_name(hwnd)
+                .chars()
+                .take(50)
+                .collect();
             println!(
-                "🎬 Started animation for window {:?}: '{}' [{}] {} -> {} over {:?} {} {} {} {}",
+                "🎬 Started animation for window {:?}: '{}' [{}] ({},{},{},{}) -> ({},{},{},{}) over {:?} {} {} {} {}",
                 hwnd,
                 title,
                 class,
-                format!(
-                    "({},{},{},{})",
-                    current_rect.left, current_rect.top, current_rect.right, current_rect.bottom
-                ),
-                format!(
-                    "({},{},{},{})",
-                    target_rect.left, target_rect.top, target_rect.right, target_rect.bottom
-                ),
+                current_rect.left,
+                current_rect.top,
+                current_rect.right,
+                current_rect.bottom,
+                target_rect.left,
+                target_rect.top,
+                target_rect.right,
+                target_rect.bottom,
                 duration,
                 (current_rect.left - target_rect.left),
                 (current_rect.top - target_rect.top),
@@ -1362,6 +1332,7 @@ impl WindowTracker {
         }
     }
 
+    #[cfg(feature = "animation")]
     pub fn update_animations(&mut self) -> (Vec<u64>, Vec<u64>) {
         let mut completed_animations = Vec::new();
         let mut failed_animations = Vec::new();
@@ -1374,87 +1345,26 @@ impl WindowTracker {
 
         for hwnd in animation_keys {
             if let Some(mut animation_entry) = self.active_animations.get_mut(&hwnd) {
-                let is_window_maximized = WindowTracker::is_window_maximized(hwnd);
+                let is_window_maximized = self.backend.is_window_maximized(hwnd);
                 if animation_entry.is_completed() || is_window_maximized {
                     completed_animations.push(hwnd);
                 } else {
                     let current_rect = animation_entry.get_current_rect();
 
-                    // Move window to current animation position
-                    unsafe {
-                        let result = SetWindowPos(
-                            hwnd as HWND,
-                            std::ptr::null_mut(),
-                            current_rect.left,
-                            current_rect.top,
-                            current_rect.right - current_rect.left,
-                            current_rect.bottom - current_rect.top,
-                            SWP_NOZORDER | SWP_NOACTIVATE,
-                        );
-                        if result == 0 {
-                            let error = GetLastError();
-                            println!(
-                                "[DEBUG] SetWindowPos failed for hwnd=0x{:X} with error code: {}",
-                                hwnd, error
-                            );
-                            // If error is 5 (access denied), try moving without resizing
-                            if error == 5 {
-                                let move_only_result = SetWindowPos(
-                                    hwnd as HWND,
-                                    std::ptr::null_mut(),
-                                    current_rect.left,
-                                    current_rect.top,
-                                    0,
-                                    0,
-                                    SWP_NOZORDER | SWP_NOACTIVATE | SWP_NOSIZE,
-                                );
-                                if move_only_result == 0 {
-                                    let move_error = GetLastError();
-                                    println!(
-                                        "[DEBUG] Move-only SetWindowPos also failed for hwnd=0x{:X} with error code: {}",
-                                        hwnd, move_error
-                                    );
-                                } else {
-                                    println!(
-                                        "[DEBUG] Move-only SetWindowPos succeeded for hwnd=0x{:X}",
-                                        hwnd
-                                    );
-                                }
-                                // Send window to backmost
-                                SetWindowPos(
-                                    hwnd as HWND,
-                                    HWND_BOTTOM,
-                                    0,
-                                    0,
-                                    0,
-                                    0,
-                                    SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
-                                );
-                                // Mark as failed
-                                failed_animations.push(hwnd);
-                            }
-                        }
+                    // Move window to current animation position, skipping this frame if it fails
+                    if let Err(e) = self.backend.move_window_to_rect(hwnd, current_rect) {
+                        println!("[DEBUG] {}", e);
+                        continue;
                     }
 
-                    if let Some(prev_rect) = WindowTracker::get_window_rect(hwnd) {
-                        if prev_rect.left != current_rect.left
+                    if let Some(prev_rect) = self.backend.get_window_rect(hwnd) {
+                        if (prev_rect.left != current_rect.left
                             || prev_rect.top != current_rect.top
                             || prev_rect.right != current_rect.right
-                            || prev_rect.bottom != current_rect.bottom
+                            || prev_rect.bottom != current_rect.bottom)
+                            && !failed_animations.contains(&hwnd)
                         {
-                            if !failed_animations.contains(&hwnd) {
-                                failed_animations.push(hwnd);
-                            }
-                            // println!(
-                            //     "[DEBUG] Window 0x{:X} moved: prev=({}, {}, {}, {}), curr=({}, {}, {}, {}), size=({}x{}), requested=({}x{})",
-                            //     hwnd,
-                            //     prev_rect.left, prev_rect.top, prev_rect.right, prev_rect.bottom,
-                            //     current_rect.left, current_rect.top, current_rect.right, current_rect.bottom,
-                            //     current_rect.right - current_rect.left,
-                            //     current_rect.bottom - current_rect.top,
-                            //     animation_entry.target_rect.right - animation_entry.target_rect.left,
-                            //     animation_entry.target_rect.bottom - animation_entry.target_rect.top
-                            // );
+                            failed_animations.push(hwnd);
                         }
                     }
                 }
@@ -1470,6 +1380,7 @@ impl WindowTracker {
         (completed_animations, failed_animations)
     }
 
+    #[cfg(feature = "animation")]
     pub fn apply_grid_layout(
         &mut self,
         layout: &GridLayout,
@@ -1482,7 +1393,11 @@ impl WindowTracker {
         for row in 0..layout.config.rows {
             for col in 0..layout.config.cols {
                 if let Some(target_hwnd) = layout.virtual_grid[row][col] {
-                    // Calculate target position from grid coordinates
+                    // Calculate target position from grid coordinates, off the dead space
+                    // a layout saved under another monitor arrangement may point into
+                    let Ok((row, col)) = self.onscreen_virtual_cell(row, col) else {
+                        continue;
+                    };
                     if let Some(target_rect) = self.virtual_cell_to_window_rect(row, col) {
                         if self.windows.contains_key(&target_hwnd) {
                             match self.start_window_animation(
@@ -1503,172 +1418,1893 @@ impl WindowTracker {
             }
         }
 
-        println!(
-            "🎬 Started {} animations for grid layout '{}'",
-            animations_started, layout.name
-        );
-        Ok(animations_started)
-    }
+        // Apply sub-grid cells, wherever the monitor still has the same cell
+        for monitor_layout in &layout.monitor_grids {
+            let Some(monitor_grid) = self.monitor_grids.get(monitor_layout.monitor_id) else {
+                continue;
+            };
+            let targets: Vec<(u64, Rect)> = monitor_layout
+                .subgrid_cells
+                .iter()
+                .filter_map(|(path, owner)| {
+                    Some(((*owner)?, monitor_grid.cell_path_to_rect(path)?))
+                })
+                .filter(|(hwnd, _)| self.windows.contains_key(hwnd))
+                .collect();
+            for (target_hwnd, target_rect) in targets {
+                match self.start_window_animation(target_hwnd, target_rect, duration, easing) {
+                    Ok(_) => animations_started += 1,
+                    Err(e) => println!(
+                        "⚠️ Failed to start animation for window {:?}: {}",
+                        target_hwnd, e
+                    ),
+                }
+            }
+        }
 
-    pub fn save_current_layout(&mut self, name: String) {
-        // let layout = GridLayout::from_current_state(self, name.clone());
-        // self.saved_layouts.insert(name.clone(), layout);
+        // Apply zone layouts, on monitors that still use the same zones
+        for monitor_layout in &layout.monitor_grids {
+            let Some(zones) = &monitor_layout.zones else {
+                continue;
+            };
+            let Some(monitor_grid) = self.monitor_grids.get(monitor_layout.monitor_id) else {
+                continue;
+            };
+            if monitor_grid.zones.as_ref() != Some(zones) {
+                println!(
+                    "⚠️ Monitor {} no longer uses zone layout '{}', skipping its zones",
+                    monitor_layout.monitor_id, zones.name
+                );
+                continue;
+            }
+            let grid_rect = monitor_grid.grid_rect();
+            for (zone, owner) in zones.zones.iter().zip(&monitor_layout.zone_owners) {
+                let Some(target_hwnd) = *owner else {
+                    continue;
+                };
+                if !self.windows.contains_key(&target_hwnd) {
+                    continue;
+                }
+                let target_rect = crate::grid::math::zone_to_rect(&grid_rect, zone);
+                match self.start_window_animation(target_hwnd, target_rect, duration, easing) {
+                    Ok(_) => animations_started += 1,
+                    Err(e) => println!(
+                        "⚠️ Failed to start animation for window {:?}: {}",
+                        target_hwnd, e
+                    ),
+                }
+            }
+        }
+
+        println!(
+            "🎬 Started {} animations for grid layout '{}'",
+            animations_started, layout.name
+        );
+        Ok(animations_started)
+    }
+
+    pub fn save_current_layout(&mut self, name: String) {
+        let layout = crate::grid::LayoutGrid::from_current_state(self, name.clone());
+        self.saved_layouts.insert(name.clone(), layout);
         println!("💾 Saved current grid layout as '{}'", name);
     }
 
-    pub fn get_saved_layout(&self, name: &str) -> Option<GridLayout> {
-        self.saved_layouts
-            .get(name)
-            .map(|layout_ref| layout_ref.clone())
+    pub fn get_saved_layout(&self, name: &str) -> Option<GridLayout> {
+        self.saved_layouts
+            .get(name)
+            .map(|layout_ref| layout_ref.clone())
+    }
+
+    pub fn list_saved_layouts(&self) -> Vec<String> {
+        self.saved_layouts
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect()
+    }
+
+    #[cfg(feature = "animation")]
+    fn virtual_cell_to_window_rect(&self, row: usize, col: usize) -> Option<Rect> {
+        crate::grid::math::cell_to_rect(&self.monitor_rect, &self.virtual_config, row, col)
+    }
+
+    /// Move a window to a specific grid cell
+    pub fn move_window_to_cell(
+        &mut self,
+        hwnd: u64,
+        target_row: usize,
+        target_col: usize,
+    ) -> Result<(), String> {
+        if target_row >= self.config.rows || target_col >= self.config.cols {
+            return Err(format!(
+                "Invalid grid coordinates: ({}, {})",
+                target_row, target_col
+            ));
+        }
+
+        // Validate window handle first
+        if !self.backend.is_window(hwnd) {
+            return Err(format!("Invalid window handle: {:?}", hwnd));
+        }
+
+        // Check if window is manageable
+        if !self.backend.is_manageable_window(hwnd) {
+            return Err(format!("Window {:?} is not manageable", hwnd));
+        }
+
+        // Debug: Show all monitors before positioning
+        self.list_all_monitors();
+
+        // Get target rectangle on primary monitor instead of virtual grid
+        if let Some(target_rect) = self.primary_monitor_cell_to_rect(target_row, target_col) {
+            let primary_rect = self.get_primary_monitor_rect();
+            println!(
+                "🖥️  Using primary monitor: left={}, top={}, right={}, bottom={}",
+                primary_rect.left, primary_rect.top, primary_rect.right, primary_rect.bottom
+            );
+            println!(
+                "🖥️  Primary monitor dimensions: {}x{}",
+                primary_rect.right - primary_rect.left,
+                primary_rect.bottom - primary_rect.top
+            );
+            println!(
+                "🎯 Target cell ({}, {}) of grid ({}x{})",
+                target_row, target_col, self.config.rows, self.config.cols
+            );
+            println!(
+                "🎯 Calculated target rect: left={}, top={}, width={}, height={}",
+                target_rect.left,
+                target_rect.top,
+                target_rect.right - target_rect.left,
+                target_rect.bottom - target_rect.top
+            );
+
+            // Validate that the target rectangle is within primary monitor bounds
+            if target_rect.left < primary_rect.left
+                || target_rect.top < primary_rect.top
+                || target_rect.right > primary_rect.right
+                || target_rect.bottom > primary_rect.bottom
+            {
+                println!("⚠️  WARNING: Target rectangle is outside primary monitor bounds!");
+                println!(
+                    "   Target: ({}, {}) to ({}, {})",
+                    target_rect.left, target_rect.top, target_rect.right, target_rect.bottom
+                );
+                println!(
+                    "   Monitor: ({}, {}) to ({}, {})",
+                    primary_rect.left, primary_rect.top, primary_rect.right, primary_rect.bottom
+                );
+            } else {
+                println!("✅ Target rectangle is within primary monitor bounds");
+            }
+
+            println!(
+                "🎯 Moving window {:?} to rect: left={}, top={}, width={}, height={}",
+                hwnd,
+                target_rect.left,
+                target_rect.top,
+                target_rect.right - target_rect.left,
+                target_rect.bottom - target_rect.top
+            );
+
+            // Safely move the window (this operation doesn't need the tracker lock)
+            self.backend.move_window_to_rect(hwnd, target_rect)?;
+
+            println!("✅ Successfully moved window {:?}", hwnd);
+
+            // Update the grid tracking (keep this lock brief)
+            self.assign_window_to_virtual_cell(hwnd, target_row, target_col)?;
+
+            // Trigger callback notification if the window is tracked
+            if let Some(window_info) = self.windows.get(&hwnd) {
+                // Use a reference instead of cloning to avoid potential issues
+                self.trigger_window_moved(hwnd, &window_info);
+            }
+        } else {
+            return Err(format!(
+                "Could not calculate target rectangle for cell ({}, {})",
+                target_row, target_col
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Move a window to another monitor, into the cells equivalent to the ones it covers
+    /// on its current monitor. Sizes follow each monitor's grid, so a window moved from a
+    /// 100% to a 150% monitor keeps its place in the grid rather than its pixel size.
+    pub fn move_window_to_monitor(&mut self, hwnd: u64, monitor_id: usize) -> Result<(), String> {
+        let Some(target) = self.monitor_grids.get(monitor_id) else {
+            return Err(format!("Invalid monitor ID: {}", monitor_id));
+        };
+        let Some(rect) = self.backend.get_window_rect(hwnd) else {
+            return Err(format!("Invalid window handle: {:?}", hwnd));
+        };
+
+        // The window's current monitor is the one holding most of it
+        let source = self
+            .monitor_grids
+            .iter()
+            .max_by_key(|m| m.monitor_rect.intersection_area(&rect))
+            .filter(|m| m.monitor_rect.intersects(&rect))
+            .ok_or_else(|| format!("Window {:?} is not on any monitor", hwnd))?;
+        let cells =
+            crate::grid::math::window_to_grid_rect(&source.grid_rect(), &source.config, &rect);
+
+        let target_rect = crate::grid::math::cell_span_to_rect(
+            &target.grid_rect(),
+            &target.config,
+            cells.top,
+            cells.left,
+            cells.bottom - cells.top + 1,
+            cells.right - cells.left + 1,
+        )
+        .ok_or_else(|| {
+            format!(
+                "Cells ({}, {})-({}, {}) do not exist on monitor {}",
+                cells.top, cells.left, cells.bottom, cells.right, monitor_id
+            )
+        })?;
+
+        self.backend.move_window_to_rect(hwnd, target_rect)?;
+        self.update_window(hwnd);
+        if let Some(window_info) = self.windows.get(&hwnd) {
+            self.trigger_window_moved(hwnd, &window_info);
+        }
+        Ok(())
+    }
+
+    /// Pixel rectangle covering the cells from `start` to `end` (both `(row, col)`,
+    /// inclusive, in either order) on monitor `monitor_id`, or on the virtual grid when
+    /// `monitor_id` is `None`, where off-screen corners move to the nearest on-screen cell.
+    pub fn span_to_rect(
+        &self,
+        monitor_id: Option<usize>,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> Result<Rect, String> {
+        let (area, config, start, end) = match monitor_id {
+            Some(id) => {
+                let grid = self
+                    .monitor_grids
+                    .get(id)
+                    .ok_or_else(|| format!("Invalid monitor ID: {}", id))?;
+                (grid.grid_rect(), &grid.config, start, end)
+            }
+            // Corners in the dead space between monitors move to the nearest cell on one
+            None => (
+                self.monitor_rect,
+                &self.virtual_config,
+                self.onscreen_virtual_cell(start.0, start.1)?,
+                self.onscreen_virtual_cell(end.0, end.1)?,
+            ),
+        };
+        let (top, bottom) = (start.0.min(end.0), start.0.max(end.0));
+        let (left, right) = (start.1.min(end.1), start.1.max(end.1));
+        crate::grid::math::cell_span_to_rect(
+            &area,
+            config,
+            top,
+            left,
+            bottom - top + 1,
+            right - left + 1,
+        )
+        .ok_or_else(|| {
+            format!(
+                "Invalid grid span: ({}, {})-({}, {}) on a {}x{} grid",
+                start.0, start.1, end.0, end.1, config.rows, config.cols
+            )
+        })
+    }
+
+    /// Move and resize a window to cover the cells from `start` to `end`, on monitor
+    /// `monitor_id` or on the virtual grid. See [`Self::span_to_rect`].
+    pub fn move_window_to_span(
+        &mut self,
+        hwnd: u64,
+        monitor_id: Option<usize>,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> Result<(), String> {
+        let target_rect = self.span_to_rect(monitor_id, start, end)?;
+        self.place_window(hwnd, target_rect)
+    }
+
+    /// Animated [`Self::move_window_to_span`].
+    #[cfg(feature = "animation")]
+    pub fn animate_window_to_span(
+        &mut self,
+        hwnd: u64,
+        monitor_id: Option<usize>,
+        start: (usize, usize),
+        end: (usize, usize),
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<(), String> {
+        let target_rect = self.span_to_rect(monitor_id, start, end)?;
+        if !self.backend.is_manageable_window(hwnd) {
+            return Err(format!("Window {:?} is not manageable", hwnd));
+        }
+        self.start_window_animation(hwnd, target_rect, duration, easing)
+    }
+
+    /// Pixel rectangle of the cell at `path` on monitor `monitor_id`.
+    pub fn cell_path_to_rect(&self, monitor_id: usize, path: &CellPath) -> Result<Rect, String> {
+        let grid = self
+            .monitor_grids
+            .get(monitor_id)
+            .ok_or_else(|| format!("Invalid monitor ID: {}", monitor_id))?;
+        grid.cell_path_to_rect(path)
+            .ok_or_else(|| format!("Monitor {} has no cell {}", monitor_id, path))
+    }
+
+    /// Move and resize a window to fill the cell at `path` on monitor `monitor_id`.
+    pub fn move_window_to_cell_path(
+        &mut self,
+        hwnd: u64,
+        monitor_id: usize,
+        path: &CellPath,
+    ) -> Result<(), String> {
+        let target_rect = self.cell_path_to_rect(monitor_id, path)?;
+        self.place_window(hwnd, target_rect)
+    }
+
+    /// Animated [`Self::move_window_to_cell_path`].
+    #[cfg(feature = "animation")]
+    pub fn animate_window_to_cell_path(
+        &mut self,
+        hwnd: u64,
+        monitor_id: usize,
+        path: &CellPath,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<(), String> {
+        let target_rect = self.cell_path_to_rect(monitor_id, path)?;
+        if !self.backend.is_manageable_window(hwnd) {
+            return Err(format!("Window {:?} is not manageable", hwnd));
+        }
+        self.start_window_animation(hwnd, target_rect, duration, easing)
+    }
+
+    /// Pixel rectangle of the zone called `zone` on monitor `monitor_id`.
+    pub fn zone_to_rect(&self, monitor_id: usize, zone: &str) -> Result<Rect, String> {
+        let grid = self
+            .monitor_grids
+            .get(monitor_id)
+            .ok_or_else(|| format!("Invalid monitor ID: {}", monitor_id))?;
+        if grid.zones.is_none() {
+            return Err(format!("Monitor {} has no zone layout", monitor_id));
+        }
+        grid.zone_rect(zone)
+            .ok_or_else(|| format!("Monitor {} has no zone '{}'", monitor_id, zone))
+    }
+
+    /// Move and resize a window to fill the zone called `zone` on monitor `monitor_id`.
+    pub fn move_window_to_zone(
+        &mut self,
+        hwnd: u64,
+        monitor_id: usize,
+        zone: &str,
+    ) -> Result<(), String> {
+        let target_rect = self.zone_to_rect(monitor_id, zone)?;
+        self.place_window(hwnd, target_rect)
+    }
+
+    /// Animated [`Self::move_window_to_zone`].
+    #[cfg(feature = "animation")]
+    pub fn animate_window_to_zone(
+        &mut self,
+        hwnd: u64,
+        monitor_id: usize,
+        zone: &str,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<(), String> {
+        let target_rect = self.zone_to_rect(monitor_id, zone)?;
+        if !self.backend.is_manageable_window(hwnd) {
+            return Err(format!("Window {:?} is not manageable", hwnd));
+        }
+        self.start_window_animation(hwnd, target_rect, duration, easing)
+    }
+
+    /// Turn snap-to-grid on or off and choose its policy and exclusions.
+    pub fn set_snap_config(&mut self, config: SnapConfig) {
+        self.snap_config = config;
+    }
+
+    /// Where `hwnd` would snap to under the snap policy. `cursor` is the mouse position
+    /// when the drag ended; the window's centre stands in for it when unknown.
+    pub fn snap_target(&self, hwnd: u64, cursor: Option<Point>) -> Result<Rect, String> {
+        let rect = self
+            .backend
+            .get_window_rect(hwnd)
+            .ok_or_else(|| format!("Failed to get current rect for window {:?}", hwnd))?;
+        let point = match self.snap_config.policy {
+            SnapPolicy::ZoneUnderCursor => cursor.unwrap_or_else(|| rect.center()),
+            SnapPolicy::NearestCell | SnapPolicy::CoveredSpan => rect.center(),
+        };
+        let grid = self
+            .monitor_grids
+            .iter()
+            .find(|grid| grid.monitor_rect.contains_point(point))
+            .or_else(|| {
+                self.monitor_grids
+                    .iter()
+                    .filter(|grid| grid.monitor_rect.intersects(&rect))
+                    .max_by_key(|grid| grid.monitor_rect.intersection_area(&rect))
+            })
+            .ok_or_else(|| format!("Window {:?} is not on any monitor", hwnd))?;
+        let area = grid.grid_rect();
+
+        let target = match self.snap_config.policy {
+            SnapPolicy::CoveredSpan => crate::grid::math::covered_span(&area, &grid.config, &rect)
+                .and_then(|(start, end)| {
+                    crate::grid::math::cell_span_to_rect(
+                        &area,
+                        &grid.config,
+                        start.0,
+                        start.1,
+                        end.0 - start.0 + 1,
+                        end.1 - start.1 + 1,
+                    )
+                }),
+            SnapPolicy::ZoneUnderCursor if grid.zones.is_some() => {
+                // Later zones are drawn on top, so the last one under the cursor wins
+                grid.zones.as_ref().and_then(|layout| {
+                    layout
+                        .zones
+                        .iter()
+                        .rev()
+                        .map(|zone| crate::grid::math::zone_to_rect(&area, zone))
+                        .find(|zone_rect| zone_rect.contains_point(point))
+                })
+            }
+            SnapPolicy::NearestCell | SnapPolicy::ZoneUnderCursor => {
+                crate::grid::math::nearest_cell_path(&area, &grid.config, point)
+                    .and_then(|path| grid.cell_path_to_rect(&path))
+            }
+        };
+        target.ok_or_else(|| {
+            format!(
+                "No {:?} snap target for window {:?} on monitor {}",
+                self.snap_config.policy, hwnd, grid.monitor_id
+            )
+        })
+    }
+
+    /// Snap a window whose drag or resize just ended, unless snapping is off, the window
+    /// is maximized or an exclusion rule matches it. Returns where the window was sent.
+    pub fn snap_window(
+        &mut self,
+        hwnd: u64,
+        cursor: Option<Point>,
+    ) -> Result<Option<Rect>, String> {
+        if !self.snap_config.enabled || self.backend.is_window_maximized(hwnd) {
+            return Ok(None);
+        }
+        let process_id = self
+            .backend
+            .get_window_info(hwnd)
+            .map_or(0, |info| info.process_id);
+        if self.snap_config.is_excluded(
+            &self.backend.get_window_class_name(hwnd),
+            &self.backend.get_window_title(hwnd),
+            process_id,
+        ) {
+            return Ok(None);
+        }
+
+        let target_rect = self.snap_target(hwnd, cursor)?;
+        #[cfg(feature = "animation")]
+        if let Some((duration_ms, easing)) = self.snap_config.animation {
+            if !self.backend.is_manageable_window(hwnd) {
+                return Err(format!("Window {:?} is not manageable", hwnd));
+            }
+            let duration = Duration::from_millis(duration_ms as u64);
+            self.start_window_animation(hwnd, target_rect, duration, easing)?;
+            return Ok(Some(target_rect));
+        }
+        self.place_window(hwnd, target_rect)?;
+        Ok(Some(target_rect))
+    }
+
+    /// Windows a tiling layout arranges on monitor `monitor_id`: managed, non-floating
+    /// windows whose centre lies on the monitor, topmost first.
+    pub fn tiled_windows(&self, monitor_id: usize) -> Result<Vec<u64>, String> {
+        let grid = self
+            .monitor_grids
+            .get(monitor_id)
+            .ok_or_else(|| format!("Monitor {} does not exist", monitor_id))?;
+        let z_order = self.backend.get_hwnd_z_order_map();
+        let mut windows: Vec<u64> = self
+            .windows
+            .iter()
+            .map(|entry| *entry.key())
+            .filter(|hwnd| !self.is_desktop_hwnd(*hwnd) && !self.floating_windows.contains(hwnd))
+            .filter(|hwnd| {
+                self.backend.is_manageable_window(*hwnd)
+                    && self
+                        .backend
+                        .get_window_rect(*hwnd)
+                        .is_some_and(|rect| grid.monitor_rect.contains_point(rect.center()))
+            })
+            .collect();
+        windows.sort_by_key(|hwnd| (z_order.get(hwnd).copied().unwrap_or(usize::MAX), *hwnd));
+        Ok(windows)
+    }
+
+    /// Where monitor `monitor_id`'s tiling layout puts each of its windows, or an empty
+    /// list when the monitor isn't tiled.
+    pub fn tiling_targets(&self, monitor_id: usize) -> Result<Vec<(u64, Rect)>, String> {
+        let windows = self.tiled_windows(monitor_id)?;
+        let Some(algorithm) = self.monitor_tilings.get(&monitor_id) else {
+            return Ok(Vec::new());
+        };
+        let area = self.monitor_grids[monitor_id].grid_rect();
+        Ok(algorithm.arrange(&area, &windows))
+    }
+
+    /// Tile monitor `monitor_id` with `algorithm`, animating its windows into place, or
+    /// stop tiling it with `None`. Floating windows on the monitor are tiled again.
+    /// Returns how many windows started moving.
+    #[cfg(feature = "animation")]
+    pub fn set_monitor_tiling(
+        &mut self,
+        monitor_id: usize,
+        algorithm: Option<Arc<dyn LayoutAlgorithm>>,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<usize, String> {
+        if monitor_id >= self.monitor_grids.len() {
+            return Err(format!("Monitor {} does not exist", monitor_id));
+        }
+        match algorithm {
+            Some(algorithm) => {
+                println!("🧱 Tiling monitor {} with {}", monitor_id, algorithm.name());
+                self.monitor_tilings.insert(monitor_id, algorithm);
+                let monitor_rect = self.monitor_grids[monitor_id].monitor_rect;
+                let unfloated: Vec<u64> = self
+                    .floating_windows
+                    .iter()
+                    .copied()
+                    .filter(|hwnd| {
+                        self.backend
+                            .get_window_rect(*hwnd)
+                            .is_some_and(|rect| monitor_rect.contains_point(rect.center()))
+                            && !self.matching_rule(*hwnd).is_some_and(|rule| rule.float)
+                    })
+                    .collect();
+                for hwnd in unfloated {
+                    self.floating_windows.remove(&hwnd);
+                }
+            }
+            None => {
+                self.monitor_tilings.remove(&monitor_id);
+                self.tiled_arrangements.remove(&monitor_id);
+                return Ok(0);
+            }
+        }
+        self.retile_monitor(monitor_id, duration, easing)
+    }
+
+    /// Animate monitor `monitor_id`'s windows back into its tiling layout, e.g. after
+    /// windows open or close. Returns how many windows started moving.
+    #[cfg(feature = "animation")]
+    pub fn retile_monitor(
+        &mut self,
+        monitor_id: usize,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<usize, String> {
+        let targets = self.tiling_targets(monitor_id)?;
+        self.tiled_arrangements.insert(monitor_id, targets.clone());
+        Ok(self.animate_to_targets(&targets, duration, easing))
+    }
+
+    /// Retile every tiled monitor whose windows changed since it was last arranged,
+    /// e.g. because windows opened, closed, were minimized or restored, or started
+    /// floating. Returns the monitors that were retiled.
+    #[cfg(feature = "animation")]
+    pub fn retile_changed_monitors(
+        &mut self,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Vec<usize> {
+        let mut monitors: Vec<usize> = self.monitor_tilings.keys().copied().collect();
+        monitors.sort_unstable();
+        monitors.retain(|&monitor_id| {
+            let Ok(mut current) = self.tiled_windows(monitor_id) else {
+                return false;
+            };
+            let mut arranged: Vec<u64> = self
+                .tiled_arrangements
+                .get(&monitor_id)
+                .map(|targets| targets.iter().map(|(hwnd, _)| *hwnd).collect())
+                .unwrap_or_default();
+            // Focus changes reorder the windows; only joining or leaving counts
+            current.sort_unstable();
+            arranged.sort_unstable();
+            current != arranged
+        });
+        for &monitor_id in &monitors {
+            if let Err(e) = self.retile_monitor(monitor_id, duration, easing) {
+                println!("⚠️ Failed to retile monitor {}: {}", monitor_id, e);
+            }
+        }
+        monitors
+    }
+
+    /// Monitor whose tiling layout last arranged `hwnd`, if any.
+    pub fn tiling_monitor_of(&self, hwnd: u64) -> Option<usize> {
+        self.tiled_arrangements
+            .iter()
+            .find(|(_, targets)| targets.iter().any(|(tiled, _)| *tiled == hwnd))
+            .map(|(monitor_id, _)| *monitor_id)
+    }
+
+    /// Float `hwnd` if it was tiled and has been moved off its tile, e.g. dragged away
+    /// by the user; windows still animating into place are left alone. Returns the
+    /// monitor the window was tiled on.
+    #[cfg(feature = "animation")]
+    pub fn float_if_moved_off_tile(&mut self, hwnd: u64) -> Option<usize> {
+        if self.active_animations.contains_key(&hwnd) {
+            return None;
+        }
+        let rect = self.backend.get_window_rect(hwnd)?;
+        let (monitor_id, tile) =
+            self.tiled_arrangements
+                .iter()
+                .find_map(|(monitor_id, targets)| {
+                    targets
+                        .iter()
+                        .find(|(tiled, _)| *tiled == hwnd)
+                        .map(|(_, tile)| (*monitor_id, *tile))
+                })?;
+        if tile.contains_point(rect.center()) {
+            return None;
+        }
+        self.floating_windows.insert(hwnd);
+        Some(monitor_id)
+    }
+
+    /// Let `hwnd` float, so tiling layouts leave it wherever the user puts it, or with
+    /// `false` let the next retile arrange it again.
+    pub fn set_window_floating(&mut self, hwnd: u64, floating: bool) {
+        if floating {
+            self.floating_windows.insert(hwnd);
+        } else {
+            self.floating_windows.remove(&hwnd);
+        }
+    }
+
+    /// Replace the filter deciding which windows are manageable, then stop tracking
+    /// windows it now rejects and start tracking the ones it now accepts. Returns how
+    /// many windows were (added, removed).
+    pub fn set_window_filter(&mut self, filter: WindowFilter) -> (usize, usize) {
+        self.backend.set_window_filter(filter);
+        let rejected: Vec<u64> = self
+            .windows
+            .iter()
+            .map(|entry| *entry.key())
+            .filter(|hwnd| !self.backend.is_manageable_window(*hwnd))
+            .collect();
+        for hwnd in &rejected {
+            self.remove_window(*hwnd);
+        }
+        let mut accepted: Vec<u64> = self
+            .backend
+            .enumerate_windows()
+            .iter()
+            .map(|entry| *entry.key())
+            .filter(|hwnd| !self.windows.contains_key(hwnd))
+            .collect();
+        accepted.sort_unstable();
+        let added = accepted
+            .into_iter()
+            .filter(|hwnd| self.add_window(*hwnd))
+            .count();
+        println!(
+            "🔎 Window filter updated: {} windows added, {} removed",
+            added,
+            rejected.len()
+        );
+        (added, rejected.len())
+    }
+
+    /// Why the manageable-window filter accepts or rejects `hwnd`.
+    pub fn explain_window(&self, hwnd: u64) -> FilterDecision {
+        self.backend.explain_window(hwnd)
+    }
+
+    /// Replace the window rules. They apply to windows created from now on and at the
+    /// next scan; [`Self::apply_window_rules_to_all`] applies them right away.
+    pub fn set_window_rules(&mut self, rules: WindowRules) {
+        println!("📏 Loaded {} window rules", rules.rules.len());
+        self.window_rules = rules;
+    }
+
+    /// What the window rules see of `hwnd`, or None if the window doesn't exist.
+    pub fn window_properties(&self, hwnd: u64) -> Option<WindowProperties> {
+        let info = self.backend.get_window_info(hwnd)?;
+        Some(WindowProperties {
+            class_name: self.backend.get_window_class_name(hwnd),
+            title: self.backend.get_window_title(hwnd),
+            process_id: info.process_id,
+            executable: self.backend.get_window_executable(hwnd),
+            width: info.window_rect.0.width(),
+            height: info.window_rect.0.height(),
+        })
+    }
+
+    /// The first window rule matching `hwnd`.
+    pub fn matching_rule(&self, hwnd: u64) -> Option<&WindowRule> {
+        let window = self.window_properties(hwnd)?;
+        self.window_rules.first_match(&window)
+    }
+
+    /// Apply the first window rule matching `hwnd`: stop tracking the window, float it,
+    /// move it to its monitor or placement and remember its easing. A window a rule no
+    /// longer ignores is tracked again. Returns the name of the rule that matched.
+    pub fn apply_window_rules(&mut self, hwnd: u64) -> Result<Option<String>, String> {
+        let was_ignored = self.ignored_windows.remove(&hwnd);
+        let Some(rule) = self.matching_rule(hwnd).cloned() else {
+            if was_ignored && self.backend.is_manageable_window(hwnd) {
+                self.add_window(hwnd);
+            }
+            return Ok(None);
+        };
+        println!("📏 Rule '{}' matched window 0x{:X}", rule.name, hwnd);
+
+        if rule.ignore {
+            self.ignored_windows.insert(hwnd);
+            self.remove_window(hwnd);
+            return Ok(Some(rule.name));
+        }
+        if was_ignored && self.backend.is_manageable_window(hwnd) {
+            self.add_window(hwnd);
+        }
+        if rule.float {
+            self.floating_windows.insert(hwnd);
+        }
+        #[cfg(feature = "animation")]
+        if let Some(easing) = rule.easing {
+            self.window_easings.insert(hwnd, easing);
+        }
+
+        match (&rule.placement, rule.monitor) {
+            (Some(RulePlacement::Span { start, end }), monitor_id) => {
+                self.move_window_to_span(hwnd, monitor_id, *start, *end)?
+            }
+            (Some(RulePlacement::Zone(zone)), Some(monitor_id)) => {
+                self.move_window_to_zone(hwnd, monitor_id, zone)?
+            }
+            (Some(RulePlacement::Zone(zone)), None) => {
+                return Err(format!(
+                    "Rule '{}' places windows in zone '{}' but names no monitor",
+                    rule.name, zone
+                ))
+            }
+            (None, Some(monitor_id)) => {
+                // Windows already on the monitor stay where they are
+                let current = self.backend.get_window_rect(hwnd).and_then(|rect| {
+                    self.monitor_grids
+                        .iter()
+                        .position(|m| m.monitor_rect.contains_point(rect.center()))
+                });
+                if current != Some(monitor_id) {
+                    self.move_window_to_monitor(hwnd, monitor_id)?;
+                }
+            }
+            (None, None) => {}
+        }
+        Ok(Some(rule.name))
+    }
+
+    /// Apply the window rules to every tracked or ignored window, e.g. after a rescan or
+    /// when the rules change. Returns how many windows a rule matched.
+    pub fn apply_window_rules_to_all(&mut self) -> usize {
+        if self.window_rules.is_empty() && self.ignored_windows.is_empty() {
+            return 0;
+        }
+        let mut hwnds: Vec<u64> = self
+            .windows
+            .iter()
+            .map(|entry| *entry.key())
+            .chain(self.ignored_windows.iter().copied())
+            .collect();
+        hwnds.sort_unstable();
+        hwnds.dedup();
+        let mut matched = 0;
+        for hwnd in hwnds {
+            match self.apply_window_rules(hwnd) {
+                Ok(Some(_)) => matched += 1,
+                Ok(None) => {}
+                Err(e) => {
+                    matched += 1;
+                    println!("⚠️ Window rule failed for 0x{:X}: {}", hwnd, e);
+                }
+            }
+        }
+        matched
+    }
+
+    /// Create an empty workspace; windows join it with [`Self::move_window_to_workspace`]
+    /// or when it is switched away from.
+    pub fn create_workspace(&mut self, name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("Workspace name is empty".to_string());
+        }
+        if self.workspaces.contains_key(name) {
+            return Err(format!("Workspace '{}' already exists", name));
+        }
+        self.workspaces
+            .insert(name.to_string(), Workspace::new(name));
+        println!("🗂️ Created workspace '{}'", name);
+        Ok(())
+    }
+
+    /// Monitor showing workspace `name`, if any.
+    pub fn workspace_monitor(&self, name: &str) -> Option<usize> {
+        self.active_workspaces
+            .iter()
+            .find(|(_, active)| active.as_str() == name)
+            .map(|(monitor_id, _)| *monitor_id)
+    }
+
+    /// Every workspace by name. Shown workspaces count the windows now on their monitor.
+    pub fn list_workspaces(&self) -> Vec<WorkspaceSummary> {
+        self.workspaces
+            .values()
+            .map(|workspace| {
+                let monitor_id = self.workspace_monitor(&workspace.name);
+                let window_count = match monitor_id {
+                    Some(monitor_id) => self.workspace_windows(monitor_id).len(),
+                    None => workspace.windows.len(),
+                };
+                WorkspaceSummary {
+                    name: workspace.name.clone(),
+                    monitor_id,
+                    window_count,
+                }
+            })
+            .collect()
+    }
+
+    /// Monitor holding the centre of `rect` and the cells it covers on that monitor's grid.
+    fn monitor_span(&self, rect: &Rect) -> Option<(usize, UsizeRect)> {
+        let monitor_id = self
+            .monitor_grids
+            .iter()
+            .position(|grid| grid.monitor_rect.contains_point(rect.center()))?;
+        let grid = &self.monitor_grids[monitor_id];
+        let cells = crate::grid::math::window_to_grid_rect(&grid.grid_rect(), &grid.config, rect);
+        Some((monitor_id, cells))
+    }
+
+    /// Monitor holding the centre of `hwnd` and the cells the window covers on it.
+    fn window_placement(&self, hwnd: u64) -> Option<(usize, WorkspaceWindow)> {
+        let rect = self.backend.get_window_rect(hwnd)?;
+        let (monitor_id, cells) = self.monitor_span(&rect)?;
+        Some((
+            monitor_id,
+            WorkspaceWindow {
+                hwnd,
+                start: (cells.top, cells.left),
+                end: (cells.bottom, cells.right),
+            },
+        ))
+    }
+
+    /// Windows the workspace shown on monitor `monitor_id` holds: managed windows whose
+    /// centre lies on the monitor, with their cells.
+    fn workspace_windows(&self, monitor_id: usize) -> Vec<WorkspaceWindow> {
+        let mut windows: Vec<WorkspaceWindow> = self
+            .windows
+            .iter()
+            .map(|entry| *entry.key())
+            .filter(|hwnd| {
+                !self.is_desktop_hwnd(*hwnd)
+                    && !self.is_scratchpad_window(*hwnd)
+                    && self.backend.is_manageable_window(*hwnd)
+            })
+            .filter_map(|hwnd| self.window_placement(hwnd))
+            .filter(|(monitor, _)| *monitor == monitor_id)
+            .map(|(_, window)| window)
+            .collect();
+        windows.sort_by_key(|window| window.hwnd);
+        windows
+    }
+
+    /// Minimize or hide `hwnd` as `workspace_hide_mode` says and stop tracking it.
+    #[cfg(feature = "animation")]
+    fn put_away_window(&mut self, hwnd: u64) {
+        self.active_animations.remove(&hwnd);
+        let state = match self.workspace_hide_mode {
+            WorkspaceHideMode::Minimize => WindowShowState::Minimized,
+            WorkspaceHideMode::Hide => WindowShowState::Hidden,
+        };
+        if let Err(e) = self.backend.show_window(hwnd, state) {
+            println!("⚠️ Failed to put away window 0x{:X}: {}", hwnd, e);
+        }
+        self.remove_window(hwnd);
+    }
+
+    /// Show workspace `name` on monitor `monitor_id`. The windows on the monitor are
+    /// saved into the workspace it showed (see [`Workspace::default_name`] for monitors
+    /// never switched before) and minimized or hidden; the incoming workspace's windows
+    /// come back and animate into their saved cells. Returns how many windows came back.
+    #[cfg(feature = "animation")]
+    pub fn switch_workspace(
+        &mut self,
+        monitor_id: usize,
+        name: &str,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<usize, String> {
+        if monitor_id >= self.monitor_grids.len() {
+            return Err(format!("Monitor {} does not exist", monitor_id));
+        }
+        if !self.workspaces.contains_key(name) {
+            return Err(format!("Workspace '{}' does not exist", name));
+        }
+        let outgoing_name = self
+            .active_workspaces
+            .get(&monitor_id)
+            .cloned()
+            .unwrap_or_else(|| Workspace::default_name(monitor_id));
+        if outgoing_name == name {
+            return Ok(0);
+        }
+        if let Some(other) = self.workspace_monitor(name) {
+            return Err(format!(
+                "Workspace '{}' is already shown on monitor {}",
+                name, other
+            ));
+        }
+
+        // A window belongs to one workspace: the one it was last put away with
+        let outgoing_windows = self.workspace_windows(monitor_id);
+        for workspace in self.workspaces.values_mut() {
+            for window in &outgoing_windows {
+                workspace.remove(window.hwnd);
+            }
+        }
+        for window in &outgoing_windows {
+            self.put_away_window(window.hwnd);
+        }
+        self.workspaces
+            .entry(outgoing_name.clone())
+            .or_insert_with(|| Workspace::new(outgoing_name.as_str()))
+            .windows = outgoing_windows;
+
+        // Windows closed while put away are dropped from the workspace
+        let mut incoming = self.workspaces[name].windows.clone();
+        incoming.retain(|window| self.backend.is_window(window.hwnd));
+        let mut restored = 0;
+        for window in &incoming {
+            if let Err(e) = self
+                .backend
+                .show_window(window.hwnd, WindowShowState::Shown)
+            {
+                println!("⚠️ Failed to show window 0x{:X}: {}", window.hwnd, e);
+                continue;
+            }
+            self.add_window(window.hwnd);
+            let started = self
+                .span_to_rect(Some(monitor_id), window.start, window.end)
+                .and_then(|rect| self.start_window_animation(window.hwnd, rect, duration, easing));
+            match started {
+                Ok(()) => restored += 1,
+                Err(e) => println!("⚠️ Failed to restore window 0x{:X}: {}", window.hwnd, e),
+            }
+        }
+        if let Some(workspace) = self.workspaces.get_mut(name) {
+            workspace.windows = incoming;
+        }
+        self.active_workspaces.insert(monitor_id, name.to_string());
+        println!(
+            "🗂️ Monitor {} switched from workspace '{}' to '{}' ({} windows)",
+            monitor_id, outgoing_name, name, restored
+        );
+        Ok(restored)
+    }
+
+    /// Move `hwnd` to workspace `name`, keeping its cells. If the workspace is shown, the
+    /// window animates onto its monitor; otherwise it is put away until the workspace is
+    /// switched in.
+    #[cfg(feature = "animation")]
+    pub fn move_window_to_workspace(
+        &mut self,
+        hwnd: u64,
+        name: &str,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<(), String> {
+        if !self.workspaces.contains_key(name) {
+            return Err(format!("Workspace '{}' does not exist", name));
+        }
+        let (current_monitor, window) = self
+            .window_placement(hwnd)
+            .ok_or_else(|| format!("Window 0x{:X} is not on any monitor", hwnd))?;
+        for workspace in self.workspaces.values_mut() {
+            workspace.remove(hwnd);
+        }
+        match self.workspace_monitor(name) {
+            // A shown workspace holds whatever is on its monitor
+            Some(monitor_id) if monitor_id == current_monitor => Ok(()),
+            Some(monitor_id) => {
+                let rect = self.span_to_rect(Some(monitor_id), window.start, window.end)?;
+                self.start_window_animation(hwnd, rect, duration, easing)
+            }
+            None => {
+                self.put_away_window(hwnd);
+                if let Some(workspace) = self.workspaces.get_mut(name) {
+                    workspace.insert(window);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Replace the scratchpads, then look for the window of each one that has none by
+    /// its identity, e.g. after a restart, and hide it. Returns how many were found.
+    pub fn set_scratchpads(&mut self, scratchpads: Scratchpads) -> usize {
+        println!("📋 Loaded {} scratchpads", scratchpads.scratchpads.len());
+        self.scratchpads = scratchpads;
+        self.scratchpad_windows
+            .retain(|name, _| self.scratchpads.get(name).is_some());
+        self.shown_scratchpads
+            .retain(|name| self.scratchpads.get(name).is_some());
+        let names: Vec<String> = self
+            .scratchpads
+            .scratchpads
+            .iter()
+            .map(|scratchpad| scratchpad.name.clone())
+            .collect();
+        names
+            .iter()
+            .filter(|name| self.attach_scratchpad_window(name))
+            .count()
+    }
+
+    pub fn is_scratchpad_window(&self, hwnd: u64) -> bool {
+        self.scratchpad_windows.values().any(|held| *held == hwnd)
+    }
+
+    /// If scratchpad `name` has no live window, give it the first window matching its
+    /// identity, hidden ones included, and hide that window. Returns true if one was found.
+    fn attach_scratchpad_window(&mut self, name: &str) -> bool {
+        let Some(matcher) = self.scratchpads.get(name).map(|s| s.matcher.clone()) else {
+            return false;
+        };
+        if self
+            .scratchpad_windows
+            .get(name)
+            .is_some_and(|hwnd| self.backend.is_window(*hwnd))
+        {
+            return false;
+        }
+        let found = self.backend.list_windows().into_iter().find(|hwnd| {
+            !self.is_scratchpad_window(*hwnd)
+                && self
+                    .window_properties(*hwnd)
+                    .is_some_and(|window| matcher.matches(&window))
+        });
+        let Some(hwnd) = found else {
+            return false;
+        };
+        self.hide_scratchpad_window(hwnd);
+        self.scratchpad_windows.insert(name.to_string(), hwnd);
+        self.shown_scratchpads.remove(name);
+        println!("📋 Window 0x{:X} is back in scratchpad '{}'", hwnd, name);
+        true
+    }
+
+    /// Hide `hwnd` and stop tracking it.
+    fn hide_scratchpad_window(&mut self, hwnd: u64) {
+        #[cfg(feature = "animation")]
+        self.active_animations.remove(&hwnd);
+        if let Err(e) = self.backend.show_window(hwnd, WindowShowState::Hidden) {
+            println!("⚠️ Failed to hide window 0x{:X}: {}", hwnd, e);
+        }
+        self.remove_window(hwnd);
+    }
+
+    /// Send tracked window `hwnd` to scratchpad `name` and hide it. The scratchpad
+    /// remembers the window's identity, and a new one also the cells the window covers
+    /// now; save [`Self::scratchpads`] to keep them across restarts.
+    pub fn send_to_scratchpad(&mut self, hwnd: u64, name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("Scratchpad name is empty".to_string());
+        }
+        if !self.windows.contains_key(&hwnd) {
+            return Err(format!("Window 0x{:X} is not tracked", hwnd));
+        }
+        if let Some(held) = self
+            .scratchpad_windows
+            .get(name)
+            .copied()
+            .filter(|held| *held != hwnd && self.backend.is_window(*held))
+        {
+            return Err(format!(
+                "Scratchpad '{}' already holds window 0x{:X}",
+                name, held
+            ));
+        }
+        let properties = self
+            .window_properties(hwnd)
+            .ok_or_else(|| format!("Invalid window handle: 0x{:X}", hwnd))?;
+        let (_, placement) = self
+            .window_placement(hwnd)
+            .ok_or_else(|| format!("Window 0x{:X} is not on any monitor", hwnd))?;
+
+        // A window belongs to one scratchpad and leaves its workspace
+        self.scratchpad_windows.retain(|_, held| *held != hwnd);
+        for workspace in self.workspaces.values_mut() {
+            workspace.remove(hwnd);
+        }
+        self.scratchpads
+            .assign(name, &properties, (placement.start, placement.end));
+        self.hide_scratchpad_window(hwnd);
+        self.scratchpad_windows.insert(name.to_string(), hwnd);
+        self.shown_scratchpads.remove(name);
+        println!("📋 Window 0x{:X} sent to scratchpad '{}'", hwnd, name);
+        Ok(())
+    }
+
+    /// Monitor holding the centre of the foreground window, or the first monitor.
+    pub fn focused_monitor(&self) -> usize {
+        self.backend
+            .get_foreground_window()
+            .and_then(|hwnd| self.backend.get_window_rect(hwnd))
+            .and_then(|rect| {
+                self.monitor_grids
+                    .iter()
+                    .position(|grid| grid.monitor_rect.contains_point(rect.center()))
+            })
+            .unwrap_or(0)
+    }
+
+    /// Show scratchpad `name`'s window over the grid, dropping it from the top of the
+    /// focused monitor into the scratchpad's cells, or hide it again if it is shown.
+    /// Returns true if the window is now shown.
+    #[cfg(feature = "animation")]
+    pub fn toggle_scratchpad(
+        &mut self,
+        name: &str,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<bool, String> {
+        let scratchpad = self
+            .scratchpads
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Scratchpad '{}' does not exist", name))?;
+        // The window may have closed and been reopened since
+        self.attach_scratchpad_window(name);
+        let hwnd = self
+            .scratchpad_windows
+            .get(name)
+            .copied()
+            .filter(|hwnd| self.backend.is_window(*hwnd))
+            .ok_or_else(|| format!("Scratchpad '{}' has no window", name))?;
+
+        if self.shown_scratchpads.remove(name) {
+            self.hide_scratchpad_window(hwnd);
+            println!("📋 Scratchpad '{}' hidden", name);
+            return Ok(false);
+        }
+        let monitor_id = self.focused_monitor();
+        let target = self.span_to_rect(Some(monitor_id), scratchpad.start, scratchpad.end)?;
+        let monitor_top = self.monitor_grids[monitor_id].monitor_rect.top;
+        self.backend.show_window(hwnd, WindowShowState::Focused)?;
+        self.backend
+            .move_window_to_rect(hwnd, target.offset(0, monitor_top - target.bottom))?;
+        self.add_window(hwnd);
+        // Tiling layouts leave it alone while it is shown
+        self.floating_windows.insert(hwnd);
+        self.start_window_animation(hwnd, target, duration, easing)?;
+        self.shown_scratchpads.insert(name.to_string());
+        println!("📋 Scratchpad '{}' shown on monitor {}", name, monitor_id);
+        Ok(true)
+    }
+
+    /// The window next to `hwnd` in `direction`. Windows on the same monitor are
+    /// compared by their cells on its grid; if none lies that way, the search goes on
+    /// over the virtual grid to windows on the other monitors.
+    pub fn neighbour_window(&self, hwnd: u64, direction: Direction) -> Option<u64> {
+        let rect = self.backend.get_window_rect(hwnd)?;
+        let candidates: Vec<(u64, Rect)> = self
+            .distribution_candidates()
+            .into_iter()
+            .filter(|(candidate, _)| *candidate != hwnd)
+            .collect();
+
+        let origin = self.monitor_span(&rect);
+        let origin_monitor = origin.map(|(monitor_id, _)| monitor_id);
+        if let Some((monitor_id, span)) = origin {
+            let same_monitor: Vec<(u64, UsizeRect)> = candidates
+                .iter()
+                .filter_map(|(candidate, rect)| {
+                    let (monitor, cells) = self.monitor_span(rect)?;
+                    (monitor == monitor_id).then_some((*candidate, cells))
+                })
+                .collect();
+            if let Some(found) = navigation::neighbour(&span, &same_monitor, direction) {
+                return Some(found);
+            }
+        }
+
+        let elsewhere: Vec<(u64, UsizeRect)> = candidates
+            .iter()
+            .filter(|(_, rect)| {
+                origin_monitor.is_none()
+                    || self.monitor_span(rect).map(|(monitor_id, _)| monitor_id) != origin_monitor
+            })
+            .map(|(candidate, rect)| (*candidate, self.window_to_grid_rect(rect)))
+            .collect();
+        navigation::neighbour(&self.window_to_grid_rect(&rect), &elsewhere, direction)
+    }
+
+    /// Focus the window next to `hwnd` in `direction`. Returns the focused window.
+    pub fn focus_neighbour(&mut self, hwnd: u64, direction: Direction) -> Result<u64, String> {
+        let neighbour = self
+            .neighbour_window(hwnd, direction)
+            .ok_or_else(|| format!("Window 0x{:X} has no neighbour {}", hwnd, direction))?;
+        self.backend
+            .show_window(neighbour, WindowShowState::Focused)?;
+        println!(
+            "🧭 Focused window 0x{:X}, {} of 0x{:X}",
+            neighbour, direction, hwnd
+        );
+        Ok(neighbour)
+    }
+
+    /// Move `hwnd` one cell in `direction` on its monitor's grid. At the edge of the
+    /// grid it crosses onto the monitor lying that way, if any, keeping its size.
+    #[cfg(feature = "animation")]
+    pub fn move_window_in_direction(
+        &mut self,
+        hwnd: u64,
+        direction: Direction,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<usize, String> {
+        let rect = self
+            .backend
+            .get_window_rect(hwnd)
+            .ok_or_else(|| format!("Failed to get rect for window 0x{:X}", hwnd))?;
+        let (monitor_id, span) = self
+            .monitor_span(&rect)
+            .ok_or_else(|| format!("Window 0x{:X} is not on any monitor", hwnd))?;
+        let config = &self.monitor_grids[monitor_id].config;
+        let (target_monitor, target) =
+            match navigation::shift_span(&span, direction, config.rows, config.cols) {
+                Some(shifted) => (monitor_id, shifted),
+                None => {
+                    let monitors: Vec<Rect> = self
+                        .monitor_grids
+                        .iter()
+                        .map(|grid| grid.monitor_rect)
+                        .collect();
+                    let next = navigation::adjacent_monitor(&monitors, monitor_id, direction)
+                        .ok_or_else(|| {
+                            format!("Monitor {} has no monitor {} of it", monitor_id, direction)
+                        })?;
+                    let config = &self.monitor_grids[next].config;
+                    (
+                        next,
+                        navigation::enter_span(&span, direction, config.rows, config.cols),
+                    )
+                }
+            };
+        let target_rect = self.span_to_rect(
+            Some(target_monitor),
+            (target.top, target.left),
+            (target.bottom, target.right),
+        )?;
+        self.start_window_animation(hwnd, target_rect, duration, easing)?;
+        Ok(target_monitor)
+    }
+
+    /// Swap `hwnd` with the window next to it in `direction`, each taking the other's
+    /// rect. Returns the other window and the animation batch.
+    #[cfg(feature = "animation")]
+    pub fn swap_window_in_direction(
+        &mut self,
+        hwnd: u64,
+        direction: Direction,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<(u64, u64), String> {
+        let neighbour = self
+            .neighbour_window(hwnd, direction)
+            .ok_or_else(|| format!("Window 0x{:X} has no neighbour {}", hwnd, direction))?;
+        let batch = self.swap_windows(hwnd, neighbour, duration, easing)?;
+        Ok((neighbour, batch))
     }
 
-    pub fn list_saved_layouts(&self) -> Vec<String> {
-        self.saved_layouts
-            .iter()
-            .map(|entry| entry.key().clone())
+    /// Push the edge of `hwnd`'s span in `direction` out one cell when `grow`, or pull
+    /// it in one cell otherwise, on the window's monitor grid.
+    #[cfg(feature = "animation")]
+    pub fn resize_window_span(
+        &mut self,
+        hwnd: u64,
+        direction: Direction,
+        grow: bool,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<(), String> {
+        let rect = self
+            .backend
+            .get_window_rect(hwnd)
+            .ok_or_else(|| format!("Failed to get rect for window 0x{:X}", hwnd))?;
+        let (monitor_id, span) = self
+            .monitor_span(&rect)
+            .ok_or_else(|| format!("Window 0x{:X} is not on any monitor", hwnd))?;
+        let config = &self.monitor_grids[monitor_id].config;
+        let target = if grow {
+            navigation::grow_span(&span, direction, config.rows, config.cols)
+        } else {
+            navigation::shrink_span(&span, direction)
+        }
+        .ok_or_else(|| {
+            format!(
+                "Window 0x{:X} can't {} {}",
+                hwnd,
+                if grow { "grow" } else { "shrink" },
+                direction
+            )
+        })?;
+        let target_rect = self.span_to_rect(
+            Some(monitor_id),
+            (target.top, target.left),
+            (target.bottom, target.right),
+        )?;
+        self.start_window_animation(hwnd, target_rect, duration, easing)
+    }
+
+    /// The group `hwnd` belongs to, if any.
+    pub fn window_group_of(&self, hwnd: u64) -> Option<&WindowGroup> {
+        self.window_groups
+            .values()
+            .find(|group| group.contains(hwnd))
+    }
+
+    /// Where every grouped window sits in its group, for reporting to clients.
+    pub fn group_memberships(&self) -> HashMap<u64, GroupMembership> {
+        self.window_groups
+            .values()
+            .flat_map(|group| {
+                group
+                    .members
+                    .iter()
+                    .filter_map(|hwnd| Some((*hwnd, group.membership(*hwnd)?)))
+            })
             .collect()
     }
 
-    fn virtual_cell_to_window_rect(&self, row: usize, col: usize) -> Option<RECT> {
-        if row >= self.config.rows || col >= self.config.cols {
-            return None;
+    /// Window info for the members hidden behind their groups' active windows, which
+    /// aren't tracked. Each is given its active member's rect: hidden members only
+    /// move into the group's span when they are shown again.
+    pub fn hidden_group_member_infos(&self) -> Vec<WindowInfo> {
+        self.window_groups
+            .values()
+            .flat_map(|group| {
+                let active = group.active_window();
+                let rect = active.and_then(|hwnd| self.group_member_rect(hwnd));
+                group
+                    .members
+                    .iter()
+                    .filter(move |hwnd| Some(**hwnd) != active)
+                    .filter_map(move |hwnd| {
+                        let mut info = self.backend.get_window_info(*hwnd)?;
+                        if let Some(rect) = rect {
+                            info.window_rect = RectWrapper::from_rect(rect);
+                        }
+                        info.is_visible = false;
+                        Some(info)
+                    })
+            })
+            .collect()
+    }
+
+    /// True for grouped windows hidden behind their group's active member.
+    pub fn is_hidden_group_member(&self, hwnd: u64) -> bool {
+        self.window_group_of(hwnd)
+            .is_some_and(|group| group.active_window() != Some(hwnd))
+    }
+
+    /// Show a hidden group member, in `rect` if given, and track it again.
+    fn show_group_member(&mut self, hwnd: u64, rect: Option<Rect>) -> Result<(), String> {
+        self.backend.show_window(hwnd, WindowShowState::Focused)?;
+        if let Some(rect) = rect {
+            self.backend.move_window_to_rect(hwnd, rect)?;
         }
+        self.add_window(hwnd);
+        Ok(())
+    }
 
-        let grid_width = self.monitor_rect.right - self.monitor_rect.left;
-        let grid_height = self.monitor_rect.bottom - self.monitor_rect.top;
+    /// Hide a group member behind the active one and stop tracking it.
+    fn hide_group_member(&mut self, hwnd: u64) {
+        #[cfg(feature = "animation")]
+        self.active_animations.remove(&hwnd);
+        if let Err(e) = self.backend.show_window(hwnd, WindowShowState::Hidden) {
+            println!("⚠️ Failed to hide window 0x{:X}: {}", hwnd, e);
+        }
+        self.remove_window(hwnd);
+    }
 
-        let cell_width = grid_width / self.config.cols as i32;
-        let cell_height = grid_height / self.config.rows as i32;
+    /// Where a group's active member is, or is animating to.
+    fn group_member_rect(&self, hwnd: u64) -> Option<Rect> {
+        #[cfg(feature = "animation")]
+        if let Some(animation) = self.active_animations.get(&hwnd) {
+            return Some(animation.target_rect);
+        }
+        self.backend.get_window_rect(hwnd)
+    }
 
-        let left = self.monitor_rect.left + (col as i32 * cell_width);
-        let top = self.monitor_rect.top + (row as i32 * cell_height);
-        let right = left + cell_width;
-        let bottom = top + cell_height;
+    /// Put group member `to` on screen in place of `from`, taking its rect (or the end
+    /// of its animation) and floating state, then hide `from`.
+    fn switch_group_member(&mut self, from: u64, to: u64) -> Result<(), String> {
+        if from == to {
+            return Ok(());
+        }
+        let rect = self.group_member_rect(from);
+        let floating = self.floating_windows.contains(&from);
+        self.show_group_member(to, rect)?;
+        if floating {
+            self.floating_windows.insert(to);
+        }
+        self.hide_group_member(from);
+        Ok(())
+    }
 
-        Some(RECT {
-            left,
-            top,
-            right,
-            bottom,
-        })
+    /// Drop members of group `id` that were closed. If the active member closed, the
+    /// next one is shown where it was left; a group left with one window is dissolved.
+    fn prune_window_group(&mut self, id: u32) {
+        let Some(group) = self.window_groups.get_mut(&id) else {
+            return;
+        };
+        let active = group.active_window();
+        let closed: Vec<u64> = group
+            .members
+            .iter()
+            .copied()
+            .filter(|hwnd| !self.backend.is_window(*hwnd))
+            .collect();
+        for hwnd in &closed {
+            group.remove(*hwnd);
+        }
+        let next = group.active_window();
+        if group.members.len() < 2 {
+            self.window_groups.remove(&id);
+        }
+        let active_closed = active.is_some_and(|hwnd| closed.contains(&hwnd));
+        if let Some(next) = next.filter(|_| active_closed) {
+            if let Err(e) = self.show_group_member(next, None) {
+                println!("⚠️ Failed to show window 0x{:X}: {}", next, e);
+            }
+        }
     }
 
-    /// Move a window to a specific grid cell
-    pub fn move_window_to_cell(
+    /// Add `hwnd` to the group of `target`, grouping `target` first if it is on its
+    /// own. `hwnd` becomes the active member, taking the group's place on screen, and
+    /// leaves any group it was in. Returns the group id.
+    pub fn add_to_window_group(&mut self, hwnd: u64, target: u64) -> Result<u32, String> {
+        if hwnd == target {
+            return Err(format!("Can't group window 0x{:X} with itself", hwnd));
+        }
+        if !self.backend.is_window(hwnd) {
+            return Err(format!("Window 0x{:X} does not exist", hwnd));
+        }
+        let target_group = self.window_group_of(target).map(|group| group.id);
+        if target_group.is_none() && !self.windows.contains_key(&target) {
+            return Err(format!("Window 0x{:X} is not tracked", target));
+        }
+        if let Some(group) = self.window_group_of(target) {
+            if group.members.len() >= MAX_GROUP_MEMBERS {
+                return Err(format!(
+                    "Window group {} already holds {} windows",
+                    group.id, MAX_GROUP_MEMBERS
+                ));
+            }
+        }
+        if let Some(current) = self.window_group_of(hwnd).map(|group| group.id) {
+            if Some(current) == target_group {
+                return Err(format!(
+                    "Window 0x{:X} is already in group {}",
+                    hwnd, current
+                ));
+            }
+            self.remove_from_window_group(hwnd)?;
+        }
+        let id = target_group.unwrap_or_else(|| {
+            self.window_group_counter += 1;
+            let id = self.window_group_counter;
+            self.window_groups.insert(id, WindowGroup::new(id, target));
+            id
+        });
+        let Some(group) = self.window_groups.get_mut(&id) else {
+            return Err(format!("Window group {} does not exist", id));
+        };
+        let Some(previous) = group.active_window() else {
+            return Err(format!("Window group {} is empty", id));
+        };
+        group.add(hwnd);
+        self.switch_group_member(previous, hwnd)?;
+        println!("📑 Window 0x{:X} joined group {}", hwnd, id);
+        Ok(id)
+    }
+
+    /// Take `hwnd` out of its group and leave it on screen. If it was the active
+    /// member, the next one takes its place; a group left with one window is
+    /// dissolved. Returns the group id.
+    pub fn remove_from_window_group(&mut self, hwnd: u64) -> Result<u32, String> {
+        let id = self
+            .window_group_of(hwnd)
+            .map(|group| group.id)
+            .ok_or_else(|| format!("Window 0x{:X} is not in a group", hwnd))?;
+        let Some(group) = self.window_groups.get_mut(&id) else {
+            return Err(format!("Window group {} does not exist", id));
+        };
+        let was_active = group.active_window() == Some(hwnd);
+        group.remove(hwnd);
+        let next = group.active_window();
+        if group.members.len() < 2 {
+            self.window_groups.remove(&id);
+        }
+        // Whichever window comes out from behind the group is shown in its span, as
+        // hidden members don't follow the group while it moves
+        match next {
+            Some(next) if was_active => {
+                let rect = self.group_member_rect(hwnd);
+                self.show_group_member(next, rect)?;
+            }
+            Some(next) => {
+                let rect = self.group_member_rect(next);
+                self.show_group_member(hwnd, rect)?;
+            }
+            None => {}
+        }
+        println!("📑 Window 0x{:X} left group {}", hwnd, id);
+        Ok(id)
+    }
+
+    /// Show the next member of `hwnd`'s group, or the previous one when `forward` is
+    /// false, in place of the active one. Returns the member now shown.
+    pub fn cycle_window_group(&mut self, hwnd: u64, forward: bool) -> Result<u64, String> {
+        let id = self
+            .window_group_of(hwnd)
+            .map(|group| group.id)
+            .ok_or_else(|| format!("Window 0x{:X} is not in a group", hwnd))?;
+        self.prune_window_group(id);
+        let Some(group) = self.window_groups.get_mut(&id) else {
+            return Err(format!("Window group {} has no other windows left", id));
+        };
+        let (Some(previous), Some(next)) = (group.active_window(), group.cycle(forward)) else {
+            return Err(format!("Window group {} is empty", id));
+        };
+        self.switch_group_member(previous, next)?;
+        Ok(next)
+    }
+
+    /// Show group member `hwnd` in place of the active one, e.g. when its tab is
+    /// clicked.
+    pub fn activate_window_group_member(&mut self, hwnd: u64) -> Result<u32, String> {
+        let id = self
+            .window_group_of(hwnd)
+            .map(|group| group.id)
+            .ok_or_else(|| format!("Window 0x{:X} is not in a group", hwnd))?;
+        self.prune_window_group(id);
+        let Some(group) = self.window_groups.get_mut(&id) else {
+            return Err(format!("Window group {} has no other windows left", id));
+        };
+        let Some(previous) = group.active_window() else {
+            return Err(format!("Window group {} is empty", id));
+        };
+        if !group.activate(hwnd) {
+            return Err(format!("Window 0x{:X} was closed", hwnd));
+        }
+        self.switch_group_member(previous, hwnd)?;
+        Ok(id)
+    }
+
+    /// Start animating every window to its target as one batch, with one duration and
+    /// easing. Returns the batch id; [`Self::finished_animation_batches`] reports the
+    /// batch once all of its windows have stopped.
+    #[cfg(feature = "animation")]
+    pub fn start_animation_batch(
         &mut self,
-        hwnd: u64,
-        target_row: usize,
-        target_col: usize,
-    ) -> Result<(), String> {
-        if target_row >= self.config.rows || target_col >= self.config.cols {
+        targets: &[(u64, Rect)],
+        duration: Duration,
+        easing: EasingType,
+    ) -> u64 {
+        self.animation_batch_counter += 1;
+        let mut batch = AnimationBatch {
+            id: self.animation_batch_counter,
+            ..Default::default()
+        };
+        for (hwnd, target_rect) in targets {
+            match self.start_window_animation(*hwnd, *target_rect, duration, easing) {
+                // Windows already in place finish without animating
+                Ok(()) if self.active_animations.contains_key(hwnd) => {
+                    batch.pending.insert(*hwnd);
+                }
+                Ok(()) => {}
+                Err(e) => println!("⚠️ Failed to start animation for window {:?}: {}", hwnd, e),
+            }
+        }
+        batch.window_count = batch.pending.len();
+        let id = batch.id;
+        self.animation_batches.push(batch);
+        id
+    }
+
+    /// Batches whose windows have all stopped animating, oldest first. Each batch is
+    /// reported once.
+    #[cfg(feature = "animation")]
+    pub fn finished_animation_batches(&mut self) -> Vec<AnimationBatch> {
+        for batch in &mut self.animation_batches {
+            batch
+                .pending
+                .retain(|hwnd| self.active_animations.contains_key(hwnd));
+        }
+        let (finished, pending) = std::mem::take(&mut self.animation_batches)
+            .into_iter()
+            .partition(|batch| batch.pending.is_empty());
+        self.animation_batches = pending;
+        finished
+    }
+
+    /// Swap two windows, each animating into the other's rect. Returns the batch id.
+    #[cfg(feature = "animation")]
+    pub fn swap_windows(
+        &mut self,
+        hwnd_a: u64,
+        hwnd_b: u64,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<u64, String> {
+        if hwnd_a == hwnd_b {
+            return Err(format!("Can't swap window 0x{:X} with itself", hwnd_a));
+        }
+        let (Some(rect_a), Some(rect_b)) = (
+            self.backend.get_window_rect(hwnd_a),
+            self.backend.get_window_rect(hwnd_b),
+        ) else {
             return Err(format!(
-                "Invalid grid coordinates: ({}, {})",
-                target_row, target_col
+                "Failed to get rects for windows 0x{:X} and 0x{:X}",
+                hwnd_a, hwnd_b
             ));
+        };
+        let batch =
+            self.start_animation_batch(&[(hwnd_a, rect_b), (hwnd_b, rect_a)], duration, easing);
+        println!("🔀 Swapped window 0x{:X} with 0x{:X}", hwnd_a, hwnd_b);
+        Ok(batch)
+    }
+
+    /// Windows that take part in rotations on monitor `monitor_id`: visible, restored,
+    /// managed windows whose centre lies on it, leaving out floating windows.
+    #[cfg(feature = "animation")]
+    fn rotation_windows(&self, monitor_id: usize) -> Vec<(u64, Rect)> {
+        self.distribution_candidates()
+            .into_iter()
+            .filter(|(hwnd, rect)| {
+                !self.floating_windows.contains(hwnd)
+                    && self.monitor_span(rect).map(|(monitor, _)| monitor) == Some(monitor_id)
+            })
+            .collect()
+    }
+
+    /// Rotate the windows on monitor `monitor_id`: ordered clockwise around the centre
+    /// of its grid area, each window animates into the rect of the next one in
+    /// `direction`. See [`crate::grid::rotation`] for the ordering. Returns the batch id.
+    #[cfg(feature = "animation")]
+    pub fn rotate_monitor_windows(
+        &mut self,
+        monitor_id: usize,
+        direction: RotationDirection,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<u64, String> {
+        let grid = self
+            .monitor_grids
+            .get(monitor_id)
+            .ok_or_else(|| format!("Monitor {} does not exist", monitor_id))?;
+        let ordered = rotation::clockwise_order(
+            grid.grid_rect().center(),
+            &self.rotation_windows(monitor_id),
+        );
+        let targets = rotation::rotate_targets(&ordered, direction);
+        let batch = self.start_animation_batch(&targets, duration, easing);
+        println!(
+            "🔄 Rotated {} windows {:?} on monitor {}",
+            targets.len(),
+            direction,
+            monitor_id
+        );
+        Ok(batch)
+    }
+
+    /// Rotate windows across monitors: with monitors ordered left to right, every
+    /// monitor's windows move to the next monitor in `direction`, keeping their cells
+    /// scaled to its grid. Returns the batch id.
+    #[cfg(feature = "animation")]
+    pub fn rotate_windows_across_monitors(
+        &mut self,
+        direction: RotationDirection,
+        duration: Duration,
+        easing: EasingType,
+    ) -> Result<u64, String> {
+        let monitors: Vec<Rect> = self
+            .monitor_grids
+            .iter()
+            .map(|grid| grid.monitor_rect)
+            .collect();
+        if monitors.len() < 2 {
+            return Err("Rotating across monitors needs at least two monitors".to_string());
+        }
+        let next_monitor = rotation::rotate_monitors(&monitors, direction);
+        let mut targets = Vec::new();
+        for (monitor_id, &target_id) in next_monitor.iter().enumerate() {
+            let from = &self.monitor_grids[monitor_id].config;
+            let to = &self.monitor_grids[target_id].config;
+            let (from, to) = ((from.rows, from.cols), (to.rows, to.cols));
+            for (hwnd, rect) in self.rotation_windows(monitor_id) {
+                let Some((_, span)) = self.monitor_span(&rect) else {
+                    continue;
+                };
+                let cells = rotation::scale_span(&span, from, to);
+                let target_rect = self.span_to_rect(
+                    Some(target_id),
+                    (cells.top, cells.left),
+                    (cells.bottom, cells.right),
+                )?;
+                targets.push((hwnd, target_rect));
+            }
         }
+        let batch = self.start_animation_batch(&targets, duration, easing);
+        println!(
+            "🔄 Rotated {} windows {:?} across {} monitors",
+            targets.len(),
+            direction,
+            monitors.len()
+        );
+        Ok(batch)
+    }
 
-        // Validate window handle first
-        unsafe {
-            if IsWindow(hwnd as HWND) == 0 {
-                return Err(format!("Invalid window handle: {:?}", hwnd));
+    /// Start animating each window to its target. Returns how many started.
+    #[cfg(feature = "animation")]
+    fn animate_to_targets(
+        &mut self,
+        targets: &[(u64, Rect)],
+        duration: Duration,
+        easing: EasingType,
+    ) -> usize {
+        let mut animations_started = 0;
+        for (hwnd, target_rect) in targets {
+            match self.start_window_animation(*hwnd, *target_rect, duration, easing) {
+                Ok(_) => animations_started += 1,
+                Err(e) => println!("⚠️ Failed to start animation for window {:?}: {}", hwnd, e),
             }
         }
+        animations_started
+    }
 
-        // Check if window is manageable
-        if !Self::is_manageable_window(hwnd) {
-            return Err(format!("Window {:?} is not manageable", hwnd));
+    /// Step animations until none are left or `timeout` passes, e.g. to let windows
+    /// finish moving back before exiting.
+    #[cfg(feature = "animation")]
+    pub fn finish_animations(&mut self, timeout: Duration) {
+        let deadline = std::time::Instant::now() + timeout;
+        while !self.active_animations.is_empty() && std::time::Instant::now() < deadline {
+            self.update_animations();
+            std::thread::sleep(Duration::from_millis(16));
         }
+    }
 
-        // Debug: Show all monitors before positioning
-        self.list_all_monitors();
+    /// Windows a distribution spreads over the monitors, with their current rects:
+    /// visible, restored, manageable windows other than the desktop, oldest handle first.
+    pub fn distribution_candidates(&self) -> Vec<(u64, Rect)> {
+        let mut candidates: Vec<(u64, Rect)> = self
+            .windows
+            .iter()
+            .filter(|entry| entry.value().is_visible && !entry.value().is_minimized)
+            .map(|entry| *entry.key())
+            .filter(|hwnd| !self.is_desktop_hwnd(*hwnd) && self.backend.is_manageable_window(*hwnd))
+            .filter_map(|hwnd| Some((hwnd, self.backend.get_window_rect(hwnd)?)))
+            .collect();
+        candidates.sort_by_key(|(hwnd, _)| *hwnd);
+        candidates
+    }
 
-        // Get target rectangle on primary monitor instead of virtual grid
-        if let Some(target_rect) = self.primary_monitor_cell_to_rect(target_row, target_col) {
-            let primary_rect = self.get_primary_monitor_rect();
-            println!(
-                "🖥️  Using primary monitor: left={}, top={}, right={}, bottom={}",
-                primary_rect.left, primary_rect.top, primary_rect.right, primary_rect.bottom
-            );
-            println!(
-                "🖥️  Primary monitor dimensions: {}x{}",
-                primary_rect.right - primary_rect.left,
-                primary_rect.bottom - primary_rect.top
-            );
-            println!(
-                "🎯 Target cell ({}, {}) of grid ({}x{})",
-                target_row, target_col, self.config.rows, self.config.cols
-            );
-            println!(
-                "🎯 Calculated target rect: left={}, top={}, width={}, height={}",
-                target_rect.left,
-                target_rect.top,
-                target_rect.right - target_rect.left,
-                target_rect.bottom - target_rect.top
-            );
+    /// Area each monitor's windows are spread over, indexed by monitor id.
+    pub fn distribution_monitors(&self) -> Vec<Rect> {
+        self.monitor_grids
+            .iter()
+            .map(|grid| grid.grid_rect())
+            .collect()
+    }
 
-            // Validate that the target rectangle is within primary monitor bounds
-            if target_rect.left < primary_rect.left
-                || target_rect.top < primary_rect.top
-                || target_rect.right > primary_rect.right
-                || target_rect.bottom > primary_rect.bottom
-            {
-                println!("⚠️  WARNING: Target rectangle is outside primary monitor bounds!");
-                println!(
-                    "   Target: ({}, {}) to ({}, {})",
-                    target_rect.left, target_rect.top, target_rect.right, target_rect.bottom
-                );
-                println!(
-                    "   Monitor: ({}, {}) to ({}, {})",
-                    primary_rect.left, primary_rect.top, primary_rect.right, primary_rect.bottom
-                );
-            } else {
-                println!("✅ Target rectangle is within primary monitor bounds");
-            }
+    /// Catch `distributor` up with the windows that opened or closed since the last call.
+    /// Returns true if the distribution changed and should be applied again.
+    pub fn sync_distribution(&self, distributor: &mut Distributor) -> bool {
+        distributor.sync(&self.distribution_candidates())
+    }
 
+    /// Move every distributed window into its place across the monitors, animated if
+    /// the constraints ask for it. Returns the plan that was applied.
+    pub fn apply_distribution(&mut self, distributor: &Distributor) -> DistributionPlan {
+        let plan = distributor.plan(&self.distribution_monitors());
+        for monitor in &plan.monitors {
             println!(
-                "🎯 Moving window {:?} to rect: left={}, top={}, width={}, height={}",
-                hwnd,
-                target_rect.left,
-                target_rect.top,
-                target_rect.right - target_rect.left,
-                target_rect.bottom - target_rect.top
+                "🧮 Monitor {}: {} windows in a {}x{} grid",
+                monitor.monitor_id,
+                monitor.windows.len(),
+                monitor.rows,
+                monitor.cols
             );
+        }
+        self.move_to_targets(&plan.targets, distributor);
+        plan
+    }
 
-            // Safely move the window (this operation doesn't need the tracker lock)
-            unsafe {
-                let result = SetWindowPos(
-                    hwnd as HWND,
-                    ptr::null_mut(),
-                    target_rect.left,
-                    target_rect.top,
-                    target_rect.right - target_rect.left,
-                    target_rect.bottom - target_rect.top,
-                    SWP_NOZORDER | SWP_NOACTIVATE,
-                );
-
-                if result == 0 {
-                    let error = GetLastError();
-                    return Err(format!("SetWindowPos failed with error: {}", error));
-                }
-            }
+    /// Put every distributed window back where it was before it was first distributed.
+    pub fn restore_distribution(&mut self, distributor: &Distributor) {
+        self.move_to_targets(&distributor.restore_targets(), distributor);
+    }
 
-            println!("✅ Successfully moved window {:?}", hwnd);
+    /// Put every distributed window straight back, without animating, e.g. when the
+    /// server shuts down and nothing steps animations any more. Returns the windows
+    /// that could not be moved, with why.
+    pub fn restore_distribution_now(&mut self, distributor: &Distributor) -> Vec<(u64, String)> {
+        let targets = distributor.restore_targets();
+        #[cfg(feature = "animation")]
+        for (hwnd, _) in &targets {
+            self.active_animations.remove(hwnd);
+        }
+        self.place_at_targets(&targets)
+    }
 
-            // Update the grid tracking (keep this lock brief)
-            self.assign_window_to_virtual_cell(hwnd, target_row, target_col)?;
+    /// Move windows to their targets the way `distributor` asks: animated or at once.
+    #[cfg_attr(not(feature = "animation"), allow(unused_variables))]
+    fn move_to_targets(&mut self, targets: &[(u64, Rect)], distributor: &Distributor) {
+        #[cfg(feature = "animation")]
+        if let Some((duration_ms, easing)) = distributor.constraints.animation {
+            let duration = Duration::from_millis(duration_ms as u64);
+            self.animate_to_targets(targets, duration, easing);
+            return;
+        }
+        for (hwnd, e) in self.place_at_targets(targets) {
+            println!("⚠️ Failed to move window {:?}: {}", hwnd, e);
+        }
+    }
 
-            // Trigger callback notification if the window is tracked
-            if let Some(window_info) = self.windows.get(&hwnd) {
-                // Use a reference instead of cloning to avoid potential issues
-                self.trigger_window_moved(hwnd, &*window_info);
+    /// Move windows straight to their targets, leaving maximized ones alone. Returns
+    /// the windows that could not be moved, with why.
+    fn place_at_targets(&mut self, targets: &[(u64, Rect)]) -> Vec<(u64, String)> {
+        let mut failures = Vec::new();
+        for (hwnd, target_rect) in targets {
+            if self.backend.is_window_maximized(*hwnd) {
+                continue;
             }
-        } else {
-            return Err(format!(
-                "Could not calculate target rectangle for cell ({}, {})",
-                target_row, target_col
-            ));
+            if let Err(e) = self.place_window(*hwnd, *target_rect) {
+                failures.push((*hwnd, e));
+            }
+        }
+        failures
+    }
+
+    /// Move a managed window to `target_rect` and refresh its tracking.
+    fn place_window(&mut self, hwnd: u64, target_rect: Rect) -> Result<(), String> {
+        if !self.backend.is_window(hwnd) {
+            return Err(format!("Invalid window handle: {:?}", hwnd));
+        }
+        if !self.backend.is_manageable_window(hwnd) {
+            return Err(format!("Window {:?} is not manageable", hwnd));
         }
 
+        self.backend.move_window_to_rect(hwnd, target_rect)?;
+        self.update_window(hwnd);
         Ok(())
     }
 
@@ -1685,6 +3321,7 @@ impl WindowTracker {
                 target_row, target_col
             ));
         }
+        let (target_row, target_col) = self.onscreen_virtual_cell(target_row, target_col)?;
 
         // Clear the old position
         for row in 0..self.config.rows {
@@ -1786,6 +3423,7 @@ impl WindowTracker {
     }
 
     /// Returns the process ID for the given window handle, or None if not found.
+    #[cfg(feature = "win32")]
     pub fn get_window_process_id(hwnd: u64) -> Option<u32> {
         let mut process_id: u32 = 0;
         unsafe {
@@ -1799,99 +3437,37 @@ impl WindowTracker {
     }
 
     /// Returns the class name for the given window handle, or an empty string if not found.
+    #[cfg(feature = "win32")]
     pub fn get_window_class_name(hwnd: u64) -> String {
-        let mut class_name_buf = [0u16; 256];
-        let class_name_len = unsafe {
-            GetClassNameW(
-                hwnd as HWND,
-                class_name_buf.as_mut_ptr(),
-                class_name_buf.len() as i32,
-            )
-        };
-        if class_name_len > 0 {
-            String::from_utf16_lossy(&class_name_buf[..class_name_len as usize])
-        } else {
-            String::new()
-        }
+        Win32Backend::new().get_window_class_name(hwnd)
     }
 
     /// Returns true if the window is visible.
+    #[cfg(feature = "win32")]
     pub fn is_window_visible(hwnd: u64) -> bool {
-        unsafe { IsWindowVisible(hwnd as HWND) != 0 }
+        Win32Backend::new().is_window_visible(hwnd)
     }
 
     /// Returns true if the window is minimized (iconic).
+    #[cfg(feature = "win32")]
     pub fn is_window_minimized(hwnd: u64) -> bool {
         unsafe { IsIconic(hwnd as HWND) != 0 }
     }
 
     /// Returns true if the window is maximized.
+    #[cfg(feature = "win32")]
     pub fn is_window_maximized(hwnd: u64) -> bool {
-        unsafe {
-            if IsWindow(hwnd as HWND) == 0 {
-                return false;
-            }
-            // Check window placement
-            let mut placement = std::mem::zeroed::<WINDOWPLACEMENT>();
-            placement.length = std::mem::size_of::<WINDOWPLACEMENT>() as u32;
-            if GetWindowPlacement(hwnd as HWND, &mut placement) != 0 {
-                if placement.showCmd == SW_MAXIMIZE as u32 {
-                    return true;
-                }
-            }
-            // Fallback to IsZoomed and WS_MAXIMIZE style
-            let is_zoomed = IsZoomed(hwnd as HWND) != 0;
-            let style = GetWindowLongW(hwnd as HWND, GWL_STYLE) as u32;
-            let is_maximized_style = (style & WS_MAXIMIZE) != 0;
-            if is_zoomed || is_maximized_style {
-                return true;
-            }
-            // Check if window rect matches any monitor rect
-            if let Some(rect) = Self::get_window_rect(hwnd) {
-                // Get all monitor bounds
-                let monitor_rects = Self::get_actual_monitor_bounds_static();
-                for monitor_rect in monitor_rects {
-                    if rect.left == monitor_rect.left
-                        && rect.top == monitor_rect.top
-                        && rect.right == monitor_rect.right
-                        && rect.bottom == monitor_rect.bottom
-                    {
-                        return true;
-                    }
-                }
-            }
-            false
-        }
+        Win32Backend::new().is_window_maximized(hwnd)
     }
 
-    // Helper for static monitor bounds (for use in static fn)
-    fn get_actual_monitor_bounds_static() -> Vec<RECT> {
-        let mut monitors = Vec::new();
-        unsafe {
-            extern "system" fn monitor_enum_proc(
-                _hmonitor: winapi::shared::windef::HMONITOR,
-                _hdc: winapi::shared::windef::HDC,
-                rect: *mut RECT,
-                data: LPARAM,
-            ) -> i32 {
-                let monitors = unsafe { &mut *(data as *mut Vec<RECT>) };
-                monitors.push(unsafe { *rect });
-                1
-            }
-            EnumDisplayMonitors(
-                ptr::null_mut(),
-                ptr::null(),
-                Some(monitor_enum_proc),
-                &mut monitors as *mut Vec<RECT> as LPARAM,
-            );
-        }
-        monitors
+    pub fn set_grid_size(&mut self, rows: usize, cols: usize) {
+        self.set_grid_config(GridConfig::new(rows, cols));
     }
 
-    pub fn set_grid_size(&mut self, rows: usize, cols: usize) {
-        self.config.rows = rows;
-        self.config.cols = cols;
-        self.grid = vec![vec![CellState::Empty; cols]; rows];
+    /// Replace the grid layout, including any weighted row/column tracks.
+    pub fn set_grid_config(&mut self, config: GridConfig) {
+        self.grid = vec![vec![CellState::Empty; config.cols]; config.rows];
+        self.config = config;
         self.initialize_grid();
         self.update_grid();
         self.initialize_monitor_grids();
@@ -1899,6 +3475,7 @@ impl WindowTracker {
     }
 
     /// Add window creation/destruction detection
+    #[cfg(feature = "ipc")]
     pub fn detect_window_lifecycle_events(&mut self) -> Vec<crate::ipc_protocol::GridEvent> {
         let mut events = Vec::new();
 
@@ -1985,67 +3562,13 @@ impl WindowTracker {
     }
 
     /// Call this periodically to detect window lifecycle changes
+    #[cfg(feature = "ipc")]
     pub fn update_window_lifecycle(&mut self) -> Vec<crate::ipc_protocol::GridEvent> {
         self.detect_window_lifecycle_events()
     }
 
     /// Enumerate all windows currently visible to the system and return a DashMap of them
     pub fn enumerate_windows(&mut self) -> DashMap<u64, WindowInfo> {
-        let found_windows = DashMap::new();
-
-        // Callback for EnumWindows to collect windows
-        unsafe extern "system" fn enum_windows_proc_collect(hwnd: HWND, lparam: LPARAM) -> i32 {
-            let found_windows = &*(lparam as *const DashMap<u64, WindowInfo>);
-            // println!("Checking window: {:?}", hwnd);
-            if WindowTracker::is_manageable_window(hwnd as u64) {
-                // println!("Found manageable window: {:?}", hwnd);
-                if let Some(rect) = WindowTracker::get_window_rect(hwnd as u64) {
-                    // println!(
-                    //     "[ENUM] Window HWND=0x{:X} rect: left={}, top={}, right={}, bottom={} (w={}, h={})",
-                    //     hwnd as u64, rect.left, rect.top, rect.right, rect.bottom, rect.right-rect.left, rect.bottom-rect.top
-                    // );
-                    let mut title_buf = [0u16; 256];
-                    let title_len =
-                        GetWindowTextW(hwnd, title_buf.as_mut_ptr(), title_buf.len() as i32);
-                    let mut class_name_buf = [0u16; 256];
-                    let class_name_len = GetClassNameW(
-                        hwnd,
-                        class_name_buf.as_mut_ptr(),
-                        class_name_buf.len() as i32,
-                    );
-                    let is_visible = IsWindowVisible(hwnd) != 0;
-                    let is_minimized = IsIconic(hwnd) != 0;
-                    let is_maximized = WindowTracker::is_window_maximized(hwnd as u64);
-                    let mut process_id: u32 = 0;
-                    GetWindowThreadProcessId(hwnd, &mut process_id);
-
-                    let window_info = WindowInfo {
-                        hwnd: hwnd as u64,
-                        title: title_buf,
-                        title_len: title_len as u32,
-                        monitor_ids: [0usize; 8],
-                        z_order: 0,
-                        window_rect: RectWrapper::from_rect(rect),
-                        is_visible,
-                        is_minimized,
-                        is_maximized,
-                        process_id,
-                        class_name: class_name_buf,
-                        class_name_len: class_name_len as u32,
-                    };
-                    found_windows.insert(hwnd as u64, window_info);
-                }
-            }
-            1
-        }
-
-        unsafe {
-            EnumWindows(
-                Some(enum_windows_proc_collect),
-                &found_windows as *const _ as LPARAM,
-            );
-        }
-
-        found_windows
+        self.backend.enumerate_windows()
     }
 }
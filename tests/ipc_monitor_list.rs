@@ -1,5 +1,9 @@
 //! Integration test: server-client monitor list exchange
-use e_grid::ipc_protocol::{IpcCommand, IpcCommandType, IpcResponseType};
+use e_grid::ipc_protocol::{
+    validate_grid_config, CellRunAssembler, GridCellRunsMessage, IpcCommand, IpcCommandType,
+    IpcResponseType, MonitorGridIPC, MonitorList, MonitorListAssembler, MAX_CELL_PATH_DEPTH,
+    MAX_CELL_RUNS, MAX_MONITORS, PROTOCOL_VERSION,
+};
 use e_grid::ipc_server::GridIpcServer;
 use e_grid::WindowTracker;
 use iceoryx2::port::publisher::Publisher;
@@ -58,11 +62,21 @@ fn test_monitor_list_exchange() {
         hwnd: None,
         target_row: None,
         target_col: None,
+        end_row: None,
+        end_col: None,
         monitor_id: None,
         layout_id: None,
         animation_duration_ms: None,
         easing_type: None,
-        protocol_version: 1,
+        occupancy_strategy: None,
+        tiling_algorithm: None,
+        direction: None,
+        other_hwnd: None,
+        rotation: None,
+        name: [0; 32],
+        cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+        cell_path_len: 0,
+        protocol_version: PROTOCOL_VERSION,
     };
     command_publisher.send_copy(cmd).unwrap();
     // Process one command in the server
@@ -102,11 +116,21 @@ fn test_error_response_on_unsupported_command() {
         hwnd: None,
         target_row: Some(0),
         target_col: Some(0),
+        end_row: None,
+        end_col: None,
         monitor_id: None,
         layout_id: None,
         animation_duration_ms: None,
         easing_type: None,
-        protocol_version: 1,
+        occupancy_strategy: None,
+        tiling_algorithm: None,
+        direction: None,
+        other_hwnd: None,
+        rotation: None,
+        name: [0; 32],
+        cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+        cell_path_len: 0,
+        protocol_version: PROTOCOL_VERSION,
     };
     command_publisher.send_copy(cmd).unwrap();
     // Process one command in the server
@@ -131,6 +155,30 @@ fn test_error_response_on_unsupported_command() {
     );
 }
 
+#[test]
+#[serial]
+fn test_mismatched_protocol_version_is_rejected() {
+    let (mut server, _node, command_publisher, response_subscriber) = setup_server_and_client();
+    let cmd = IpcCommand {
+        command_type: IpcCommandType::GetMonitorList,
+        protocol_version: PROTOCOL_VERSION - 1,
+        ..Default::default()
+    };
+    command_publisher.send_copy(cmd).unwrap();
+    server.process_commands().unwrap();
+    let mut response = None;
+    for _ in 0..10 {
+        if let Some(sample) = response_subscriber.receive().unwrap() {
+            response = Some(*sample);
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    let response = response.expect("Did not receive a response from server");
+    assert_eq!(response.response_type, IpcResponseType::Error);
+    assert_eq!(response.has_error_message, 1);
+}
+
 #[test]
 #[serial]
 fn test_multiple_monitor_list_requests() {
@@ -142,11 +190,21 @@ fn test_multiple_monitor_list_requests() {
             hwnd: None,
             target_row: None,
             target_col: None,
+            end_row: None,
+            end_col: None,
             monitor_id: None,
             layout_id: None,
             animation_duration_ms: None,
             easing_type: None,
-            protocol_version: 1,
+            occupancy_strategy: None,
+            tiling_algorithm: None,
+            direction: None,
+            other_hwnd: None,
+            rotation: None,
+            name: [0; 32],
+            cell_path: [[0; 2]; MAX_CELL_PATH_DEPTH],
+            cell_path_len: 0,
+            protocol_version: PROTOCOL_VERSION,
         };
         command_publisher.send_copy(cmd).unwrap();
         server.process_commands().unwrap();
@@ -173,3 +231,107 @@ fn test_multiple_monitor_list_requests() {
         );
     }
 }
+
+#[test]
+fn test_chunks_of_older_publishes_are_ignored() {
+    let checkerboard = |value: u64| -> Vec<Vec<u64>> {
+        (0..40)
+            .map(|row| {
+                (0..48)
+                    .map(|col| if (row + col) % 2 == 0 { value } else { 0 })
+                    .collect()
+            })
+            .collect()
+    };
+    let older = GridCellRunsMessage::encode(2, 7, &checkerboard(0x1000));
+    let newer = GridCellRunsMessage::encode(2, 8, &checkerboard(0x2000));
+    assert!(older.len() > 1 && newer.len() > 1);
+
+    // A late chunk of snapshot 7 neither restarts nor corrupts snapshot 8
+    let mut assembler = CellRunAssembler::default();
+    assert!(assembler.push(&older[0]).is_none());
+    assert!(assembler.push(&newer[0]).is_none());
+    assert!(assembler.push(&older[1]).is_none());
+    let (last, middle) = newer[1..].split_last().unwrap();
+    for message in middle {
+        assert!(assembler.push(message).is_none());
+    }
+    assert_eq!(assembler.push(last), Some(checkerboard(0x2000)));
+    for message in &older {
+        assert!(assembler.push(message).is_none());
+    }
+
+    let monitors = |first_id: u32| -> Vec<MonitorGridIPC> {
+        (first_id..first_id + MAX_MONITORS as u32 + 5)
+            .map(|monitor_id| MonitorGridIPC {
+                monitor_id,
+                ..Default::default()
+            })
+            .collect()
+    };
+    let older = MonitorList::chunks(&monitors(0), 1);
+    let newer = MonitorList::chunks(&monitors(100), 2);
+    let mut assembler = MonitorListAssembler::default();
+    assert!(assembler.push(&newer[0]).is_none());
+    assert!(assembler.push(&older[1]).is_none());
+    let received = assembler.push(&newer[1]).unwrap();
+    assert_eq!(received[0].monitor_id, 100);
+    assert_eq!(received.len(), monitors(100).len());
+    assert!(assembler.push(&older[0]).is_none());
+    assert!(assembler.push(&older[1]).is_none());
+}
+
+#[test]
+fn test_grids_larger_than_32x32() {
+    // 48-column ultrawide grid with a checkerboard of windows, so it needs many chunks
+    let grid: Vec<Vec<u64>> = (0..40)
+        .map(|row| {
+            (0..48)
+                .map(|col| match (row + col) % 3 {
+                    0 => 0,
+                    1 => 0x1000 + col as u64,
+                    _ => u64::MAX,
+                })
+                .collect()
+        })
+        .collect();
+    let messages = GridCellRunsMessage::encode(2, 7, &grid);
+    assert!(messages.len() > 1);
+    assert!(messages
+        .iter()
+        .all(|m| m.run_count as usize <= MAX_CELL_RUNS));
+
+    // Chunks may arrive in any order; only the last completes the grid
+    let mut assembler = CellRunAssembler::default();
+    let (first, rest) = messages.split_first().unwrap();
+    for message in rest {
+        assert!(assembler.push(message).is_none());
+    }
+    assert_eq!(assembler.push(first), Some(grid.clone()));
+
+    // A newer snapshot replaces a partial older one
+    let empty = vec![vec![0; 48]; 40];
+    assert!(assembler.push(&messages[1]).is_none());
+    let newer = GridCellRunsMessage::encode(2, 8, &empty);
+    assert_eq!(newer.len(), 1);
+    assert_eq!(assembler.push(&newer[0]), Some(empty));
+
+    // More monitors than fit in one MonitorList
+    let monitors: Vec<MonitorGridIPC> = (0..MAX_MONITORS as u32 + 5)
+        .map(|monitor_id| MonitorGridIPC {
+            monitor_id,
+            ..Default::default()
+        })
+        .collect();
+    let chunks = MonitorList::chunks(&monitors, 1);
+    assert_eq!(chunks.len(), 2);
+    let mut assembler = MonitorListAssembler::default();
+    assert!(assembler.push(&chunks[0]).is_none());
+    let received = assembler.push(&chunks[1]).unwrap();
+    assert_eq!(received.len(), monitors.len());
+    assert_eq!(received[MAX_MONITORS].monitor_id, MAX_MONITORS as u32);
+
+    assert!(validate_grid_config(&e_grid::GridConfig::new(8, 48)).is_ok());
+    assert!(validate_grid_config(&e_grid::GridConfig::new(8, 300)).is_err());
+    assert!(validate_grid_config(&e_grid::GridConfig::new(0, 12)).is_err());
+}
@@ -0,0 +1,959 @@
+//! Integration test: WindowTracker features on a simulated desktop
+use e_grid::backend::{SimulatedDesktop, SimulatedWindow};
+use e_grid::config::{
+    CellOccupancyStrategy, CellPath, GridArea, GridConfig, SnapConfig, SnapPolicy, SnapRule,
+    TrackSize, ZoneLayout, MAX_CELL_PATH_DEPTH,
+};
+use e_grid::grid::{DistributionConstraints, Distributor, MAX_GROUP_MEMBERS};
+use e_grid::{CellState, Point, Rect, WindowTracker};
+use std::sync::Arc;
+
+/// A tracker over `desktop` on a grid of `config`, having scanned its windows.
+fn scanned_tracker(desktop: &Arc<SimulatedDesktop>, config: GridConfig) -> WindowTracker {
+    let mut tracker = WindowTracker::new_with_backend(config, desktop.clone());
+    tracker.scan_existing_windows();
+    tracker
+}
+
+/// Two 1200x800 monitors side by side.
+fn two_monitors() -> Arc<SimulatedDesktop> {
+    Arc::new(SimulatedDesktop::new(vec![
+        Rect::new(0, 0, 1200, 800),
+        Rect::new(1200, 0, 2400, 800),
+    ]))
+}
+
+#[test]
+fn test_tracker_on_simulated_desktop() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1920, 1080));
+    let hwnd = desktop.add_window("Editor", "Notepad", Rect::new(0, 0, 960, 540));
+
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    assert!(tracker.windows.contains_key(&hwnd));
+    assert!(matches!(tracker.grid[0][0], CellState::Occupied(h) if h == hwnd));
+    assert!(matches!(tracker.grid[1][1], CellState::Empty));
+
+    tracker.move_window_to_cell(hwnd, 1, 1).unwrap();
+    let moved = desktop.window(hwnd).unwrap().rect;
+    assert_eq!(
+        (moved.left, moved.top, moved.right, moved.bottom),
+        (960, 540, 1920, 1080)
+    );
+}
+
+#[test]
+fn test_monitor_grid_follows_work_area() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1920, 1080));
+    let hwnd = desktop.add_window("Editor", "Notepad", Rect::new(0, 0, 960, 540));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    assert!(tracker.refresh_monitor_areas().is_empty());
+
+    // A 40px taskbar docks at the bottom
+    desktop.set_work_area(0, Rect::new(0, 0, 1920, 1040));
+    assert_eq!(tracker.refresh_monitor_areas(), vec![0]);
+    let monitor = &tracker.monitor_grids[0];
+    assert_eq!(monitor.monitor_rect, Rect::new(0, 0, 1920, 1080));
+    assert_eq!(monitor.grid_rect(), Rect::new(0, 0, 1920, 1040));
+
+    tracker.move_window_to_cell(hwnd, 1, 0).unwrap();
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(0, 520, 960, 1040)
+    );
+
+    // Opting back into the full bounds sticks across rebuilds
+    tracker.set_monitor_grid_area(0, GridArea::Bounds).unwrap();
+    desktop.set_work_area(0, Rect::new(0, 0, 1920, 1000));
+    assert_eq!(tracker.refresh_monitor_areas(), vec![0]);
+    assert_eq!(
+        tracker.monitor_grids[0].grid_rect(),
+        Rect::new(0, 0, 1920, 1080)
+    );
+    assert!(tracker.set_monitor_grid_area(1, GridArea::Bounds).is_err());
+}
+
+#[test]
+fn test_move_window_to_span() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1920, 1080));
+    let hwnd = desktop.add_window("Editor", "Notepad", Rect::new(0, 0, 100, 100));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 3));
+
+    // Left two-thirds, corners given bottom-right first
+    tracker
+        .move_window_to_span(hwnd, Some(0), (1, 1), (0, 0))
+        .unwrap();
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(0, 0, 1280, 1080)
+    );
+    let cells = &tracker.monitor_grids[0].grid;
+    assert!(matches!(cells[1][1], CellState::Occupied(h) if h == hwnd));
+    assert!(matches!(cells[0][2], CellState::Empty));
+
+    // Top-right cell on the virtual grid
+    tracker
+        .move_window_to_span(hwnd, None, (0, 2), (0, 2))
+        .unwrap();
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(1280, 0, 1920, 540)
+    );
+
+    assert!(tracker
+        .move_window_to_span(hwnd, None, (0, 0), (2, 0))
+        .is_err());
+    assert!(tracker
+        .move_window_to_span(hwnd, Some(1), (0, 0), (0, 0))
+        .is_err());
+}
+
+#[test]
+fn test_subdivided_cell() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1920, 1080));
+    let hwnd = desktop.add_window("Chat", "Notepad", Rect::new(0, 0, 100, 100));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+
+    let corner = CellPath::new(0, 1);
+    tracker
+        .subdivide_cell(0, &corner, Some(GridConfig::new(3, 1)))
+        .unwrap();
+    let path = corner.child(2, 0);
+    tracker.move_window_to_cell_path(hwnd, 0, &path).unwrap();
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(960, 360, 1920, 540)
+    );
+
+    // Occupancy is reported for the sub-cell and for the cell it sits in
+    let monitor = &tracker.monitor_grids[0];
+    assert!(matches!(monitor.subgrid_cells[&path], CellState::Occupied(h) if h == hwnd));
+    assert!(matches!(
+        monitor.subgrid_cells[&corner.child(0, 0)],
+        CellState::Empty
+    ));
+    assert!(matches!(monitor.grid[0][1], CellState::Occupied(h) if h == hwnd));
+
+    // Sub-grids survive a rebuild of the monitor grid
+    desktop.set_work_area(0, Rect::new(0, 0, 1920, 1080 - 60));
+    assert_eq!(tracker.refresh_monitor_areas(), vec![0]);
+    assert_eq!(
+        tracker.cell_path_to_rect(0, &path),
+        Ok(Rect::new(960, 340, 1920, 510))
+    );
+
+    tracker.subdivide_cell(0, &corner, None).unwrap();
+    assert!(tracker.move_window_to_cell_path(hwnd, 0, &path).is_err());
+    assert!(tracker
+        .subdivide_cell(0, &CellPath::new(2, 0), Some(GridConfig::new(2, 2)))
+        .is_err());
+}
+
+#[test]
+fn test_offscreen_virtual_cells() {
+    // A landscape monitor beside a taller portrait one leaves dead space bottom-left
+    let desktop = Arc::new(SimulatedDesktop::new(vec![
+        Rect::new(0, 0, 1920, 1080),
+        Rect::new(1920, 0, 3000, 1920),
+    ]));
+    let hwnd = desktop.add_window("Editor", "Notepad", Rect::new(0, 500, 600, 1500));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(4, 5));
+
+    // The window hangs into the dead space without claiming it
+    assert!(matches!(tracker.grid[2][0], CellState::Occupied(h) if h == hwnd));
+    assert!(matches!(tracker.grid[3][0], CellState::OffScreen));
+    assert!(matches!(tracker.grid[3][3], CellState::Empty));
+
+    // Moves into the dead space land on the nearest cells that are on a monitor
+    tracker
+        .move_window_to_span(hwnd, None, (3, 0), (3, 1))
+        .unwrap();
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(0, 960, 1200, 1440)
+    );
+    tracker.assign_window_to_virtual_cell(hwnd, 3, 2).unwrap();
+    assert!(matches!(tracker.grid[2][2], CellState::Occupied(h) if h == hwnd));
+    assert!(matches!(tracker.grid[3][2], CellState::OffScreen));
+    assert!(tracker
+        .move_window_to_span(hwnd, None, (4, 0), (4, 0))
+        .is_err());
+}
+
+#[test]
+fn test_snap_after_drag() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1200, 900));
+    let hwnd = desktop.add_window("Editor", "Notepad", Rect::new(0, 0, 100, 100));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(3, 4));
+
+    // Off by default
+    desktop.set_window_rect(hwnd, Rect::new(280, 20, 880, 560));
+    assert_eq!(tracker.snap_window(hwnd, None), Ok(None));
+
+    // Edges move to the nearest boundaries: columns 1-2, rows 0-1
+    tracker.set_snap_config(SnapConfig::new(SnapPolicy::CoveredSpan));
+    assert_eq!(
+        tracker.snap_window(hwnd, None),
+        Ok(Some(Rect::new(300, 0, 900, 600)))
+    );
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(300, 0, 900, 600)
+    );
+
+    // The cell holding the window's centre (650, 350)
+    tracker.set_snap_config(SnapConfig::new(SnapPolicy::NearestCell));
+    desktop.set_window_rect(hwnd, Rect::new(400, 200, 900, 500));
+    assert_eq!(
+        tracker.snap_target(hwnd, None),
+        Ok(Rect::new(600, 300, 900, 600))
+    );
+
+    // The zone under the cursor, or the cell under it without zones
+    tracker.set_snap_config(SnapConfig::new(SnapPolicy::ZoneUnderCursor));
+    let cursor = Some(Point::new(100, 850));
+    assert_eq!(
+        tracker.snap_target(hwnd, cursor),
+        Ok(Rect::new(0, 600, 300, 900))
+    );
+    tracker
+        .set_monitor_zone_layout(0, Some(ZoneLayout::priority_grid()))
+        .unwrap();
+    assert_eq!(
+        tracker.snap_target(hwnd, cursor),
+        Ok(Rect::new(0, 0, 300, 900))
+    );
+
+    // Excluded and maximized windows stay put
+    tracker.set_snap_config(
+        SnapConfig::new(SnapPolicy::CoveredSpan).with_exclusion(SnapRule::title("edit")),
+    );
+    assert_eq!(tracker.snap_window(hwnd, None), Ok(None));
+    tracker.set_snap_config(SnapConfig::new(SnapPolicy::CoveredSpan));
+    desktop.set_maximized(hwnd, true);
+    assert_eq!(tracker.snap_window(hwnd, None), Ok(None));
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(400, 200, 900, 500)
+    );
+}
+
+#[test]
+fn test_subdivide_cell_respects_grid_limits() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1920, 1080));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    assert!(tracker
+        .subdivide_cell(0, &CellPath::new(0, 0), Some(GridConfig::new(2, 300)))
+        .is_err());
+    assert!(tracker.monitor_subgrids.is_empty());
+
+    // Splits nest until a cell path would be deeper than commands can address
+    let mut path = CellPath::default();
+    for depth in 0..MAX_CELL_PATH_DEPTH {
+        path = path.child(0, 0);
+        let result = tracker.subdivide_cell(0, &path, Some(GridConfig::new(2, 2)));
+        assert_eq!(
+            result.is_ok(),
+            depth + 1 < MAX_CELL_PATH_DEPTH,
+            "depth {}",
+            depth
+        );
+    }
+}
+
+#[test]
+fn test_zone_layout() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1600, 900));
+    let hwnd = desktop.add_window("Editor", "Notepad", Rect::new(0, 0, 100, 100));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+
+    assert!(tracker.move_window_to_zone(hwnd, 0, "Center").is_err());
+    tracker
+        .set_monitor_zone_layout(0, Some(ZoneLayout::priority_grid()))
+        .unwrap();
+    tracker.move_window_to_zone(hwnd, 0, "Center").unwrap();
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(400, 0, 1200, 900)
+    );
+    assert_eq!(
+        tracker.monitor_grids[0].zone_owners,
+        vec![None, Some(hwnd), None]
+    );
+    assert!(tracker.move_window_to_zone(hwnd, 0, "Top").is_err());
+
+    // Saved layouts remember zone owners and put windows back into their zones
+    tracker.save_current_layout("focus".to_string());
+    tracker.move_window_to_zone(hwnd, 0, "Left").unwrap();
+    let layout = tracker.get_saved_layout("focus").unwrap();
+    assert_eq!(layout.monitor_grids[0].zone_owners[1], Some(hwnd));
+    #[cfg(feature = "animation")]
+    {
+        let easing = e_grid::EasingType::Linear;
+        tracker
+            .apply_grid_layout(&layout, std::time::Duration::from_millis(200), easing)
+            .unwrap();
+        let animation = tracker.active_animations.get(&hwnd).unwrap();
+        assert_eq!(animation.target_rect, Rect::new(400, 0, 1200, 900));
+    }
+}
+
+#[test]
+fn test_monitor_tiling() {
+    let desktop = two_monitors();
+    let back = desktop.add_window("Back", "Notepad", Rect::new(10, 10, 300, 300));
+    let front = desktop.add_window("Front", "Notepad", Rect::new(100, 100, 500, 500));
+    let other = desktop.add_window("Other", "Notepad", Rect::new(1300, 100, 1600, 400));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+
+    // Only windows on the monitor are tiled, the topmost one as master
+    assert_eq!(tracker.tiled_windows(0), Ok(vec![front, back]));
+    assert_eq!(tracker.tiled_windows(1), Ok(vec![other]));
+    assert_eq!(tracker.tiling_targets(0), Ok(Vec::new()));
+    tracker
+        .monitor_tilings
+        .insert(0, Arc::new(e_grid::grid::MasterStack::default()));
+    assert_eq!(
+        tracker.tiling_targets(0),
+        Ok(vec![
+            (front, Rect::new(0, 0, 660, 800)),
+            (back, Rect::new(660, 0, 1200, 800)),
+        ])
+    );
+    assert!(tracker.tiling_targets(2).is_err());
+
+    #[cfg(feature = "animation")]
+    {
+        let easing = e_grid::EasingType::Linear;
+        let duration = std::time::Duration::from_millis(200);
+        let started = tracker
+            .set_monitor_tiling(1, Some(Arc::new(e_grid::grid::Monocle)), duration, easing)
+            .unwrap();
+        assert_eq!(started, 1);
+        let animation = tracker.active_animations.get(&other).unwrap();
+        assert_eq!(animation.target_rect, Rect::new(1200, 0, 2400, 800));
+        drop(animation);
+        assert_eq!(tracker.set_monitor_tiling(1, None, duration, easing), Ok(0));
+        assert_eq!(tracker.tiling_targets(1), Ok(Vec::new()));
+    }
+}
+
+#[cfg(feature = "animation")]
+#[test]
+fn test_retile_changed_monitors() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1200, 800));
+    let first = desktop.add_window("First", "Notepad", Rect::new(10, 10, 300, 300));
+    let second = desktop.add_window("Second", "Notepad", Rect::new(100, 100, 500, 500));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    let easing = e_grid::EasingType::Linear;
+    let duration = std::time::Duration::from_millis(200);
+    let columns: Arc<dyn e_grid::grid::LayoutAlgorithm> = Arc::new(e_grid::grid::Columns);
+    tracker
+        .set_monitor_tiling(0, Some(columns.clone()), duration, easing)
+        .unwrap();
+    tracker.finish_animations(duration * 4);
+    assert_eq!(tracker.tiling_monitor_of(first), Some(0));
+
+    // Nothing joined or left, even though focus reordered the windows
+    desktop.bring_to_front(first);
+    assert!(tracker.retile_changed_monitors(duration, easing).is_empty());
+
+    // A new window joins the layout
+    let third = desktop.add_window("Third", "Notepad", Rect::new(50, 50, 200, 200));
+    tracker.add_window(third);
+    assert_eq!(tracker.retile_changed_monitors(duration, easing), vec![0]);
+    assert_eq!(tracker.tiled_arrangements[&0].len(), 3);
+
+    // Minimized windows leave it, and so do windows dragged off their tile
+    tracker.finish_animations(duration * 4);
+    assert_eq!(tracker.float_if_moved_off_tile(second), None);
+    desktop.set_minimized(third, true);
+    desktop.set_window_rect(second, Rect::new(100, 500, 300, 700));
+    assert_eq!(tracker.float_if_moved_off_tile(second), Some(0));
+    assert_eq!(tracker.retile_changed_monitors(duration, easing), vec![0]);
+    assert_eq!(
+        tracker.tiling_targets(0),
+        Ok(vec![(first, Rect::new(0, 0, 1200, 800))])
+    );
+    tracker.finish_animations(duration * 4);
+    assert_eq!(
+        desktop.window(first).unwrap().rect,
+        Rect::new(0, 0, 1200, 800)
+    );
+
+    // Tiling the monitor again brings floating windows back
+    tracker
+        .set_monitor_tiling(0, Some(columns), duration, easing)
+        .unwrap();
+    assert!(tracker.floating_windows.is_empty());
+    assert_eq!(tracker.tiled_arrangements[&0].len(), 2);
+}
+
+#[test]
+fn test_distribute_across_monitors() {
+    let desktop = Arc::new(SimulatedDesktop::new(vec![
+        Rect::new(0, 0, 1600, 900),
+        Rect::new(1600, 0, 2400, 900),
+    ]));
+    let first = desktop.add_window("First", "Notepad", Rect::new(10, 10, 300, 300));
+    let second = desktop.add_window("Second", "Notepad", Rect::new(20, 20, 400, 400));
+    let third = desktop.add_window("Third", "Notepad", Rect::new(1700, 30, 2000, 300));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+
+    // The wider monitor takes two windows side by side, the narrow one the third
+    let mut distributor = Distributor::new(DistributionConstraints::default());
+    assert!(tracker.sync_distribution(&mut distributor));
+    let plan = tracker.apply_distribution(&distributor);
+    assert_eq!(plan.monitors[0].windows, vec![first, second]);
+    assert_eq!(
+        desktop.window(second).unwrap().rect,
+        Rect::new(800, 0, 1600, 900)
+    );
+    assert_eq!(
+        desktop.window(third).unwrap().rect,
+        Rect::new(1600, 0, 2400, 900)
+    );
+    assert!(!tracker.sync_distribution(&mut distributor));
+
+    // Closing a window regrows the rest
+    desktop.remove_window(second);
+    tracker.remove_window(second);
+    assert!(tracker.sync_distribution(&mut distributor));
+    tracker.apply_distribution(&distributor);
+    assert_eq!(
+        desktop.window(first).unwrap().rect,
+        Rect::new(0, 0, 1600, 900)
+    );
+
+    // And everyone goes back where they started
+    #[cfg(feature = "animation")]
+    {
+        distributor.constraints =
+            DistributionConstraints::default().with_animation(20, e_grid::EasingType::Linear);
+        tracker.restore_distribution(&distributor);
+        // Windows still land on their targets when time runs out between frames
+        std::thread::sleep(std::time::Duration::from_millis(40));
+        tracker.finish_animations(std::time::Duration::from_secs(2));
+    }
+    #[cfg(not(feature = "animation"))]
+    tracker.restore_distribution(&distributor);
+    assert_eq!(
+        desktop.window(first).unwrap().rect,
+        Rect::new(10, 10, 300, 300)
+    );
+    assert_eq!(
+        desktop.window(third).unwrap().rect,
+        Rect::new(1700, 30, 2000, 300)
+    );
+
+    // Restoring at shutdown moves windows back at once, even mid-animation
+    tracker.apply_distribution(&distributor);
+    assert!(tracker.restore_distribution_now(&distributor).is_empty());
+    assert_eq!(
+        desktop.window(first).unwrap().rect,
+        Rect::new(10, 10, 300, 300)
+    );
+    #[cfg(feature = "animation")]
+    assert!(tracker.active_animations.is_empty());
+}
+
+#[test]
+fn test_move_between_monitors_with_different_dpi() {
+    let desktop = Arc::new(SimulatedDesktop::new(vec![
+        Rect::new(0, 0, 1920, 1080),
+        Rect::new(1920, 0, 4800, 1620),
+    ]));
+    desktop.set_monitor_dpi(1, 144);
+    let hwnd = desktop.add_window("Sidebar", "Notepad", Rect::new(0, 0, 480, 540));
+
+    // A 480px sidebar column in logical pixels, 720 physical pixels at 150%
+    let config = GridConfig::with_tracks(
+        vec![TrackSize::Fr(1), TrackSize::Fr(1)],
+        vec![TrackSize::Px(480), TrackSize::Fr(1)],
+    )
+    .in_logical_units();
+    let mut tracker = scanned_tracker(&desktop, config);
+    let target = &tracker.monitor_grids[1];
+    assert_eq!(target.config.col_tracks[0], TrackSize::Px(720));
+    assert_eq!(
+        target.to_logical(&Rect::new(1920, 0, 2640, 810)),
+        Rect::new(1920, 0, 2400, 540)
+    );
+
+    tracker.move_window_to_monitor(hwnd, 1).unwrap();
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(1920, 0, 2640, 810)
+    );
+
+    // And back again lands in the same cell
+    tracker.move_window_to_monitor(hwnd, 0).unwrap();
+    assert_eq!(
+        desktop.window(hwnd).unwrap().rect,
+        Rect::new(0, 0, 480, 540)
+    );
+}
+
+#[test]
+fn test_virtual_grid_with_logical_units() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1920, 1080));
+    desktop.set_monitor_dpi(0, 144);
+    let config = GridConfig::with_tracks(
+        vec![TrackSize::Fr(1)],
+        vec![TrackSize::Px(480), TrackSize::Fr(1)],
+    )
+    .in_logical_units();
+    let mut tracker = scanned_tracker(&desktop, config);
+    tracker
+        .set_occupancy_strategy(None, CellOccupancyStrategy::AnyOverlap)
+        .unwrap();
+
+    // The virtual grid scales logical tracks like the primary monitor's grid does
+    assert_eq!(tracker.virtual_config.col_tracks[0], TrackSize::Px(720));
+    assert_eq!(
+        tracker.window_to_grid_cells(&Rect::new(0, 0, 600, 1080)),
+        vec![(0, 0)]
+    );
+
+    desktop.set_monitor_dpi(0, 96);
+    tracker.refresh_monitor_areas();
+    assert_eq!(tracker.virtual_config.col_tracks[0], TrackSize::Px(480));
+    assert_eq!(
+        tracker.window_to_grid_cells(&Rect::new(0, 0, 600, 1080)),
+        vec![(0, 0), (0, 1)]
+    );
+}
+
+#[cfg(feature = "animation")]
+#[test]
+fn test_window_rules() {
+    use e_grid::config::{RulePlacement, WindowMatcher, WindowRule, WindowRules};
+
+    let desktop = two_monitors();
+    let popup = desktop.add_window("Tooltip", "Popup", Rect::new(10, 10, 60, 40));
+    let video = desktop.add_window("Picture in picture", "Chrome", Rect::new(0, 0, 400, 300));
+    let editor = SimulatedWindow {
+        executable: "notepad.exe".to_string(),
+        ..SimulatedWindow::new(0x2000, "notes.txt", "Notepad", Rect::new(10, 10, 500, 500))
+    };
+    desktop.insert_window(editor);
+    let mut tracker = WindowTracker::new_with_backend(GridConfig::new(2, 2), desktop.clone());
+    let editors = WindowRule::new("editors", WindowMatcher::executable("Notepad.exe"))
+        .on_monitor(1)
+        .with_placement(RulePlacement::Span {
+            start: (0, 1),
+            end: (1, 1),
+        })
+        .with_easing(e_grid::EasingType::Bounce);
+    tracker.set_window_rules(
+        WindowRules::default()
+            .with_rule(
+                WindowRule::new("popups", WindowMatcher::default().with_max_size(100, 100))
+                    .ignored(),
+            )
+            .with_rule(
+                WindowRule::new(
+                    "pip",
+                    WindowMatcher::title("(?i)^picture in picture$").unwrap(),
+                )
+                .floating(),
+            )
+            .with_rule(editors),
+    );
+
+    // A scan applies the rules to every window it finds
+    tracker.scan_existing_windows();
+    assert!(!tracker.windows.contains_key(&popup));
+    assert!(tracker.ignored_windows.contains(&popup));
+    assert!(tracker.floating_windows.contains(&video));
+    assert_eq!(
+        desktop.window(0x2000).unwrap().rect,
+        Rect::new(1800, 0, 2400, 800)
+    );
+    assert_eq!(tracker.window_easings[&0x2000], e_grid::EasingType::Bounce);
+
+    // Windows created later are matched by their initial size
+    let big_popup = desktop.add_window("Tooltip", "Popup", Rect::new(10, 10, 600, 400));
+    tracker.add_window(big_popup);
+    assert_eq!(tracker.apply_window_rules(big_popup), Ok(None));
+    assert!(tracker.windows.contains_key(&big_popup));
+
+    // A popup that grew out of its rule is tracked again at the next rescan
+    desktop.set_window_rect(popup, Rect::new(10, 10, 600, 400));
+    tracker.scan_existing_windows();
+    assert!(tracker.windows.contains_key(&popup));
+    assert!(tracker.ignored_windows.is_empty());
+}
+
+#[test]
+fn test_window_filter() {
+    use e_grid::config::{TitlePattern, WindowFilter, WindowPredicate};
+
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1200, 800));
+    let editor = desktop.add_window("notes.txt", "Notepad", Rect::new(0, 0, 600, 800));
+    let popup = SimulatedWindow {
+        style: e_grid::config::window_filter::styles::WS_POPUP,
+        ..SimulatedWindow::new(
+            0x2000,
+            "Slack",
+            "Chrome_WidgetWin_1",
+            Rect::new(600, 0, 1200, 800),
+        )
+    };
+    desktop.insert_window(popup);
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    assert!(tracker.windows.contains_key(&editor));
+    assert!(!tracker.windows.contains_key(&0x2000));
+    assert_eq!(
+        tracker.explain_window(0x2000).rule.as_deref(),
+        Some("borderless Chrome popups")
+    );
+
+    // Including the popup and excluding the editor swaps which one is tracked
+    let filter = WindowFilter::default()
+        .with_include(
+            "slack",
+            WindowPredicate::Title(TitlePattern::new("^Slack$").unwrap()),
+        )
+        .with_exclude("editors", WindowPredicate::Class("Notepad".to_string()));
+    assert_eq!(tracker.set_window_filter(filter), (1, 1));
+    assert!(tracker.windows.contains_key(&0x2000));
+    assert!(!tracker.windows.contains_key(&editor));
+    assert_eq!(
+        tracker.explain_window(editor).to_string(),
+        "rejected by rule 'editors'"
+    );
+}
+
+#[cfg(feature = "animation")]
+#[test]
+fn test_workspaces() {
+    use e_grid::grid::WorkspaceHideMode;
+
+    let desktop = two_monitors();
+    let editor = desktop.add_window("Editor", "Notepad", Rect::new(0, 0, 600, 800));
+    let browser = desktop.add_window("Browser", "Chrome", Rect::new(600, 0, 1200, 800));
+    let chat = desktop.add_window("Chat", "Slack", Rect::new(1200, 0, 1800, 400));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    let easing = e_grid::EasingType::Linear;
+    let duration = std::time::Duration::from_millis(100);
+
+    // Switching to an empty workspace puts the monitor's windows away
+    tracker.create_workspace("code").unwrap();
+    assert!(tracker.create_workspace("code").is_err());
+    assert_eq!(tracker.switch_workspace(0, "code", duration, easing), Ok(0));
+    assert!(desktop.window(editor).unwrap().is_minimized);
+    assert!(desktop.window(browser).unwrap().is_minimized);
+    assert!(!tracker.windows.contains_key(&editor));
+    assert!(tracker.windows.contains_key(&chat));
+    assert_eq!(tracker.workspace_monitor("code"), Some(0));
+    assert_eq!(tracker.workspaces["Monitor 0"].windows.len(), 2);
+
+    // Moving the chat window into the shown workspace brings it onto monitor 0
+    tracker
+        .move_window_to_workspace(chat, "code", duration, easing)
+        .unwrap();
+    tracker.finish_animations(duration * 4);
+    assert_eq!(
+        desktop.window(chat).unwrap().rect,
+        Rect::new(0, 0, 600, 400)
+    );
+
+    // Switching back restores the saved windows into their cells
+    desktop.set_window_rect(editor, Rect::new(-32000, -32000, -31840, -31970));
+    tracker.workspace_hide_mode = WorkspaceHideMode::Hide;
+    assert_eq!(
+        tracker.switch_workspace(0, "Monitor 0", duration, easing),
+        Ok(2)
+    );
+    tracker.finish_animations(duration * 4);
+    assert!(!desktop.window(editor).unwrap().is_minimized);
+    assert_eq!(
+        desktop.window(editor).unwrap().rect,
+        Rect::new(0, 0, 600, 800)
+    );
+    assert!(!desktop.window(chat).unwrap().is_visible);
+    assert!(tracker.windows.contains_key(&browser));
+
+    // A workspace can only be shown on one monitor at a time
+    assert!(tracker
+        .switch_workspace(1, "Monitor 0", duration, easing)
+        .is_err());
+    let summaries = tracker.list_workspaces();
+    assert_eq!(
+        summaries
+            .iter()
+            .map(|w| (w.name.as_str(), w.monitor_id, w.window_count))
+            .collect::<Vec<_>>(),
+        vec![("Monitor 0", Some(0), 2), ("code", None, 1)]
+    );
+}
+
+#[cfg(feature = "animation")]
+#[test]
+fn test_scratchpads() {
+    use e_grid::backend::WindowBackend;
+
+    let desktop = two_monitors();
+    let terminal = SimulatedWindow {
+        executable: "wt.exe".to_string(),
+        ..SimulatedWindow::new(0x3000, "PowerShell", "Terminal", Rect::new(0, 0, 1200, 400))
+    };
+    desktop.insert_window(terminal);
+    let browser = desktop.add_window("Browser", "Chrome", Rect::new(1200, 0, 2400, 800));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    let easing = e_grid::EasingType::Linear;
+    let duration = std::time::Duration::from_millis(100);
+
+    // Sending a window hides it and remembers its identity and cells
+    tracker.send_to_scratchpad(0x3000, "term").unwrap();
+    assert!(!desktop.window(0x3000).unwrap().is_visible);
+    assert!(!tracker.windows.contains_key(&0x3000));
+    assert!(tracker.send_to_scratchpad(browser, "term").is_err());
+    let scratchpad = tracker.scratchpads.get("term").unwrap();
+    assert_eq!((scratchpad.start, scratchpad.end), ((0, 0), (0, 1)));
+
+    // Toggling drops it into its cells on the focused monitor, floating over tiling
+    desktop.bring_to_front(browser);
+    assert_eq!(
+        tracker.toggle_scratchpad("term", duration, easing),
+        Ok(true)
+    );
+    assert_eq!(
+        tracker.active_animations.get(&0x3000).unwrap().start_rect,
+        Rect::new(1200, -400, 2400, 0)
+    );
+    tracker.finish_animations(duration * 4);
+    let shown = desktop.window(0x3000).unwrap();
+    assert!(shown.is_visible);
+    assert_eq!(shown.rect, Rect::new(1200, 0, 2400, 400));
+    assert_eq!(desktop.get_foreground_window(), Some(0x3000));
+    assert!(tracker.floating_windows.contains(&0x3000));
+    assert_eq!(
+        tracker.toggle_scratchpad("term", duration, easing),
+        Ok(false)
+    );
+    assert!(!desktop.window(0x3000).unwrap().is_visible);
+
+    // After a restart the hidden terminal is found again by its identity
+    let mut restarted = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    assert_eq!(restarted.set_scratchpads(tracker.scratchpads.clone()), 1);
+    assert_eq!(restarted.scratchpad_windows["term"], 0x3000);
+    assert!(restarted
+        .toggle_scratchpad("missing", duration, easing)
+        .is_err());
+    assert_eq!(
+        restarted.toggle_scratchpad("term", duration, easing),
+        Ok(true)
+    );
+}
+
+#[cfg(feature = "animation")]
+#[test]
+fn test_directional_navigation() {
+    use e_grid::backend::WindowBackend;
+    use e_grid::grid::Direction;
+
+    let desktop = two_monitors();
+    let editor = desktop.add_window("Editor", "Notepad", Rect::new(0, 0, 600, 800));
+    let browser = desktop.add_window("Browser", "Chrome", Rect::new(600, 0, 1200, 400));
+    let terminal = desktop.add_window("Terminal", "Console", Rect::new(600, 400, 1200, 800));
+    let chat = desktop.add_window("Chat", "Slack", Rect::new(1200, 0, 1800, 800));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    let easing = e_grid::EasingType::Linear;
+    let duration = std::time::Duration::from_millis(100);
+
+    // Neighbours on the same monitor come first, then the search crosses monitors
+    assert_eq!(
+        tracker.neighbour_window(editor, Direction::Right),
+        Some(browser)
+    );
+    assert_eq!(
+        tracker.neighbour_window(browser, Direction::Right),
+        Some(chat)
+    );
+    assert_eq!(
+        tracker.neighbour_window(chat, Direction::Left),
+        Some(editor)
+    );
+    assert_eq!(tracker.neighbour_window(editor, Direction::Left), None);
+    assert_eq!(
+        tracker.focus_neighbour(terminal, Direction::Up),
+        Ok(browser)
+    );
+    assert_eq!(desktop.get_foreground_window(), Some(browser));
+
+    assert_eq!(
+        tracker
+            .swap_window_in_direction(browser, Direction::Down, duration, easing)
+            .map(|(neighbour, _)| neighbour),
+        Ok(terminal)
+    );
+    assert!(tracker
+        .resize_window_span(editor, Direction::Up, true, duration, easing)
+        .is_err());
+    tracker
+        .resize_window_span(editor, Direction::Down, false, duration, easing)
+        .unwrap();
+    tracker.finish_animations(duration * 4);
+    assert_eq!(
+        desktop.window(browser).unwrap().rect,
+        Rect::new(600, 400, 1200, 800)
+    );
+    assert_eq!(
+        desktop.window(terminal).unwrap().rect,
+        Rect::new(600, 0, 1200, 400)
+    );
+    assert_eq!(
+        desktop.window(editor).unwrap().rect,
+        Rect::new(0, 0, 600, 400)
+    );
+
+    // Moving off the edge of a grid enters the next monitor at its near edge
+    assert_eq!(
+        tracker.move_window_in_direction(chat, Direction::Left, duration, easing),
+        Ok(0)
+    );
+    tracker.finish_animations(duration * 4);
+    assert_eq!(
+        desktop.window(chat).unwrap().rect,
+        Rect::new(600, 0, 1200, 800)
+    );
+    assert!(tracker
+        .move_window_in_direction(chat, Direction::Up, duration, easing)
+        .is_err());
+}
+
+#[cfg(feature = "animation")]
+#[test]
+fn test_swap_and_rotate_batches() {
+    use e_grid::grid::RotationDirection;
+
+    let desktop = two_monitors();
+    let top_left = desktop.add_window("A", "Notepad", Rect::new(0, 0, 600, 400));
+    let top_right = desktop.add_window("B", "Notepad", Rect::new(600, 0, 1200, 400));
+    let bottom_right = desktop.add_window("C", "Notepad", Rect::new(600, 400, 1200, 800));
+    let bottom_left = desktop.add_window("D", "Notepad", Rect::new(0, 400, 600, 800));
+    let chat = desktop.add_window("Chat", "Slack", Rect::new(1200, 0, 1800, 800));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+    let easing = e_grid::EasingType::Linear;
+    let duration = std::time::Duration::from_millis(100);
+    let rect = |hwnd| desktop.window(hwnd).unwrap().rect;
+
+    // Clockwise, every window takes the place of the next one round the monitor
+    let batch = tracker
+        .rotate_monitor_windows(0, RotationDirection::Clockwise, duration, easing)
+        .unwrap();
+    assert!(tracker.finished_animation_batches().is_empty());
+    tracker.finish_animations(duration * 4);
+    let finished = tracker.finished_animation_batches();
+    assert_eq!(finished.len(), 1);
+    assert_eq!((finished[0].id, finished[0].window_count), (batch, 4));
+    assert_eq!(rect(top_left), Rect::new(600, 0, 1200, 400));
+    assert_eq!(rect(top_right), Rect::new(600, 400, 1200, 800));
+    assert_eq!(rect(bottom_right), Rect::new(0, 400, 600, 800));
+    assert_eq!(rect(bottom_left), Rect::new(0, 0, 600, 400));
+    assert!(tracker.finished_animation_batches().is_empty());
+
+    // Across monitors, windows keep their cells on the next monitor
+    tracker
+        .rotate_windows_across_monitors(RotationDirection::Clockwise, duration, easing)
+        .unwrap();
+    tracker.finish_animations(duration * 4);
+    assert_eq!(tracker.finished_animation_batches()[0].window_count, 5);
+    assert_eq!(rect(chat), Rect::new(0, 0, 600, 800));
+    assert_eq!(rect(top_left), Rect::new(1800, 0, 2400, 400));
+
+    assert!(tracker.swap_windows(chat, chat, duration, easing).is_err());
+    tracker
+        .swap_windows(chat, top_left, duration, easing)
+        .unwrap();
+    tracker.finish_animations(duration * 4);
+    assert_eq!(rect(chat), Rect::new(1800, 0, 2400, 400));
+    assert_eq!(rect(top_left), Rect::new(0, 0, 600, 800));
+    assert_eq!(tracker.finished_animation_batches()[0].window_count, 2);
+}
+
+#[test]
+fn test_window_groups() {
+    let desktop = Arc::new(SimulatedDesktop::single_monitor(1200, 800));
+    let editor = desktop.add_window("Editor", "Notepad", Rect::new(0, 0, 600, 800));
+    let browser = desktop.add_window("Browser", "Chrome", Rect::new(600, 0, 1200, 800));
+    let notes = desktop.add_window("Notes", "Notepad", Rect::new(0, 0, 300, 300));
+    let mut tracker = scanned_tracker(&desktop, GridConfig::new(2, 2));
+
+    // Joining a window takes over its place; the other member is hidden and untracked
+    assert_eq!(tracker.add_to_window_group(notes, editor), Ok(1));
+    assert!(tracker.add_to_window_group(notes, editor).is_err());
+    assert_eq!(
+        desktop.window(notes).unwrap().rect,
+        Rect::new(0, 0, 600, 800)
+    );
+    assert!(!desktop.window(editor).unwrap().is_visible);
+    assert!(!tracker.windows.contains_key(&editor));
+    assert!(tracker.is_hidden_group_member(editor));
+    let memberships = tracker.group_memberships();
+    assert_eq!(
+        (memberships[&notes].index, memberships[&notes].size),
+        (1, 2)
+    );
+    assert!(memberships[&notes].is_active);
+
+    // The group moves as a unit: cycling shows the next member where the last was
+    desktop.set_window_rect(notes, Rect::new(0, 0, 600, 400));
+    assert_eq!(tracker.cycle_window_group(notes, true), Ok(editor));
+    assert_eq!(
+        desktop.window(editor).unwrap().rect,
+        Rect::new(0, 0, 600, 400)
+    );
+    assert!(desktop.window(editor).unwrap().is_visible);
+    assert!(!desktop.window(notes).unwrap().is_visible);
+    assert_eq!(tracker.activate_window_group_member(notes), Ok(1));
+    assert!(tracker.windows.contains_key(&notes));
+
+    // Hidden members stay where they were hidden, but are reported in the group's
+    // span and come out in it when they leave the group
+    assert_eq!(tracker.add_to_window_group(browser, editor), Ok(1));
+    assert_eq!(
+        tracker.window_group_of(editor).unwrap().members,
+        vec![editor, notes, browser]
+    );
+    desktop.set_window_rect(browser, Rect::new(600, 400, 1200, 800));
+    let hidden = tracker.hidden_group_member_infos();
+    assert_eq!(hidden.len(), 2);
+    assert!(hidden
+        .iter()
+        .all(|info| info.window_rect.0 == Rect::new(600, 400, 1200, 800) && !info.is_visible));
+    assert_eq!(tracker.remove_from_window_group(notes), Ok(1));
+    assert_eq!(
+        desktop.window(notes).unwrap().rect,
+        Rect::new(600, 400, 1200, 800)
+    );
+    assert!(tracker.windows.contains_key(&notes));
+
+    // Removing the active member leaves it on screen and shows the next one in its
+    // place; a group left with one window is dissolved
+    assert_eq!(tracker.remove_from_window_group(browser), Ok(1));
+    assert!(desktop.window(browser).unwrap().is_visible);
+    assert!(desktop.window(editor).unwrap().is_visible);
+    assert!(tracker.window_group_of(editor).is_none());
+
+    // A group whose other members closed is dissolved
+    assert_eq!(tracker.add_to_window_group(notes, editor), Ok(2));
+    desktop.remove_window(editor);
+    assert!(tracker.cycle_window_group(notes, true).is_err());
+    assert!(tracker.window_group_of(notes).is_none());
+    assert!(tracker.remove_from_window_group(notes).is_err());
+
+    // Groups stop growing at the most members a group message can list
+    let tabs: Vec<u64> = (0..MAX_GROUP_MEMBERS)
+        .map(|i| desktop.add_window(&format!("Tab {}", i), "Notepad", Rect::new(0, 0, 100, 100)))
+        .collect();
+    for tab in &tabs[1..] {
+        assert_eq!(tracker.add_to_window_group(*tab, notes), Ok(3));
+    }
+    assert!(tracker.add_to_window_group(tabs[0], notes).is_err());
+    assert_eq!(
+        tracker.window_group_of(notes).unwrap().members.len(),
+        MAX_GROUP_MEMBERS
+    );
+}